@@ -26,17 +26,53 @@ impl BumpAllocator {
         }
     }
     
-    /// Initialise l'allocateur avec une zone mémoire.
-    /// 
+    /// Initialise l'allocateur avec une zone mémoire (ex: une région définie
+    /// par le script de l'éditeur de liens).
+    ///
+    /// Doit être appelé exactement une fois, avant la première allocation.
+    /// Sans la feature `static-heap`, toute allocation échoue tant que
+    /// l'allocateur n'est pas initialisé ; avec, un heap statique de 64KB est
+    /// utilisé si `init` n'a pas été appelé avant la première allocation.
+    ///
     /// # Safety
     /// - Doit être appelé une seule fois avant toute allocation
-    /// - `heap_start` et `heap_size` doivent pointer vers une zone mémoire valide
-    unsafe fn init(&self, heap_start: usize, heap_size: usize) {
+    /// - `heap_start` et `heap_size` doivent pointer vers une zone mémoire valide,
+    ///   inutilisée par ailleurs
+    /// - `heap_start` ne doit pas être nul
+    pub unsafe fn init(&self, heap_start: usize, heap_size: usize) {
         let heap = &mut *self.heap.get();
         heap.start = heap_start;
-        heap.end = heap_start + heap_size;
+        heap.end = heap_start.saturating_add(heap_size);
         heap.next = heap_start;
     }
+
+    /// Libère tout le heap d'un coup : la prochaine allocation repart du début.
+    ///
+    /// Toutes les allocations précédentes doivent avoir été rendues (ou ne plus
+    /// être utilisées) : leur mémoire sera réattribuée. Typiquement appelé
+    /// entre deux opérations sur le système de fichiers.
+    pub fn reset(&self) {
+        // Pas d'appel concurrent possible : l'allocateur n'est utilisé que
+        // depuis un seul contexte d'exécution (voir `impl Sync`)
+        let heap = unsafe { &mut *self.heap.get() };
+        heap.next = heap.start;
+    }
+
+    /// Nombre d'octets déjà attribués (alignement compris).
+    ///
+    /// Vaut 0 tant que l'allocateur n'est pas initialisé.
+    pub fn used(&self) -> usize {
+        let heap = unsafe { &*self.heap.get() };
+        heap.next - heap.start
+    }
+
+    /// Nombre d'octets encore disponibles avant la fin du heap.
+    ///
+    /// Vaut 0 tant que l'allocateur n'est pas initialisé.
+    pub fn remaining(&self) -> usize {
+        let heap = unsafe { &*self.heap.get() };
+        heap.end - heap.next
+    }
 }
 
 unsafe impl Sync for BumpAllocator {}
@@ -50,16 +86,22 @@ unsafe impl GlobalAlloc for BumpAllocator {
         let heap = &mut *self.heap.get();
         
         // Initialisation lazy au premier appel
+        #[cfg(feature = "static-heap")]
         if heap.start == 0 {
             self.init(
                 core::ptr::addr_of!(HEAP_MEMORY) as usize,
-                65536,
+                STATIC_HEAP_SIZE,
             );
         }
         
-        // Aligne le pointeur selon les besoins du Layout
-        let alloc_start = align_up(heap.next, layout.align());
-        let alloc_end = alloc_start.saturating_add(layout.size());
+        // Aligne le pointeur selon les besoins du Layout ; un débordement
+        // d'adresse signifie qu'il n'y a plus de place
+        let Some(alloc_start) = align_up(heap.next, layout.align()) else {
+            return ptr::null_mut();
+        };
+        let Some(alloc_end) = alloc_start.checked_add(layout.size()) else {
+            return ptr::null_mut();
+        };
 
         // Vérifie qu'on ne dépasse pas la fin du heap
         if alloc_end > heap.end {
@@ -79,17 +121,225 @@ unsafe impl GlobalAlloc for BumpAllocator {
     }
 }
 
+/// Allocateur à liste chaînée de blocs libres : contrairement au
+/// [`BumpAllocator`], `dealloc` rend la mémoire, qui peut être réutilisée.
+///
+/// Chaque bloc libre commence par un en-tête stocké dans la mémoire libre
+/// elle-même : la taille du bloc (en-tête compris) et un pointeur vers le
+/// bloc libre suivant. La liste est triée par adresse, ce qui permet de
+/// fusionner un bloc libéré avec ses voisins immédiats et de limiter la
+/// fragmentation. L'allocation parcourt la liste et prend le premier bloc
+/// assez grand (first fit) : son coût est linéaire en nombre de blocs libres.
+///
+/// Comme le [`BumpAllocator`], il n'est pas protégé contre les accès
+/// concurrents (cible mono-cœur sans interruptions qui allouent).
+pub struct LinkedListAllocator {
+    heap: UnsafeCell<FreeList>,
+}
+
+// En-tête d'un bloc libre : sa taille (en-tête compris) et le bloc libre
+// suivant, à une adresse plus haute.
+struct FreeBlock {
+    size: usize,
+    next: *mut FreeBlock,
+}
+
+struct FreeList {
+    initialized: bool,
+    // Premier bloc libre (le plus bas en mémoire), ou null si le heap est plein.
+    first: *mut FreeBlock,
+}
+
+// Plus petit bloc géré : il doit pouvoir contenir un en-tête une fois libéré.
+const MIN_BLOCK_SIZE: usize = core::mem::size_of::<FreeBlock>();
+const BLOCK_ALIGN: usize = core::mem::align_of::<FreeBlock>();
+
+impl LinkedListAllocator {
+    /// Crée un nouvel allocateur vide (sera initialisé au premier appel).
+    pub const fn empty() -> Self {
+        Self {
+            heap: UnsafeCell::new(FreeList {
+                initialized: false,
+                first: ptr::null_mut(),
+            }),
+        }
+    }
+
+    /// Initialise l'allocateur avec une zone mémoire.
+    ///
+    /// Mêmes règles que [`BumpAllocator::init`] : un seul appel, avant la
+    /// première allocation.
+    ///
+    /// # Safety
+    /// - Doit être appelé une seule fois avant toute allocation
+    /// - `heap_start` et `heap_size` doivent pointer vers une zone mémoire valide,
+    ///   inutilisée par ailleurs
+    pub unsafe fn init(&self, heap_start: usize, heap_size: usize) {
+        let list = &mut *self.heap.get();
+        list.initialized = true;
+        list.first = ptr::null_mut();
+
+        // Le premier en-tête doit être aligné
+        let Some(start) = align_up(heap_start, BLOCK_ALIGN) else {
+            return;
+        };
+        let size = heap_start.saturating_add(heap_size).saturating_sub(start) & !(BLOCK_ALIGN - 1);
+        if size >= MIN_BLOCK_SIZE {
+            list.first = write_block(start, size, ptr::null_mut());
+        }
+    }
+
+    // Taille réellement réservée pour `layout` : au moins un en-tête, et un
+    // multiple de l'alignement des en-têtes pour que le reste d'un bloc
+    // découpé puisse encore en accueillir un.
+    fn block_size(layout: &Layout) -> Option<usize> {
+        align_up(layout.size().max(MIN_BLOCK_SIZE), BLOCK_ALIGN)
+    }
+
+    // Cherche dans le bloc libre [`addr`, `addr + size`) une place pour `size`
+    // octets alignés sur `align`. Les morceaux qui restent avant et après
+    // doivent être vides ou pouvoir contenir un en-tête.
+    fn fit(addr: usize, region_size: usize, size: usize, align: usize) -> Option<usize> {
+        let mut start = align_up(addr, align)?;
+        if start != addr && start - addr < MIN_BLOCK_SIZE {
+            start = align_up(addr + MIN_BLOCK_SIZE, align)?;
+        }
+        let end = start.checked_add(size)?;
+        let region_end = addr + region_size;
+        if end > region_end {
+            return None;
+        }
+        let rest = region_end - end;
+        if rest != 0 && rest < MIN_BLOCK_SIZE {
+            return None;
+        }
+        Some(start)
+    }
+}
+
+// Écrit un en-tête de bloc libre à `addr` et retourne son pointeur.
+unsafe fn write_block(addr: usize, size: usize, next: *mut FreeBlock) -> *mut FreeBlock {
+    let block = addr as *mut FreeBlock;
+    block.write(FreeBlock { size, next });
+    block
+}
+
+unsafe impl Sync for LinkedListAllocator {}
+
+unsafe impl GlobalAlloc for LinkedListAllocator {
+    /// Alloue dans le premier bloc libre assez grand, découpé si besoin.
+    ///
+    /// # Safety
+    /// Retourne null si aucun bloc libre ne convient.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let list = &mut *self.heap.get();
+
+        // Initialisation lazy au premier appel
+        #[cfg(feature = "static-heap")]
+        if !list.initialized {
+            self.init(core::ptr::addr_of!(HEAP_MEMORY) as usize, STATIC_HEAP_SIZE);
+        }
+
+        let Some(size) = Self::block_size(&layout) else {
+            return ptr::null_mut();
+        };
+        // `link` pointe sur le champ qui référence le bloc courant
+        let mut link: *mut *mut FreeBlock = &mut list.first;
+        while !(*link).is_null() {
+            let block = *link;
+            let addr = block as usize;
+            let FreeBlock { size: region_size, next } = block.read();
+
+            if let Some(start) = Self::fit(addr, region_size, size, layout.align()) {
+                let end = start + size;
+                let region_end = addr + region_size;
+
+                // Morceau après l'allocation, puis morceau avant
+                let mut rest = next;
+                if end < region_end {
+                    rest = write_block(end, region_end - end, rest);
+                }
+                if start > addr {
+                    rest = write_block(addr, start - addr, rest);
+                }
+                *link = rest;
+                return start as *mut u8;
+            }
+            link = &mut (*block).next;
+        }
+        ptr::null_mut()
+    }
+
+    /// Remet le bloc dans la liste et le fusionne avec ses voisins libres.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let list = &mut *self.heap.get();
+        let addr = ptr as usize;
+        // Le calcul a réussi lors de l'allocation du même `layout`
+        let Some(size) = Self::block_size(&layout) else {
+            return;
+        };
+
+        // Bloc libre précédent (adresse plus basse) et suivant
+        let mut prev: *mut FreeBlock = ptr::null_mut();
+        let mut next = list.first;
+        while !next.is_null() && (next as usize) < addr {
+            prev = next;
+            next = (*next).next;
+        }
+
+        let block = write_block(addr, size, next);
+        if !next.is_null() && addr + size == next as usize {
+            (*block).size += (*next).size;
+            (*block).next = (*next).next;
+        }
+
+        if prev.is_null() {
+            list.first = block;
+        } else if prev as usize + (*prev).size == addr {
+            (*prev).size += (*block).size;
+            (*prev).next = (*block).next;
+        } else {
+            (*prev).next = block;
+        }
+    }
+}
+
 /// Arrondit `addr` au multiple supérieur de `align`.
-fn align_up(addr: usize, align: usize) -> usize {
-    (addr + align - 1) & !(align - 1)
+///
+/// Retourne `None` si le résultat dépasse `usize::MAX` (adresse proche de la
+/// fin de l'espace d'adressage) au lieu de revenir à une petite valeur.
+///
+/// # Panics
+/// Si `align` n'est pas une puissance de deux (ce que `Layout` garantit).
+fn align_up(addr: usize, align: usize) -> Option<usize> {
+    assert!(align.is_power_of_two(), "align_up: alignement non puissance de deux");
+    addr.checked_add(align - 1).map(|end| end & !(align - 1))
 }
 
+/// Taille du heap statique utilisé par défaut (feature `static-heap`).
+#[cfg(feature = "static-heap")]
+pub const STATIC_HEAP_SIZE: usize = 65536;
+
 // Zone de heap statique (pour la démo - 64KB)
-static mut HEAP_MEMORY: [u8; 65536] = [0; 65536];
+#[cfg(feature = "static-heap")]
+static mut HEAP_MEMORY: [u8; STATIC_HEAP_SIZE] = [0; STATIC_HEAP_SIZE];
+
+// En environnement hébergé (feature `std`), l'allocateur du système reste en place.
+// La feature `linked-list` remplace le bump allocator par un allocateur qui
+// réutilise la mémoire libérée.
+//
+// L'allocateur global est public pour pouvoir lui fournir sa zone mémoire :
+// `unsafe { GLOBAL_ALLOCATOR.init(debut, taille) }` au démarrage.
+
+/// Allocateur global de la crate (`BumpAllocator`).
+#[cfg(not(any(test, feature = "std", feature = "linked-list")))]
+#[global_allocator]
+pub static GLOBAL_ALLOCATOR: BumpAllocator = BumpAllocator::empty();
 
-#[cfg(not(test))]
+/// Allocateur global de la crate (`LinkedListAllocator`, feature `linked-list`).
+#[cfg(all(feature = "linked-list", not(any(test, feature = "std"))))]
 #[global_allocator]
-static GLOBAL_ALLOCATOR: BumpAllocator = BumpAllocator::empty();
+pub static GLOBAL_ALLOCATOR: LinkedListAllocator = LinkedListAllocator::empty();
 
 #[cfg(test)]
 mod tests {
@@ -97,24 +347,51 @@ mod tests {
 
     #[test]
     fn test_align_up() {
-        assert_eq!(align_up(0, 4), 0);
-        assert_eq!(align_up(1, 4), 4);
-        assert_eq!(align_up(4, 4), 4);
-        assert_eq!(align_up(5, 4), 8);
+        assert_eq!(align_up(0, 4), Some(0));
+        assert_eq!(align_up(1, 4), Some(4));
+        assert_eq!(align_up(4, 4), Some(4));
+        assert_eq!(align_up(5, 4), Some(8));
     }
 
     #[test]
     fn test_align_up_power_of_two() {
-        assert_eq!(align_up(10, 8), 16);
-        assert_eq!(align_up(17, 16), 32);
-        assert_eq!(align_up(100, 64), 128);
+        assert_eq!(align_up(10, 8), Some(16));
+        assert_eq!(align_up(17, 16), Some(32));
+        assert_eq!(align_up(100, 64), Some(128));
     }
 
     #[test]
     fn test_align_up_already_aligned() {
-        assert_eq!(align_up(16, 16), 16);
-        assert_eq!(align_up(32, 8), 32);
-        assert_eq!(align_up(64, 32), 64);
+        assert_eq!(align_up(16, 16), Some(16));
+        assert_eq!(align_up(32, 8), Some(32));
+        assert_eq!(align_up(64, 32), Some(64));
+    }
+
+    #[test]
+    fn test_align_up_overflow() {
+        assert_eq!(align_up(usize::MAX, 1), Some(usize::MAX));
+        assert_eq!(align_up(usize::MAX, 2), None);
+        assert_eq!(align_up(usize::MAX - 7, 8), Some(usize::MAX - 7));
+        assert_eq!(align_up(usize::MAX - 6, 8), None);
+        assert_eq!(align_up(usize::MAX - 100, 4096), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_align_up_rejects_non_power_of_two() {
+        align_up(10, 3);
+    }
+
+    #[test]
+    fn test_bump_alloc_near_address_space_end() {
+        let allocator = BumpAllocator::empty();
+        unsafe {
+            // Heap fictif collé à la fin de l'espace d'adressage : l'alignement
+            // déborderait et rendrait un pointeur sous le heap
+            allocator.init(usize::MAX - 15, 15);
+            let layout = Layout::from_size_align(8, 32).unwrap();
+            assert!(allocator.alloc(layout).is_null());
+        }
     }
 
     #[test]
@@ -139,4 +416,133 @@ mod tests {
             assert_ne!(ptr1, ptr2);
         }
     }
+
+    #[test]
+    fn test_bump_reset_and_stats() {
+        let allocator = BumpAllocator::empty();
+        assert_eq!((allocator.used(), allocator.remaining()), (0, 0));
+
+        unsafe {
+            allocator.init(0x1000, 1024);
+            let layout = Layout::from_size_align(100, 8).unwrap();
+            let ptr1 = allocator.alloc(layout);
+            assert_eq!(ptr1 as usize, 0x1000);
+            allocator.alloc(layout);
+            // 100 octets, puis 4 de remplissage pour aligner la seconde
+            assert_eq!(allocator.used(), 204);
+            assert_eq!(allocator.remaining(), 1024 - 204);
+
+            allocator.reset();
+            assert_eq!((allocator.used(), allocator.remaining()), (0, 1024));
+            assert_eq!(allocator.alloc(layout), ptr1);
+        }
+    }
+
+    #[test]
+    fn test_explicit_init_takes_precedence() {
+        let allocator = BumpAllocator::empty();
+        unsafe {
+            // Initialisé avant la première allocation : le heap statique
+            // éventuel n'est pas utilisé
+            allocator.init(0x2000, 64);
+            let layout = Layout::from_size_align(64, 8).unwrap();
+            assert_eq!(allocator.alloc(layout) as usize, 0x2000);
+            assert!(allocator.alloc(layout).is_null());
+        }
+    }
+
+    #[cfg(not(feature = "static-heap"))]
+    #[test]
+    fn test_uninitialized_allocator_fails() {
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        unsafe {
+            assert!(BumpAllocator::empty().alloc(layout).is_null());
+            assert!(LinkedListAllocator::empty().alloc(layout).is_null());
+        }
+    }
+
+    // Zone mémoire de test pour l'allocateur à liste chaînée
+    #[repr(align(16))]
+    struct Arena([u8; 1024]);
+
+    fn linked_list_on(arena: &mut Arena) -> LinkedListAllocator {
+        let allocator = LinkedListAllocator::empty();
+        unsafe { allocator.init(arena.0.as_mut_ptr() as usize, arena.0.len()) };
+        allocator
+    }
+
+    #[test]
+    fn test_linked_list_reuses_freed_memory() {
+        let mut arena = Arena([0; 1024]);
+        let allocator = linked_list_on(&mut arena);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        unsafe {
+            let ptr1 = allocator.alloc(layout);
+            let ptr2 = allocator.alloc(layout);
+            assert!(!ptr1.is_null() && !ptr2.is_null());
+            assert_ne!(ptr1, ptr2);
+
+            allocator.dealloc(ptr1, layout);
+            assert_eq!(allocator.alloc(layout), ptr1);
+        }
+    }
+
+    #[test]
+    fn test_linked_list_many_cycles() {
+        let mut arena = Arena([0; 1024]);
+        let allocator = linked_list_on(&mut arena);
+        let layout = Layout::from_size_align(512, 8).unwrap();
+
+        // Un bump allocator serait épuisé au bout de deux tours
+        for _ in 0..100 {
+            unsafe {
+                let ptr = allocator.alloc(layout);
+                assert!(!ptr.is_null());
+                allocator.dealloc(ptr, layout);
+            }
+        }
+    }
+
+    #[test]
+    fn test_linked_list_coalesces_neighbours() {
+        let mut arena = Arena([0; 1024]);
+        let allocator = linked_list_on(&mut arena);
+        let quarter = Layout::from_size_align(256, 8).unwrap();
+        let whole = Layout::from_size_align(1024, 8).unwrap();
+
+        unsafe {
+            let ptrs = [
+                allocator.alloc(quarter),
+                allocator.alloc(quarter),
+                allocator.alloc(quarter),
+                allocator.alloc(quarter),
+            ];
+            assert!(ptrs.iter().all(|p| !p.is_null()));
+            assert!(allocator.alloc(quarter).is_null());
+
+            // Libérés dans le désordre : les quatre blocs doivent fusionner
+            for i in [1, 3, 0, 2] {
+                allocator.dealloc(ptrs[i], quarter);
+            }
+            assert_eq!(allocator.alloc(whole), ptrs[0]);
+        }
+    }
+
+    #[test]
+    fn test_linked_list_alignment() {
+        let mut arena = Arena([0; 1024]);
+        let allocator = linked_list_on(&mut arena);
+
+        unsafe {
+            let small = allocator.alloc(Layout::from_size_align(8, 8).unwrap());
+            let aligned = allocator.alloc(Layout::from_size_align(32, 128).unwrap());
+            assert!(!small.is_null() && !aligned.is_null());
+            assert_eq!(aligned as usize % 128, 0);
+
+            // Le trou laissé avant le bloc aligné reste utilisable
+            allocator.dealloc(aligned, Layout::from_size_align(32, 128).unwrap());
+            assert!(!allocator.alloc(Layout::from_size_align(900, 8).unwrap()).is_null());
+        }
+    }
 }
\ No newline at end of file
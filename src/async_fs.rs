@@ -0,0 +1,202 @@
+// Variante asynchrone du périphérique bloc et des opérations de base du
+// système de fichiers (feature `async`), pour les runtimes embarqués comme
+// embassy ou le code hébergé asynchrone.
+//
+// La validation du secteur de boot et les calculs de géométrie sont partagés
+// avec `Fat32Fs` : seules les lectures passent par `.await`.
+
+use crate::block_device::BlockDeviceError;
+use crate::boot_sector::{Fat32Geometry, MAX_SECTOR_SIZE};
+use crate::error::{Fat32Error, Result};
+use crate::fat::FatEntry;
+use crate::filesystem::{check_device_size, volume_geometry};
+
+/// Support de stockage bloc dont les opérations sont asynchrones.
+///
+/// Équivalent asynchrone de [`BlockDevice`](crate::BlockDevice), avec la même
+/// taille de secteur par défaut et les mêmes valeurs par défaut pour
+/// l'écriture et la taille du support (`Unsupported`).
+///
+/// Les futures retournées n'ont pas de borne `Send` : les exécuteurs
+/// embarqués sont le plus souvent mono-thread.
+#[allow(async_fn_in_trait)]
+pub trait AsyncBlockDevice {
+    /// Taille d'un secteur en octets.
+    const SECTOR_SIZE: usize = 512;
+
+    /// Lit `count` secteurs à partir du LBA `lba` dans `buf`.
+    async fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> core::result::Result<(), BlockDeviceError>;
+
+    /// Écrit `count` secteurs à partir du LBA `lba` depuis `buf` ; refusé
+    /// par défaut.
+    async fn write_sectors(
+        &self,
+        _lba: u32,
+        _count: u32,
+        _buf: &[u8],
+    ) -> core::result::Result<(), BlockDeviceError> {
+        Err(BlockDeviceError::Unsupported)
+    }
+
+    /// Nombre de secteurs du support, `Unsupported` s'il est inconnu.
+    async fn sector_count(&self) -> core::result::Result<u32, BlockDeviceError> {
+        Err(BlockDeviceError::Unsupported)
+    }
+}
+
+/// Volume FAT32 monté sur un [`AsyncBlockDevice`], en lecture seule.
+///
+/// # Exemples
+///
+/// ```no_run
+/// use fat32_parser::async_fs::{AsyncBlockDevice, AsyncFat32Fs};
+///
+/// async fn root_next<D: AsyncBlockDevice>(device: &D) -> fat32_parser::Result<Option<u32>> {
+///     let mut boot_sector = [0u8; 512];
+///     device.read_sectors(0, 1, &mut boot_sector).await?;
+///
+///     let fs = AsyncFat32Fs::mount(device, &boot_sector).await?;
+///     Ok(fs.read_fat_entry(fs.geom.root_cluster).await?.next_cluster())
+/// }
+/// ```
+pub struct AsyncFat32Fs<'a, D: AsyncBlockDevice> {
+    pub device: &'a D,
+    pub geom: Fat32Geometry,
+}
+
+impl<'a, D: AsyncBlockDevice> AsyncFat32Fs<'a, D> {
+    /// Crée une instance avec un périphérique et une géométrie donnés.
+    pub fn new(device: &'a D, geom: Fat32Geometry) -> Self {
+        Self { device, geom }
+    }
+
+    /// Monte un volume FAT32 à partir du secteur de boot.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::mount`](crate::Fat32Fs::mount).
+    pub async fn mount(device: &'a D, boot_sector: &[u8]) -> Result<Self> {
+        let geom = volume_geometry(boot_sector, D::SECTOR_SIZE, false)?;
+        check_device_size(&geom, device.sector_count().await, 0)?;
+        Ok(Self::new(device, geom))
+    }
+
+    // Vérifie que `cluster` est un cluster de données du volume.
+    fn check_cluster(&self, cluster: u32) -> Result<()> {
+        if cluster < 2 || cluster > self.geom.max_valid_cluster() {
+            return Err(Fat32Error::InvalidCluster(cluster));
+        }
+        Ok(())
+    }
+
+    /// Lit l'entrée FAT de `cluster`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::InvalidCluster`] si le cluster est hors du
+    /// volume, ou une erreur si la lecture échoue.
+    pub async fn read_fat_entry(&self, cluster: u32) -> Result<FatEntry> {
+        self.check_cluster(cluster)?;
+
+        let (fat_sector, entry_offset) = self.geom.fat_entry_location(cluster);
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = buf
+            .get_mut(..self.geom.bytes_per_sector as usize)
+            .ok_or(Fat32Error::UnsupportedSectorSize)?;
+        self.device.read_sectors(fat_sector, 1, sector).await?;
+
+        let mut value = [0u8; 4];
+        value.copy_from_slice(&sector[entry_offset..entry_offset + 4]);
+        Ok(FatEntry::new(u32::from_le_bytes(value) & 0x0FFFFFFF))
+    }
+
+    /// Lit le cluster `cluster` entier dans `buf`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::InvalidCluster`] si le cluster est hors du
+    /// volume, [`Fat32Error::BufferTooSmall`] si `buf` est plus petit qu'un
+    /// cluster, ou une erreur si la lecture échoue.
+    pub async fn read_cluster(&self, cluster: u32, buf: &mut [u8]) -> Result<()> {
+        self.check_cluster(cluster)?;
+
+        let cluster_size = self.geom.cluster_size_bytes() as usize;
+        if buf.len() < cluster_size {
+            return Err(Fat32Error::BufferTooSmall);
+        }
+
+        let lba = self.geom.cluster_to_lba(cluster)?;
+        self.device
+            .read_sectors(lba, self.geom.sectors_per_cluster, buf)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_device::BlockDevice;
+    use crate::devices::SliceDevice;
+    use crate::testing::*;
+
+    // Device asynchrone qui délègue à un device bloquant.
+    struct AsyncSlice<D>(D);
+
+    impl<D: BlockDevice> AsyncBlockDevice for AsyncSlice<D> {
+        async fn read_sectors(
+            &self,
+            lba: u32,
+            count: u32,
+            buf: &mut [u8],
+        ) -> core::result::Result<(), BlockDeviceError> {
+            self.0.read_sectors(lba, count, buf)
+        }
+
+        async fn sector_count(&self) -> core::result::Result<u32, BlockDeviceError> {
+            self.0.sector_count()
+        }
+    }
+
+    #[test]
+    fn test_async_mount_and_read() {
+        let image = fat32_image();
+        let dev = AsyncSlice(UnsizedDevice(SliceDevice::new(&image)));
+
+        block_on(async {
+            let fs = AsyncFat32Fs::mount(&dev, &image[..512]).await.unwrap();
+            assert_eq!(fs.geom.root_cluster, 2);
+            assert!(fs.read_fat_entry(3).await.unwrap().is_end());
+            assert_eq!(fs.read_fat_entry(1).await, Err(Fat32Error::InvalidCluster(1)));
+
+            let mut buf = [0u8; 512];
+            fs.read_cluster(3, &mut buf).await.unwrap();
+            assert_eq!(&buf[..13], b"Hello, world!");
+            let mut small = [0u8; 100];
+            assert_eq!(fs.read_cluster(3, &mut small).await, Err(Fat32Error::BufferTooSmall));
+        });
+    }
+
+    #[test]
+    fn test_async_mount_errors() {
+        let image = fat32_image();
+        // Image tronquée : le volume ne tient pas sur le device
+        let dev = AsyncSlice(SliceDevice::new(&image[..8 * 512]));
+
+        block_on(async {
+            assert!(matches!(
+                AsyncFat32Fs::mount(&dev, &image[..512]).await,
+                Err(Fat32Error::DeviceTooSmall)
+            ));
+            assert!(matches!(
+                AsyncFat32Fs::mount(&dev, &[0u8; 512]).await,
+                Err(Fat32Error::InvalidBootSector)
+            ));
+        });
+    }
+}
@@ -4,13 +4,30 @@
 pub enum BlockDeviceError {
     IoError,
     OutOfBounds,
+    // Opération non prise en charge (ex: écriture sur un device en lecture seule).
+    Unsupported,
 }
 
+impl core::fmt::Display for BlockDeviceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BlockDeviceError::IoError => write!(f, "Block device I/O error"),
+            BlockDeviceError::OutOfBounds => write!(f, "Sector out of device bounds"),
+            BlockDeviceError::Unsupported => write!(f, "Operation not supported by the device"),
+        }
+    }
+}
+
+// Trait `Error` de `core` : utilisable en no_std comme avec `std`.
+impl core::error::Error for BlockDeviceError {}
+
 // Ce trait représente un support de stockage type "bloc" (block device).
 // Exemple : un disque, une image disque, une carte SD, etc.
 pub trait BlockDevice {
     // Taille d'un secteur en octets.
-    // On met 512 par défaut, c'est la valeur classique en FAT32.
+    // On met 512 par défaut, c'est la valeur classique en FAT32. Les supports
+    // à secteurs de 4 Ko (4Kn) redéfinissent la constante : `Fat32Fs::mount`
+    // vérifie qu'elle correspond au champ `bytes_per_sector` de la BPB.
     const SECTOR_SIZE: usize = 512;
 
     // Fonction de lecture : on lit `count` secteurs à partir du LBA `lba`
@@ -21,6 +38,56 @@ pub trait BlockDevice {
         count: u32,
         buf: &mut [u8],
     ) -> Result<(), BlockDeviceError>;
+
+    // Fonction d'écriture : on écrit `count` secteurs à partir du LBA `lba`
+    // depuis `buf`, qui doit contenir au moins `count * SECTOR_SIZE` octets.
+    //
+    // Par défaut le device est en lecture seule : on retourne `Unsupported`,
+    // ce qui permet aux devices existants de compiler sans changement.
+    fn write_sectors(
+        &self,
+        _lba: u32,
+        _count: u32,
+        _buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        Err(BlockDeviceError::Unsupported)
+    }
+
+    // Nombre de secteurs du support, quand le device le connaît.
+    //
+    // Par défaut on retourne `Unsupported` : `Fat32Fs::mount` ne peut alors
+    // pas vérifier que le volume tient sur le support.
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        Err(BlockDeviceError::Unsupported)
+    }
+}
+
+// Une référence vers un device est elle-même un device : cela permet
+// d'envelopper un device emprunté (ex: `OffsetDevice::new(&disque, 2048)`).
+impl<D: BlockDevice + ?Sized> BlockDevice for &D {
+    const SECTOR_SIZE: usize = D::SECTOR_SIZE;
+
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        (**self).read_sectors(lba, count, buf)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        (**self).write_sectors(lba, count, buf)
+    }
+
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        (**self).sector_count()
+    }
 }
 
 // Petit module de tests basiques pour vérifier que notre trait tient la route.
@@ -50,4 +117,23 @@ mod tests {
     fn test_sector_size() {
         assert_eq!(DummyDevice::SECTOR_SIZE, 512);
     }
+
+    // Sans implémentation explicite, l'écriture est refusée.
+    #[test]
+    fn test_write_unsupported_by_default() {
+        let buf = [0u8; 512];
+        assert!(matches!(
+            DummyDevice.write_sectors(0, 1, &buf),
+            Err(BlockDeviceError::Unsupported)
+        ));
+    }
+
+    // Sans implémentation explicite, la taille du support est inconnue.
+    #[test]
+    fn test_sector_count_unsupported_by_default() {
+        assert!(matches!(
+            DummyDevice.sector_count(),
+            Err(BlockDeviceError::Unsupported)
+        ));
+    }
 }
\ No newline at end of file
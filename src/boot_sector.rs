@@ -1,3 +1,5 @@
+use crate::error::Fat32Error;
+
 // Structure qui représente la partie importante de la BPB (BIOS Parameter Block)
 // d'un volume FAT32. Les champs correspondent à ce qui est défini dans la doc FAT32.
 //
@@ -5,6 +7,7 @@
 // - "C" : même ordre et alignement qu'en C
 // - "packed" : pas de padding entre les champs (collés)
 #[repr(C, packed)]
+#[derive(Clone, Copy)]
 pub struct BiosParameterBlock {
     pub bytes_per_sector: u16,
     pub sectors_per_cluster: u8,
@@ -22,16 +25,285 @@ pub struct BiosParameterBlock {
     pub ext_flags: u16,
     pub fs_version: u16,
     pub root_cluster: u32,
+    pub fs_info: u16,
+    pub backup_boot_sector: u16,
+}
+
+/// Emplacement standard de la copie de secours du secteur de boot (champ
+/// `backup_boot_sector` de la BPB), suivie de celle du secteur FSInfo.
+pub const BACKUP_BOOT_SECTOR: u16 = 6;
+
+// `#[derive(Debug)]` prendrait une référence vers chaque champ, non aligné
+// dans une structure `packed` : les champs sont d'abord copiés.
+impl core::fmt::Debug for BiosParameterBlock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self {
+            bytes_per_sector,
+            sectors_per_cluster,
+            reserved_sector_count,
+            num_fats,
+            root_entry_count,
+            total_sectors_16,
+            media,
+            fat_size_16,
+            sectors_per_track,
+            num_heads,
+            hidden_sectors,
+            total_sectors_32,
+            fat_size_32,
+            ext_flags,
+            fs_version,
+            root_cluster,
+            fs_info,
+            backup_boot_sector,
+        } = *self;
+        f.debug_struct("BiosParameterBlock")
+            .field("bytes_per_sector", &bytes_per_sector)
+            .field("sectors_per_cluster", &sectors_per_cluster)
+            .field("reserved_sector_count", &reserved_sector_count)
+            .field("num_fats", &num_fats)
+            .field("root_entry_count", &root_entry_count)
+            .field("total_sectors_16", &total_sectors_16)
+            .field("media", &media)
+            .field("fat_size_16", &fat_size_16)
+            .field("sectors_per_track", &sectors_per_track)
+            .field("num_heads", &num_heads)
+            .field("hidden_sectors", &hidden_sectors)
+            .field("total_sectors_32", &total_sectors_32)
+            .field("fat_size_32", &fat_size_32)
+            .field("ext_flags", &ext_flags)
+            .field("fs_version", &fs_version)
+            .field("root_cluster", &root_cluster)
+            .field("fs_info", &fs_info)
+            .field("backup_boot_sector", &backup_boot_sector)
+            .finish()
+    }
+}
+
+// Lecteurs little-endian utilisés par les parseurs octet par octet.
+pub(crate) fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
 }
 
 impl BiosParameterBlock {
+    /// Offset de la BPB dans le secteur de boot.
+    pub const OFFSET: usize = 11;
+
+    /// Taille de la BPB FAT32 lue par [`parse`](Self::parse), en octets.
+    pub const SIZE: usize = 41;
+
+    /// Lit la BPB à partir des octets du secteur de boot, champ par champ.
+    ///
+    /// Chaque valeur est décodée en little-endian depuis son offset : aucune
+    /// référence vers des données non alignées n'est créée, contrairement à
+    /// [`from_sector`](Self::from_sector).
+    ///
+    /// Retourne `None` si `sector` est trop court pour contenir la BPB.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use fat32_parser::boot_sector::BiosParameterBlock;
+    ///
+    /// let mut boot_sector = [0u8; 512];
+    /// boot_sector[11..13].copy_from_slice(&512u16.to_le_bytes());
+    /// let bpb = BiosParameterBlock::parse(&boot_sector).unwrap();
+    /// assert_eq!({ bpb.bytes_per_sector }, 512);
+    /// ```
+    pub fn parse(sector: &[u8]) -> Option<BiosParameterBlock> {
+        if sector.len() < Self::OFFSET + Self::SIZE {
+            return None;
+        }
+
+        let b = &sector[Self::OFFSET..];
+        Some(BiosParameterBlock {
+            bytes_per_sector: read_u16(b, 0),
+            sectors_per_cluster: b[2],
+            reserved_sector_count: read_u16(b, 3),
+            num_fats: b[5],
+            root_entry_count: read_u16(b, 6),
+            total_sectors_16: read_u16(b, 8),
+            media: b[10],
+            fat_size_16: read_u16(b, 11),
+            sectors_per_track: read_u16(b, 13),
+            num_heads: read_u16(b, 15),
+            hidden_sectors: read_u32(b, 17),
+            total_sectors_32: read_u32(b, 21),
+            fat_size_32: read_u32(b, 25),
+            ext_flags: read_u16(b, 29),
+            fs_version: read_u16(b, 31),
+            root_cluster: read_u32(b, 33),
+            fs_info: read_u16(b, 37),
+            backup_boot_sector: read_u16(b, 39),
+        })
+    }
+
+    /// Vérifie la cohérence des champs utilisés pour calculer la géométrie.
+    ///
+    /// Une image corrompue ou malveillante pourrait sinon provoquer des
+    /// divisions par zéro ou une géométrie absurde.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::UnsupportedSectorSize`] si `bytes_per_sector` n'est pas
+    ///   512, 1024, 2048 ou 4096
+    /// - [`Fat32Error::InvalidBpb`] avec le nom du champ si
+    ///   `sectors_per_cluster` n'est pas une puissance de deux entre 1 et 128,
+    ///   si `num_fats` ne vaut ni 1 ni 2, si `reserved_sector_count` est nul
+    ///   ou si `root_cluster` est inférieur à 2
+    pub fn validate(&self) -> Result<(), Fat32Error> {
+        if !is_valid_sector_size(self.bytes_per_sector as u32) {
+            return Err(Fat32Error::UnsupportedSectorSize);
+        }
+        let sectors_per_cluster = self.sectors_per_cluster;
+        if !sectors_per_cluster.is_power_of_two() || sectors_per_cluster > 128 {
+            return Err(Fat32Error::InvalidBpb("sectors_per_cluster"));
+        }
+        if self.num_fats != 1 && self.num_fats != 2 {
+            return Err(Fat32Error::InvalidBpb("num_fats"));
+        }
+        if self.reserved_sector_count == 0 {
+            return Err(Fat32Error::InvalidBpb("reserved_sector_count"));
+        }
+        if self.root_cluster < 2 {
+            return Err(Fat32Error::InvalidBpb("root_cluster"));
+        }
+        Ok(())
+    }
+
+    /// Vérifie les champs sans effet sur la lecture d'un volume FAT32, que
+    /// [`validate`](Self::validate) tolère : à utiliser en plus de celle-ci
+    /// pour exiger un volume conforme.
+    ///
+    /// # Errors
+    ///
+    /// [`Fat32Error::InvalidBpb`] avec le nom du champ si `fs_version` ou
+    /// `root_entry_count` n'est pas nul, ou si `media` n'est ni `0xF0` ni
+    /// compris entre `0xF8` et `0xFF`.
+    pub fn validate_strict(&self) -> Result<(), Fat32Error> {
+        if self.fs_version() != 0 {
+            return Err(Fat32Error::InvalidBpb("fs_version"));
+        }
+        if !matches!(self.media, 0xF0 | 0xF8..=0xFF) {
+            return Err(Fat32Error::InvalidBpb("media"));
+        }
+        if self.root_entry_count() != 0 {
+            return Err(Fat32Error::InvalidBpb("root_entry_count"));
+        }
+        Ok(())
+    }
+
+    // Accesseurs : la structure est `packed`, une référence vers un champ
+    // serait non alignée. Chaque méthode retourne le champ par valeur (une
+    // copie), sans jamais en prendre de référence.
+
+    /// Nombre d'octets par secteur.
+    pub fn bytes_per_sector(&self) -> u16 {
+        self.bytes_per_sector
+    }
+
+    /// Nombre de secteurs par cluster.
+    pub fn sectors_per_cluster(&self) -> u8 {
+        self.sectors_per_cluster
+    }
+
+    /// Nombre de secteurs réservés avant la première FAT.
+    pub fn reserved_sector_count(&self) -> u16 {
+        self.reserved_sector_count
+    }
+
+    /// Nombre de copies de la FAT.
+    pub fn num_fats(&self) -> u8 {
+        self.num_fats
+    }
+
+    /// Nombre d'entrées du répertoire racine fixe (0 en FAT32).
+    pub fn root_entry_count(&self) -> u16 {
+        self.root_entry_count
+    }
+
+    /// Nombre total de secteurs sur 16 bits (0 en FAT32).
+    pub fn total_sectors_16(&self) -> u16 {
+        self.total_sectors_16
+    }
+
+    /// Type de support (`0xF8` pour un disque fixe).
+    pub fn media(&self) -> u8 {
+        self.media
+    }
+
+    /// Taille d'une FAT en secteurs sur 16 bits (0 en FAT32).
+    pub fn fat_size_16(&self) -> u16 {
+        self.fat_size_16
+    }
+
+    /// Secteurs par piste (géométrie CHS).
+    pub fn sectors_per_track(&self) -> u16 {
+        self.sectors_per_track
+    }
+
+    /// Nombre de têtes (géométrie CHS).
+    pub fn num_heads(&self) -> u16 {
+        self.num_heads
+    }
+
+    /// Secteurs précédant le volume sur le disque.
+    pub fn hidden_sectors(&self) -> u32 {
+        self.hidden_sectors
+    }
+
+    /// Nombre total de secteurs sur 32 bits.
+    pub fn total_sectors_32(&self) -> u32 {
+        self.total_sectors_32
+    }
+
+    /// Taille d'une FAT en secteurs sur 32 bits.
+    pub fn fat_size_32(&self) -> u32 {
+        self.fat_size_32
+    }
+
+    /// Drapeaux étendus (FAT active, recopie des FAT).
+    pub fn ext_flags(&self) -> u16 {
+        self.ext_flags
+    }
+
+    /// Version du système de fichiers.
+    pub fn fs_version(&self) -> u16 {
+        self.fs_version
+    }
+
+    /// Premier cluster du répertoire racine.
+    pub fn root_cluster(&self) -> u32 {
+        self.root_cluster
+    }
+
+    /// Secteur FSInfo, relatif au début du volume.
+    pub fn fs_info(&self) -> u16 {
+        self.fs_info
+    }
+
+    /// Secteur de la copie de secours du secteur de boot, relatif au début
+    /// du volume (6 en principe, 0 ou `0xFFFF` si le volume n'en a pas).
+    pub fn backup_boot_sector(&self) -> u16 {
+        self.backup_boot_sector
+    }
+
     /// Construit une référence vers une BPB à partir des octets du secteur de boot.
     ///
     /// # Safety
     ///
     /// Cette fonction est unsafe car elle effectue un cast de pointeur brut sans validation.
     /// L'appelant doit garantir que :
-    /// - `sector` contient au moins `11 + size_of::<BiosParameterBlock>()` octets (≈ 47 octets minimum)
+    /// - `sector` contient au moins `11 + size_of::<BiosParameterBlock>()` octets (52 octets minimum)
     /// - Les octets à partir de l'offset 11 sont correctement alignés pour `BiosParameterBlock`
     /// - Les données représentent une BPB FAT32 valide provenant d'un vrai boot sector
     /// - La durée de vie de `sector` couvre toute utilisation de la référence retournée
@@ -39,6 +311,7 @@ impl BiosParameterBlock {
     /// # Exemples
     ///
     /// ```no_run
+    /// # #![allow(deprecated)]
     /// use fat32_parser::boot_sector::BiosParameterBlock;
     /// use core::ptr;
     ///
@@ -49,6 +322,7 @@ impl BiosParameterBlock {
     /// let bytes_per_sector = unsafe { ptr::addr_of!(bpb.bytes_per_sector).read_unaligned() };
     /// // Utilise bytes_per_sector...
     /// ```
+    #[deprecated(note = "utiliser `BiosParameterBlock::parse`, qui ne nécessite pas d'unsafe")]
     pub unsafe fn from_sector(sector: &[u8]) -> &Self {
         // Dans le format FAT, la BPB commence à l'offset 11 dans le secteur.
         let offset = 11;
@@ -56,14 +330,209 @@ impl BiosParameterBlock {
     }
 }
 
+/// Secteur de boot FAT32 complet : la BPB et les champs qui l'entourent
+/// (instruction de saut, nom OEM, champs étendus propres à FAT32).
+///
+/// Le numéro de série, le label et le type de système de fichiers ne sont
+/// significatifs qu'avec la signature étendue `0x29` (voir
+/// [`has_extended_fields`](Self::has_extended_fields)) ; ce sont de simples
+/// indications, que [`Fat32Fs`](crate::Fat32Fs) n'utilise pas pour lire le
+/// volume.
+#[derive(Debug, Clone, Copy)]
+pub struct BootSector {
+    /// Instruction de saut vers le code de boot (offset 0, `EB xx 90`).
+    pub jump: [u8; 3],
+    /// Nom de l'outil qui a formaté le volume (offset 3, ex: `"MSWIN4.1"`).
+    pub oem_name: [u8; 8],
+    /// BPB (offsets 11 à 49).
+    pub bpb: BiosParameterBlock,
+    /// Numéro de lecteur BIOS (offset 64, `0x80` pour un disque fixe).
+    pub drive_number: u8,
+    /// Signature de boot étendue (offset 66).
+    pub boot_signature: u8,
+    /// Numéro de série du volume (offset 67).
+    pub volume_id: u32,
+    /// Label du volume (offset 71), complété par des espaces.
+    pub volume_label: [u8; 11],
+    /// Type de système de fichiers (offset 82), `"FAT32   "` en principe.
+    pub fs_type: [u8; 8],
+}
+
+impl BootSector {
+    /// Nombre d'octets lus par [`parse`](Self::parse).
+    pub const SIZE: usize = 90;
+
+    /// Valeur de [`boot_signature`](Self::boot_signature) quand le numéro de
+    /// série, le label et le type sont présents.
+    pub const EXTENDED_SIGNATURE: u8 = 0x29;
+
+    /// Lit le secteur de boot champ par champ, comme
+    /// [`BiosParameterBlock::parse`].
+    ///
+    /// Retourne `None` si `sector` fait moins de [`SIZE`](Self::SIZE) octets.
+    /// La signature `0x55AA` n'est pas vérifiée.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use fat32_parser::boot_sector::BootSector;
+    ///
+    /// let mut sector = [0u8; 512];
+    /// sector[3..11].copy_from_slice(b"MSWIN4.1");
+    /// sector[66] = 0x29;
+    /// sector[82..90].copy_from_slice(b"FAT32   ");
+    ///
+    /// let boot = BootSector::parse(&sector).unwrap();
+    /// assert_eq!(&boot.oem_name, b"MSWIN4.1");
+    /// assert!(boot.has_extended_fields());
+    /// ```
+    pub fn parse(sector: &[u8]) -> Option<BootSector> {
+        if sector.len() < Self::SIZE {
+            return None;
+        }
+
+        let mut boot = BootSector {
+            jump: [0; 3],
+            oem_name: [0; 8],
+            bpb: BiosParameterBlock::parse(sector)?,
+            drive_number: sector[64],
+            boot_signature: sector[66],
+            volume_id: read_u32(sector, 67),
+            volume_label: [0; 11],
+            fs_type: [0; 8],
+        };
+        boot.jump.copy_from_slice(&sector[0..3]);
+        boot.oem_name.copy_from_slice(&sector[3..11]);
+        boot.volume_label.copy_from_slice(&sector[71..82]);
+        boot.fs_type.copy_from_slice(&sector[82..90]);
+        Some(boot)
+    }
+
+    /// True si le numéro de série, le label et le type sont présents
+    /// (signature étendue `0x29`).
+    pub fn has_extended_fields(&self) -> bool {
+        self.boot_signature == Self::EXTENDED_SIGNATURE
+    }
+
+    /// Label du volume, ou `None` sans champs étendus ou si le label est
+    /// vide ou vaut `"NO NAME"`.
+    pub fn label(&self) -> Option<[u8; 11]> {
+        let label = self.volume_label;
+        let empty = label.iter().all(|&b| b == b' ' || b == 0);
+        if !self.has_extended_fields() || empty || &label == b"NO NAME    " {
+            return None;
+        }
+        Some(label)
+    }
+
+    /// Vérifications du mode strict : celles de
+    /// [`BiosParameterBlock::validate_strict`], puis le type de système de
+    /// fichiers s'il est présent (signature étendue).
+    ///
+    /// # Errors
+    ///
+    /// [`Fat32Error::InvalidBpb`] avec le nom du champ, `"fs_type"` si le
+    /// type n'est pas `"FAT32   "`.
+    pub fn validate_strict(&self) -> Result<(), Fat32Error> {
+        self.bpb.validate_strict()?;
+        if self.has_extended_fields() && &self.fs_type != b"FAT32   " {
+            return Err(Fat32Error::InvalidBpb("fs_type"));
+        }
+        Ok(())
+    }
+}
+
+// Plus grande taille de secteur autorisée par la spécification FAT : les
+// buffers de secteur sur la pile sont dimensionnés en conséquence.
+pub const MAX_SECTOR_SIZE: usize = 4096;
+
+// Vérifie que `size` est une taille de secteur autorisée (512, 1024, 2048
+// ou 4096 octets).
+pub fn is_valid_sector_size(size: u32) -> bool {
+    matches!(size, 512 | 1024 | 2048 | 4096)
+}
+
+/// Variante de FAT d'un volume, déterminée par son nombre de clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatType {
+    Fat12,
+    Fat16,
+    Fat32,
+}
+
+/// Détermine le type de FAT d'un volume comme le prévoit la spécification :
+/// uniquement d'après le nombre de clusters de la zone de données (moins de
+/// 4085 : FAT12, moins de 65525 : FAT16, sinon FAT32).
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::boot_sector::{detect_fat_type, BiosParameterBlock, FatType};
+///
+/// let mut boot_sector = [0u8; 512];
+/// boot_sector[11..13].copy_from_slice(&512u16.to_le_bytes());
+/// boot_sector[13] = 1; // secteurs par cluster
+/// boot_sector[14..16].copy_from_slice(&32u16.to_le_bytes()); // secteurs réservés
+/// boot_sector[16] = 2; // nombre de FAT
+/// boot_sector[32..36].copy_from_slice(&100_000u32.to_le_bytes());
+/// boot_sector[36..40].copy_from_slice(&800u32.to_le_bytes());
+///
+/// let bpb = BiosParameterBlock::parse(&boot_sector).unwrap();
+/// assert_eq!(detect_fat_type(&bpb), FatType::Fat32);
+/// ```
+pub fn detect_fat_type(bpb: &BiosParameterBlock) -> FatType {
+    let bytes_per_sector = bpb.bytes_per_sector as u32;
+    let sectors_per_cluster = bpb.sectors_per_cluster as u32;
+
+    // Secteurs occupés par le répertoire racine fixe (0 en FAT32)
+    let root_dir_sectors = match bytes_per_sector {
+        0 => 0,
+        bps => (bpb.root_entry_count as u32 * 32).div_ceil(bps),
+    };
+    let fat_size = if bpb.fat_size_16 != 0 {
+        bpb.fat_size_16 as u32
+    } else {
+        bpb.fat_size_32
+    };
+    let total_sectors = if bpb.total_sectors_16 != 0 {
+        bpb.total_sectors_16 as u32
+    } else {
+        bpb.total_sectors_32
+    };
+
+    let metadata = bpb.reserved_sector_count as u32
+        + bpb.num_fats as u32 * fat_size
+        + root_dir_sectors;
+    let clusters = match sectors_per_cluster {
+        0 => 0,
+        spc => total_sectors.saturating_sub(metadata) / spc,
+    };
+
+    if clusters < 4085 {
+        FatType::Fat12
+    } else if clusters < 65525 {
+        FatType::Fat16
+    } else {
+        FatType::Fat32
+    }
+}
+
 // Structure plus "haut niveau" qui regroupe les infos utiles pour faire
 // des calculs d'adresses (clusters → secteurs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Fat32Geometry {
     pub first_data_sector: u32,
     pub fat_start_lba: u32,
     pub root_cluster: u32,
     pub sectors_per_cluster: u32,
     pub bytes_per_sector: u32,
+    // Nombre de copies de la FAT, et taille d'une copie en secteurs.
+    pub num_fats: u32,
+    pub fat_size: u32,
+    // Nombre total de secteurs du volume.
+    pub total_sectors: u32,
+    // Secteur FSInfo (0 si le volume n'en a pas).
+    pub fs_info_sector: u32,
 }
 
 impl Fat32Geometry {
@@ -83,20 +552,78 @@ impl Fat32Geometry {
         // Premier secteur de la zone de données (après les FATs).
         let first_data_sector = reserved + fats * fat_size;
 
+        // Nombre total de secteurs : champ 32 bits en FAT32, 16 bits sinon.
+        let total_sectors = if bpb.total_sectors_16 != 0 {
+            bpb.total_sectors_16 as u32
+        } else {
+            bpb.total_sectors_32
+        };
+
         Self {
             first_data_sector,
             fat_start_lba: reserved,
             root_cluster: bpb.root_cluster,
             sectors_per_cluster: bpb.sectors_per_cluster as u32,
             bytes_per_sector: bpb.bytes_per_sector as u32,
+            num_fats: fats,
+            fat_size,
+            total_sectors,
+            // 0xFFFF indique l'absence de secteur FSInfo
+            fs_info_sector: match bpb.fs_info {
+                0xFFFF => 0,
+                sector => sector as u32,
+            },
+        }
+    }
+
+    // Taille d'un cluster en octets.
+    pub fn cluster_size_bytes(&self) -> u32 {
+        self.sectors_per_cluster * self.bytes_per_sector
+    }
+
+    // Nombre de secteurs de la zone de données (après les FATs).
+    pub fn data_sectors(&self) -> u32 {
+        self.total_sectors.saturating_sub(self.first_data_sector)
+    }
+
+    // Nombre de clusters de la zone de données (numérotés à partir de 2).
+    //
+    // Calculé à partir des champs plutôt que stocké, pour rester cohérent
+    // avec une géométrie construite ou modifiée à la main.
+    pub fn total_clusters(&self) -> u32 {
+        if self.sectors_per_cluster == 0 {
+            return 0;
         }
+        self.data_sectors() / self.sectors_per_cluster
+    }
+
+    // Plus grand numéro de cluster valide (le premier cluster de données est le 2).
+    pub fn max_valid_cluster(&self) -> u32 {
+        self.total_clusters() + 1
     }
 
     // Traduit un numéro de cluster FAT en adresse LBA (numéro de secteur logique).
     //
-    // Dans FAT32, les clusters commencent à 2.
-    pub fn cluster_to_lba(&self, cluster: u32) -> u32 {
-        self.first_data_sector + (cluster - 2) * self.sectors_per_cluster
+    // Dans FAT32, les clusters commencent à 2. Un numéro absurde (lu dans une
+    // FAT corrompue) donne `InvalidCluster` plutôt qu'une adresse tronquée
+    // qui pointerait vers d'autres données.
+    pub fn cluster_to_lba(&self, cluster: u32) -> Result<u32, Fat32Error> {
+        cluster
+            .checked_sub(2)
+            .and_then(|index| index.checked_mul(self.sectors_per_cluster))
+            .and_then(|offset| offset.checked_add(self.first_data_sector))
+            .ok_or(Fat32Error::InvalidCluster(cluster))
+    }
+
+    // Position de l'entrée de `cluster` dans la première FAT : secteur (LBA
+    // du volume) et offset en octets dans ce secteur (4 octets par entrée en
+    // FAT32).
+    pub(crate) fn fat_entry_location(&self, cluster: u32) -> (u32, usize) {
+        let fat_offset = cluster * 4;
+        (
+            self.fat_start_lba + fat_offset / self.bytes_per_sector,
+            (fat_offset % self.bytes_per_sector) as usize,
+        )
     }
 }
 
@@ -112,13 +639,293 @@ mod tests {
             root_cluster: 2,
             sectors_per_cluster: 8,
             bytes_per_sector: 512,
+            num_fats: 2,
+            fat_size: 34,
+            total_sectors: 1000,
+            fs_info_sector: 1,
         };
         
         // Cluster 2 devrait être au premier secteur de données
-        assert_eq!(geom.cluster_to_lba(2), 100);
+        assert_eq!(geom.cluster_to_lba(2), Ok(100));
         // Cluster 3 devrait être 8 secteurs plus loin
-        assert_eq!(geom.cluster_to_lba(3), 108);
+        assert_eq!(geom.cluster_to_lba(3), Ok(108));
         // Cluster 10
-        assert_eq!(geom.cluster_to_lba(10), 164);
+        assert_eq!(geom.cluster_to_lba(10), Ok(164));
+
+        // Numéros qui déborderaient au lieu de donner une adresse tronquée
+        for cluster in [0, 1, u32::MAX / 8 + 3, u32::MAX - 1, u32::MAX] {
+            assert_eq!(geom.cluster_to_lba(cluster), Err(Fat32Error::InvalidCluster(cluster)));
+        }
+        // Dernier cluster dont l'adresse tient sur 32 bits
+        let last = (u32::MAX - 100) / 8 + 2;
+        assert_eq!(geom.cluster_to_lba(last), Ok(100 + (last - 2) * 8));
+        assert!(geom.cluster_to_lba(last + 1).is_err());
+    }
+
+    #[test]
+    fn test_geometry_from_bpb_table() {
+        // bytes_per_sector, sectors_per_cluster, reserved, num_fats,
+        // total_sectors_16, fat_size_16, total_sectors_32, fat_size_32,
+        // root_cluster, fs_info
+        type BpbFields = (u16, u8, u16, u8, u16, u16, u32, u32, u32, u16);
+        let cases: [(BpbFields, Fat32Geometry); 3] = [
+            (
+                (512, 8, 32, 2, 0, 0, 0x0010_0000, 1024, 2, 1),
+                Fat32Geometry {
+                    first_data_sector: 32 + 2 * 1024,
+                    fat_start_lba: 32,
+                    root_cluster: 2,
+                    sectors_per_cluster: 8,
+                    bytes_per_sector: 512,
+                    num_fats: 2,
+                    fat_size: 1024,
+                    total_sectors: 0x0010_0000,
+                    fs_info_sector: 1,
+                },
+            ),
+            // Une seule FAT, pas de secteur FSInfo (0xFFFF)
+            (
+                (4096, 1, 8, 1, 0, 0, 200_000, 100, 5, 0xFFFF),
+                Fat32Geometry {
+                    first_data_sector: 108,
+                    fat_start_lba: 8,
+                    root_cluster: 5,
+                    sectors_per_cluster: 1,
+                    bytes_per_sector: 4096,
+                    num_fats: 1,
+                    fat_size: 100,
+                    total_sectors: 200_000,
+                    fs_info_sector: 0,
+                },
+            ),
+            // Les champs 16 bits non nuls l'emportent sur les champs 32 bits
+            (
+                (1024, 4, 4, 2, 30_000, 20, 99_999, 999, 2, 2),
+                Fat32Geometry {
+                    first_data_sector: 44,
+                    fat_start_lba: 4,
+                    root_cluster: 2,
+                    sectors_per_cluster: 4,
+                    bytes_per_sector: 1024,
+                    num_fats: 2,
+                    fat_size: 20,
+                    total_sectors: 30_000,
+                    fs_info_sector: 2,
+                },
+            ),
+        ];
+
+        for ((bps, spc, reserved, fats, total16, fat16, total32, fat32, root, fs_info), expected) in cases {
+            let mut sector = [0u8; 512];
+            sector[11..13].copy_from_slice(&bps.to_le_bytes());
+            sector[13] = spc;
+            sector[14..16].copy_from_slice(&reserved.to_le_bytes());
+            sector[16] = fats;
+            sector[19..21].copy_from_slice(&total16.to_le_bytes());
+            sector[22..24].copy_from_slice(&fat16.to_le_bytes());
+            sector[32..36].copy_from_slice(&total32.to_le_bytes());
+            sector[36..40].copy_from_slice(&fat32.to_le_bytes());
+            sector[44..48].copy_from_slice(&root.to_le_bytes());
+            sector[48..50].copy_from_slice(&fs_info.to_le_bytes());
+
+            let bpb = BiosParameterBlock::parse(&sector).unwrap();
+            let geom = Fat32Geometry::from_bpb(&bpb);
+            assert_eq!(geom, expected);
+            // `Copy` : la géométrie reste utilisable après avoir été passée
+            let copy = geom;
+            assert_eq!(copy, geom);
+        }
+    }
+
+    #[test]
+    fn test_geometry_sizes() {
+        let geom = Fat32Geometry {
+            first_data_sector: 100,
+            fat_start_lba: 32,
+            root_cluster: 2,
+            sectors_per_cluster: 8,
+            bytes_per_sector: 512,
+            num_fats: 2,
+            fat_size: 34,
+            total_sectors: 1000,
+            fs_info_sector: 1,
+        };
+
+        assert_eq!(geom.cluster_size_bytes(), 4096);
+        assert_eq!(geom.data_sectors(), 900);
+        // 900 / 8 = 112 clusters complets (les 4 secteurs restants sont inutilisés)
+        assert_eq!(geom.total_clusters(), 112);
+        assert_eq!(geom.max_valid_cluster(), 113);
+    }
+
+    #[test]
+    fn test_parse_bpb() {
+        let mut sector = [0u8; 512];
+        sector[11..13].copy_from_slice(&512u16.to_le_bytes());
+        sector[13] = 8;
+        sector[14..16].copy_from_slice(&32u16.to_le_bytes());
+        sector[16] = 2;
+        sector[21] = 0xF8;
+        sector[32..36].copy_from_slice(&0x0010_0000u32.to_le_bytes());
+        sector[36..40].copy_from_slice(&1024u32.to_le_bytes());
+        sector[44..48].copy_from_slice(&2u32.to_le_bytes());
+        sector[48..50].copy_from_slice(&1u16.to_le_bytes());
+        sector[50..52].copy_from_slice(&6u16.to_le_bytes());
+
+        let bpb = BiosParameterBlock::parse(&sector).unwrap();
+        assert_eq!({ bpb.bytes_per_sector }, 512);
+        assert_eq!(bpb.sectors_per_cluster, 8);
+        assert_eq!({ bpb.reserved_sector_count }, 32);
+        assert_eq!(bpb.num_fats, 2);
+        assert_eq!(bpb.media, 0xF8);
+        assert_eq!({ bpb.total_sectors_32 }, 0x0010_0000);
+        assert_eq!({ bpb.fat_size_32 }, 1024);
+        assert_eq!({ bpb.root_cluster }, 2);
+        assert_eq!({ bpb.fs_info }, 1);
+
+        // Les accesseurs retournent les mêmes valeurs sans référence non alignée
+        assert_eq!(bpb.bytes_per_sector(), 512);
+        assert_eq!(bpb.sectors_per_cluster(), 8);
+        assert_eq!(bpb.reserved_sector_count(), 32);
+        assert_eq!(bpb.num_fats(), 2);
+        assert_eq!(bpb.media(), 0xF8);
+        assert_eq!(bpb.total_sectors_32(), 0x0010_0000);
+        assert_eq!(bpb.fat_size_32(), 1024);
+        assert_eq!(bpb.root_cluster(), 2);
+        assert_eq!(bpb.fs_info(), 1);
+        assert_eq!(bpb.backup_boot_sector(), 6);
+        assert_eq!(bpb.hidden_sectors(), 0);
+
+        let geom = Fat32Geometry::from_bpb(&bpb);
+        assert_eq!(geom.first_data_sector, 32 + 2 * 1024);
+        assert_eq!(geom.num_fats, 2);
+        assert_eq!(geom.fat_size, 1024);
+        assert_eq!(geom.total_sectors, 0x0010_0000);
+        assert_eq!(geom.total_clusters(), (0x0010_0000 - (32 + 2 * 1024)) / 8);
+        assert_eq!(geom.fs_info_sector, 1);
+
+        // Affichables pour diagnostiquer un montage
+        let bpb_debug = format!("{:?}", bpb);
+        assert!(bpb_debug.starts_with("BiosParameterBlock { bytes_per_sector: 512, sectors_per_cluster: 8,"));
+        assert!(bpb_debug.ends_with("root_cluster: 2, fs_info: 1, backup_boot_sector: 6 }"));
+        assert!(format!("{:?}", geom).contains("fat_size: 1024"));
+    }
+
+    // BPB valide : secteurs de 512 octets, 8 secteurs par cluster, 2 FAT.
+    fn valid_bpb() -> BiosParameterBlock {
+        let mut sector = [0u8; 512];
+        sector[11..13].copy_from_slice(&512u16.to_le_bytes());
+        sector[13] = 8;
+        sector[14..16].copy_from_slice(&32u16.to_le_bytes());
+        sector[16] = 2;
+        sector[44..48].copy_from_slice(&2u32.to_le_bytes());
+        BiosParameterBlock::parse(&sector).unwrap()
+    }
+
+    #[test]
+    fn test_validate_strict_bpb() {
+        let bpb = BiosParameterBlock { media: 0xF8, ..valid_bpb() };
+        assert_eq!(bpb.validate_strict(), Ok(()));
+        assert_eq!(BiosParameterBlock { media: 0xF0, ..valid_bpb() }.validate_strict(), Ok(()));
+
+        let quirks = [
+            (BiosParameterBlock { fs_version: 1, media: 0xF8, ..valid_bpb() }, "fs_version"),
+            (BiosParameterBlock { media: 0x12, ..valid_bpb() }, "media"),
+            (BiosParameterBlock { root_entry_count: 512, media: 0xF8, ..valid_bpb() }, "root_entry_count"),
+        ];
+        for (bpb, field) in quirks {
+            // Tolérés par `validate`, refusés par `validate_strict`
+            assert_eq!(bpb.validate(), Ok(()));
+            assert_eq!(bpb.validate_strict(), Err(Fat32Error::InvalidBpb(field)));
+        }
+    }
+
+    #[test]
+    fn test_validate_bpb() {
+        assert_eq!(valid_bpb().validate(), Ok(()));
+
+        for bps in [0, 256, 1000, 8192] {
+            let bpb = BiosParameterBlock { bytes_per_sector: bps, ..valid_bpb() };
+            assert_eq!(bpb.validate(), Err(Fat32Error::UnsupportedSectorSize));
+        }
+        for spc in [0, 3, 255] {
+            let bpb = BiosParameterBlock { sectors_per_cluster: spc, ..valid_bpb() };
+            assert_eq!(bpb.validate(), Err(Fat32Error::InvalidBpb("sectors_per_cluster")));
+        }
+        for fats in [0, 3] {
+            let bpb = BiosParameterBlock { num_fats: fats, ..valid_bpb() };
+            assert_eq!(bpb.validate(), Err(Fat32Error::InvalidBpb("num_fats")));
+        }
+        let bpb = BiosParameterBlock { reserved_sector_count: 0, ..valid_bpb() };
+        assert_eq!(bpb.validate(), Err(Fat32Error::InvalidBpb("reserved_sector_count")));
+        let bpb = BiosParameterBlock { root_cluster: 1, ..valid_bpb() };
+        assert_eq!(bpb.validate(), Err(Fat32Error::InvalidBpb("root_cluster")));
+    }
+
+    #[test]
+    fn test_detect_fat_type() {
+        // 1 secteur par cluster, 32 réservés, 2 FAT de 256 secteurs
+        let bpb = |total_sectors_32, root_entry_count| BiosParameterBlock {
+            sectors_per_cluster: 1,
+            fat_size_32: 256,
+            total_sectors_32,
+            root_entry_count,
+            ..valid_bpb()
+        };
+        let metadata = 32 + 2 * 256;
+
+        assert_eq!(detect_fat_type(&bpb(metadata + 4084, 0)), FatType::Fat12);
+        assert_eq!(detect_fat_type(&bpb(metadata + 4085, 0)), FatType::Fat16);
+        assert_eq!(detect_fat_type(&bpb(metadata + 65524, 0)), FatType::Fat16);
+        assert_eq!(detect_fat_type(&bpb(metadata + 65525, 0)), FatType::Fat32);
+        // 512 entrées de racine fixe = 32 secteurs en moins pour les données
+        assert_eq!(detect_fat_type(&bpb(metadata + 65525, 512)), FatType::Fat16);
+
+        let bpb = BiosParameterBlock { sectors_per_cluster: 0, ..valid_bpb() };
+        assert_eq!(detect_fat_type(&bpb), FatType::Fat12);
+    }
+
+    #[test]
+    fn test_parse_boot_sector() {
+        let mut sector = [0u8; 512];
+        sector[0..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+        sector[3..11].copy_from_slice(b"mkfs.fat");
+        sector[11..13].copy_from_slice(&512u16.to_le_bytes());
+        sector[64] = 0x80;
+        sector[66] = 0x29;
+        sector[67..71].copy_from_slice(&0x1234_ABCDu32.to_le_bytes());
+        sector[71..82].copy_from_slice(b"MY VOLUME  ");
+        sector[82..90].copy_from_slice(b"FAT32   ");
+
+        let boot = BootSector::parse(&sector).unwrap();
+        assert_eq!(boot.jump, [0xEB, 0x58, 0x90]);
+        assert_eq!(&boot.oem_name, b"mkfs.fat");
+        assert_eq!(boot.bpb.bytes_per_sector(), 512);
+        assert_eq!(boot.drive_number, 0x80);
+        assert_eq!(boot.volume_id, 0x1234_ABCD);
+        assert_eq!(boot.label(), Some(*b"MY VOLUME  "));
+
+        let mut boot = BootSector { bpb: BiosParameterBlock { media: 0xF8, ..valid_bpb() }, ..boot };
+        assert_eq!(boot.validate_strict(), Ok(()));
+        boot.fs_type = *b"FAT16   ";
+        assert_eq!(boot.validate_strict(), Err(Fat32Error::InvalidBpb("fs_type")));
+        // Sans signature étendue, le champ n'est pas significatif
+        boot.boot_signature = 0x28;
+        assert_eq!(boot.validate_strict(), Ok(()));
+
+        // Sans signature étendue, les champs suivants ne comptent pas
+        sector[66] = 0;
+        assert_eq!(BootSector::parse(&sector).unwrap().label(), None);
+        sector[66] = 0x29;
+        sector[71..82].copy_from_slice(b"NO NAME    ");
+        assert_eq!(BootSector::parse(&sector).unwrap().label(), None);
+
+        assert!(BootSector::parse(&sector[..89]).is_none());
+    }
+
+    #[test]
+    fn test_parse_bpb_too_short() {
+        assert!(BiosParameterBlock::parse(&[0u8; 51]).is_none());
+        assert!(BiosParameterBlock::parse(&[0u8; 52]).is_some());
     }
-}h
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,268 @@
+// Cache de secteurs devant un autre `BlockDevice`.
+//
+// Le parcours d'une chaîne de clusters relit sans cesse le même secteur de FAT
+// (un secteur de 512 octets couvre 128 clusters consécutifs). `CachedDevice`
+// garde les derniers secteurs lus dans un petit tableau de taille fixe, sans
+// allocation, et remplace le moins récemment utilisé (LRU) quand il est plein.
+
+use core::cell::{Cell, RefCell};
+
+use crate::block_device::{BlockDevice, BlockDeviceError};
+
+// Taille des secteurs mis en cache ; les devices dont `SECTOR_SIZE` diffère
+// sont servis directement, sans cache.
+const CACHED_SECTOR_SIZE: usize = 512;
+
+#[derive(Clone, Copy)]
+struct CacheLine {
+    lba: u32,
+    valid: bool,
+    // Valeur de l'horloge logique lors du dernier accès (pour le LRU).
+    last_used: u64,
+    data: [u8; CACHED_SECTOR_SIZE],
+}
+
+impl CacheLine {
+    const EMPTY: CacheLine = CacheLine {
+        lba: 0,
+        valid: false,
+        last_used: 0,
+        data: [0u8; CACHED_SECTOR_SIZE],
+    };
+}
+
+/// Device qui garde en mémoire les `N` derniers secteurs lus.
+///
+/// Seules les lectures d'un secteur unique passent par le cache (accès à la
+/// FAT, entrées de répertoire) ; les lectures de plusieurs secteurs, typiques
+/// des données de fichier, sont déléguées telles quelles pour ne pas évincer
+/// les secteurs de FAT. Toute écriture invalide les secteurs concernés.
+///
+/// Le cache utilise `RefCell` : le device n'est pas `Sync`.
+///
+/// # Exemple
+///
+/// ```
+/// use fat32_parser::{BlockDevice, BlockDeviceError};
+/// use fat32_parser::cached_device::CachedDevice;
+///
+/// struct Zeroes;
+///
+/// impl BlockDevice for Zeroes {
+///     fn read_sectors(&self, _lba: u32, count: u32, buf: &mut [u8]) -> Result<(), BlockDeviceError> {
+///         buf[..count as usize * 512].fill(0);
+///         Ok(())
+///     }
+/// }
+///
+/// let dev: CachedDevice<Zeroes> = CachedDevice::new(Zeroes);
+/// let mut sector = [0xFFu8; 512];
+/// dev.read_sectors(7, 1, &mut sector).unwrap();
+/// dev.read_sectors(7, 1, &mut sector).unwrap();
+/// assert_eq!(dev.hits(), 1);
+/// assert_eq!(dev.misses(), 1);
+/// ```
+pub struct CachedDevice<D, const N: usize = 8> {
+    inner: D,
+    lines: RefCell<[CacheLine; N]>,
+    clock: Cell<u64>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+impl<D: BlockDevice, const N: usize> CachedDevice<D, N> {
+    /// Place un cache vide devant `inner`.
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            lines: RefCell::new([CacheLine::EMPTY; N]),
+            clock: Cell::new(0),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    /// Device sous-jacent.
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Récupère le device sous-jacent.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    /// Nombre de lectures servies par le cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.get()
+    }
+
+    /// Nombre de lectures déléguées au device sous-jacent.
+    pub fn misses(&self) -> u64 {
+        self.misses.get()
+    }
+
+    /// Vide le cache (ex: après une modification du support par un autre moyen).
+    pub fn invalidate_all(&self) {
+        for line in self.lines.borrow_mut().iter_mut() {
+            line.valid = false;
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        let now = self.clock.get() + 1;
+        self.clock.set(now);
+        now
+    }
+
+    // Lit un secteur unique en passant par le cache.
+    fn read_cached(&self, lba: u32, buf: &mut [u8]) -> Result<(), BlockDeviceError> {
+        if buf.len() < CACHED_SECTOR_SIZE {
+            return Err(BlockDeviceError::OutOfBounds);
+        }
+        let now = self.tick();
+        let mut lines = self.lines.borrow_mut();
+
+        if let Some(line) = lines.iter_mut().find(|l| l.valid && l.lba == lba) {
+            line.last_used = now;
+            buf[..CACHED_SECTOR_SIZE].copy_from_slice(&line.data);
+            self.hits.set(self.hits.get() + 1);
+            return Ok(());
+        }
+
+        self.misses.set(self.misses.get() + 1);
+        // Ligne libre en priorité, sinon la moins récemment utilisée.
+        let Some(victim) = lines
+            .iter_mut()
+            .min_by_key(|l| if l.valid { l.last_used } else { 0 })
+        else {
+            // Cache de taille nulle : simple passe-plat.
+            return self.inner.read_sectors(lba, 1, buf);
+        };
+
+        victim.valid = false;
+        self.inner.read_sectors(lba, 1, &mut victim.data)?;
+        victim.lba = lba;
+        victim.valid = true;
+        victim.last_used = now;
+        buf[..CACHED_SECTOR_SIZE].copy_from_slice(&victim.data);
+        Ok(())
+    }
+}
+
+impl<D: BlockDevice, const N: usize> BlockDevice for CachedDevice<D, N> {
+    const SECTOR_SIZE: usize = D::SECTOR_SIZE;
+
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        if count == 1 && D::SECTOR_SIZE == CACHED_SECTOR_SIZE {
+            return self.read_cached(lba, buf);
+        }
+        self.inner.read_sectors(lba, count, buf)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        // Invalidation avant l'écriture : même en cas d'échec partiel, le
+        // cache ne peut pas servir une version périmée.
+        let end = lba as u64 + count as u64;
+        for line in self.lines.borrow_mut().iter_mut() {
+            if line.valid && (line.lba as u64) >= lba as u64 && (line.lba as u64) < end {
+                line.valid = false;
+            }
+        }
+        self.inner.write_sectors(lba, count, buf)
+    }
+
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        self.inner.sector_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boot_sector::Fat32Geometry;
+    use crate::filesystem::Fat32Fs;
+    use crate::testing::{test_geometry, CountingDevice, MemDevice};
+
+    #[test]
+    fn test_hit_and_miss() {
+        let mut mem = MemDevice::new(2);
+        mem.write_data(2, &[0xAB; 512]);
+        let dev: CachedDevice<_> = CachedDevice::new(CountingDevice::new(mem));
+
+        let mut buf = [0u8; 512];
+        dev.read_sectors(2, 1, &mut buf).unwrap();
+        dev.read_sectors(2, 1, &mut buf).unwrap();
+        assert!(buf.iter().all(|&b| b == 0xAB));
+        assert_eq!(dev.inner().reads(), 1);
+        assert_eq!((dev.hits(), dev.misses()), (1, 1));
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let dev: CachedDevice<_, 2> = CachedDevice::new(CountingDevice::new(MemDevice::new(2)));
+        let mut buf = [0u8; 512];
+
+        dev.read_sectors(0, 1, &mut buf).unwrap();
+        dev.read_sectors(1, 1, &mut buf).unwrap();
+        dev.read_sectors(0, 1, &mut buf).unwrap(); // 0 redevient le plus récent
+        dev.read_sectors(2, 1, &mut buf).unwrap(); // évince 1
+        assert_eq!(dev.inner().reads(), 3);
+
+        dev.read_sectors(0, 1, &mut buf).unwrap();
+        assert_eq!(dev.inner().reads(), 3);
+        dev.read_sectors(1, 1, &mut buf).unwrap();
+        assert_eq!(dev.inner().reads(), 4);
+    }
+
+    #[test]
+    fn test_write_invalidates() {
+        let dev: CachedDevice<_> = CachedDevice::new(MemDevice::new(2));
+        let mut buf = [0u8; 512];
+        dev.read_sectors(2, 1, &mut buf).unwrap();
+
+        dev.write_sectors(2, 1, &[0x5A; 512]).unwrap();
+        dev.read_sectors(2, 1, &mut buf).unwrap();
+        assert!(buf.iter().all(|&b| b == 0x5A));
+        assert_eq!(dev.misses(), 2);
+    }
+
+    // Chaîne de 100 clusters qui alterne entre deux secteurs de FAT
+    // (2 -> 130 -> 3 -> 131 -> ...) : le cache interne de `Fat32Fs`, limité
+    // au dernier secteur lu, relit la FAT à chaque cluster.
+    #[test]
+    fn test_chain_walk_reads_fat_sectors_once() {
+        let mut mem = MemDevice::new(200);
+        for i in 0..50 {
+            mem.set_fat(2 + i, 130 + i);
+            mem.set_fat(130 + i, 3 + i);
+        }
+        mem.set_fat(179, 0x0FFFFFFF);
+        let geom = || Fat32Geometry {
+            fat_size: 2,
+            first_data_sector: 3,
+            total_sectors: 202,
+            ..test_geometry()
+        };
+
+        let plain = CountingDevice::new(mem);
+        let fs = Fat32Fs::new(&plain, geom());
+        fs.read_cluster_chain(2, |_, _| Ok(())).unwrap();
+        assert_eq!(plain.reads(), 200);
+
+        let cached: CachedDevice<_> = CachedDevice::new(CountingDevice::new(plain.into_inner()));
+        let fs = Fat32Fs::new(&cached, geom());
+        fs.read_cluster_chain(2, |_, _| Ok(())).unwrap();
+        assert_eq!(cached.inner().reads(), 102);
+    }
+}
@@ -0,0 +1,415 @@
+// Périphériques bloc en mémoire, pratiques pour les tests et l'outillage :
+// - `SliceDevice` lit une image disque empruntée (`&[u8]`), sans allocation ;
+// - `RamDevice` possède son image dans un `Vec<u8>` et supporte l'écriture ;
+// - `FileDevice` lit et écrit un fichier image du système hôte (feature `std`).
+
+#[cfg(any(test, feature = "alloc"))]
+use alloc::vec::Vec;
+#[cfg(any(test, feature = "alloc"))]
+use core::cell::RefCell;
+
+use crate::block_device::{BlockDevice, BlockDeviceError};
+
+// Calcule la plage d'octets couverte par `count` secteurs à partir de `lba`,
+// ou `OutOfBounds` si elle dépasse l'image ou le buffer. Les calculs sont
+// vérifiés : un LBA absurde (BPB corrompue) ne peut pas déborder, même avec
+// un `usize` de 32 bits.
+fn sector_range(
+    lba: u32,
+    count: u32,
+    sector_size: usize,
+    image_len: usize,
+    buf_len: usize,
+) -> Result<core::ops::Range<usize>, BlockDeviceError> {
+    let start = (lba as usize).checked_mul(sector_size);
+    let end = (lba as usize)
+        .checked_add(count as usize)
+        .and_then(|sectors| sectors.checked_mul(sector_size));
+    match (start, end) {
+        (Some(start), Some(end)) if end <= image_len && end - start <= buf_len => Ok(start..end),
+        _ => Err(BlockDeviceError::OutOfBounds),
+    }
+}
+
+// Nombre de secteurs complets d'une image de `len` octets, plafonné à
+// `u32::MAX` (au-delà, les secteurs ne sont pas adressables).
+fn sectors_in(len: u64, sector_size: usize) -> u32 {
+    u32::try_from(len / sector_size as u64).unwrap_or(u32::MAX)
+}
+
+/// Périphérique en lecture seule adossé à une image disque en mémoire.
+#[cfg(any(test, feature = "slice-device"))]
+pub struct SliceDevice<'a> {
+    image: &'a [u8],
+}
+
+#[cfg(any(test, feature = "slice-device"))]
+impl<'a> SliceDevice<'a> {
+    /// Crée un device sur `image` (secteurs de 512 octets à la suite).
+    pub fn new(image: &'a [u8]) -> Self {
+        Self { image }
+    }
+
+    /// Image disque sous-jacente.
+    pub fn image(&self) -> &'a [u8] {
+        self.image
+    }
+}
+
+#[cfg(any(test, feature = "slice-device"))]
+impl BlockDevice for SliceDevice<'_> {
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        let range = sector_range(lba, count, Self::SECTOR_SIZE, self.image.len(), buf.len())?;
+        buf[..range.len()].copy_from_slice(&self.image[range]);
+        Ok(())
+    }
+
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        Ok(sectors_in(self.image.len() as u64, Self::SECTOR_SIZE))
+    }
+}
+
+/// Périphérique en lecture/écriture dont l'image est stockée dans un `Vec<u8>`.
+///
+/// L'image est protégée par un `RefCell` car le trait [`BlockDevice`] ne prend
+/// que `&self` : le device n'est donc pas `Sync`.
+#[cfg(any(test, feature = "alloc"))]
+pub struct RamDevice {
+    image: RefCell<Vec<u8>>,
+}
+
+#[cfg(any(test, feature = "alloc"))]
+impl RamDevice {
+    /// Crée un device de `sectors` secteurs remplis de zéros.
+    pub fn new(sectors: u32) -> Self {
+        Self::from_vec(alloc::vec![0u8; sectors as usize * Self::SECTOR_SIZE])
+    }
+
+    /// Crée un device à partir d'une image disque existante.
+    pub fn from_vec(image: Vec<u8>) -> Self {
+        Self {
+            image: RefCell::new(image),
+        }
+    }
+
+    /// Récupère l'image disque.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.image.into_inner()
+    }
+}
+
+#[cfg(any(test, feature = "alloc"))]
+impl BlockDevice for RamDevice {
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        let image = self.image.borrow();
+        let range = sector_range(lba, count, Self::SECTOR_SIZE, image.len(), buf.len())?;
+        buf[..range.len()].copy_from_slice(&image[range]);
+        Ok(())
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        let mut image = self.image.borrow_mut();
+        let range = sector_range(lba, count, Self::SECTOR_SIZE, image.len(), buf.len())?;
+        let len = range.len();
+        image[range].copy_from_slice(&buf[..len]);
+        Ok(())
+    }
+
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        Ok(sectors_in(self.image.borrow().len() as u64, Self::SECTOR_SIZE))
+    }
+}
+
+/// Périphérique adossé à un fichier image du système hôte (ex: `disk.img`).
+///
+/// Le trait [`BlockDevice`] ne prend que `&self` alors qu'une lecture
+/// nécessite un `seek` suivi d'un `read_exact` : le fichier est donc placé
+/// dans un `Mutex`. Chaque opération garde le verrou du `seek` jusqu'à la fin
+/// du transfert, ce qui rend le device `Sync` et utilisable depuis plusieurs
+/// threads sans qu'une lecture ne déplace le curseur d'une autre.
+///
+/// La taille du fichier est celle du device : un accès au-delà, en lecture
+/// comme en écriture, retourne [`BlockDeviceError::OutOfBounds`] sans
+/// agrandir le fichier (utiliser [`std::fs::File::set_len`] au préalable).
+/// Elle est lue au premier accès puis gardée en mémoire : le fichier ne doit
+/// pas être redimensionné pendant que le device l'utilise.
+#[cfg(any(test, feature = "std"))]
+pub struct FileDevice {
+    file: std::sync::Mutex<FileState>,
+}
+
+// Fichier et sa taille en octets, lue au premier accès (un `fstat` par
+// opération coûterait un appel système de plus à chaque secteur).
+#[cfg(any(test, feature = "std"))]
+struct FileState {
+    file: std::fs::File,
+    len: Option<u64>,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl FileState {
+    // Taille du fichier, lue une seule fois.
+    fn len(&mut self) -> Result<u64, BlockDeviceError> {
+        if let Some(len) = self.len {
+            return Ok(len);
+        }
+        let len = self.file.metadata().map_err(|_| BlockDeviceError::IoError)?.len();
+        self.len = Some(len);
+        Ok(len)
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl FileDevice {
+    /// Crée un device à partir d'un fichier déjà ouvert (en écriture si
+    /// `write_sectors` doit être utilisé).
+    pub fn new(file: std::fs::File) -> Self {
+        Self {
+            file: std::sync::Mutex::new(FileState { file, len: None }),
+        }
+    }
+
+    /// Ouvre le fichier image `path` en lecture/écriture.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new().read(true).write(true).open(path)?;
+        Ok(Self::new(file))
+    }
+
+    /// Récupère le fichier sous-jacent.
+    pub fn into_inner(self) -> std::fs::File {
+        self.file.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()).file
+    }
+
+    // Verrouille le fichier, vérifie que les `count` secteurs à partir de
+    // `lba` sont dans le fichier et tiennent dans un buffer de `buf_len`
+    // octets, puis le positionne au début du secteur `lba`. Retourne aussi
+    // le nombre d'octets à transférer.
+    fn seek_to(
+        &self,
+        lba: u32,
+        count: u32,
+        buf_len: usize,
+    ) -> Result<(std::sync::MutexGuard<'_, FileState>, usize), BlockDeviceError> {
+        use std::io::Seek;
+
+        let mut state = self.file.lock().map_err(|_| BlockDeviceError::IoError)?;
+        let file_len = state.len()?;
+        // En u64, les produits de deux u32 ne débordent pas
+        let sector_size = Self::SECTOR_SIZE as u64;
+        let len = count as u64 * sector_size;
+        let end = (lba as u64 + count as u64) * sector_size;
+        if end > file_len || len > buf_len as u64 {
+            return Err(BlockDeviceError::OutOfBounds);
+        }
+
+        state
+            .file
+            .seek(std::io::SeekFrom::Start(lba as u64 * sector_size))
+            .map_err(|_| BlockDeviceError::IoError)?;
+        Ok((state, len as usize))
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl BlockDevice for FileDevice {
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        use std::io::Read;
+
+        let (mut state, len) = self.seek_to(lba, count, buf.len())?;
+        state.file.read_exact(&mut buf[..len])
+            .map_err(|_| BlockDeviceError::IoError)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        use std::io::Write;
+
+        let (mut state, len) = self.seek_to(lba, count, buf.len())?;
+        state.file.write_all(&buf[..len])
+            .map_err(|_| BlockDeviceError::IoError)
+    }
+
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        let mut state = self.file.lock().map_err(|_| BlockDeviceError::IoError)?;
+        Ok(sectors_in(state.len()?, Self::SECTOR_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_device_read() {
+        let mut image = [0u8; 1536];
+        image[512..1024].fill(1);
+        image[1024..].fill(2);
+        let dev = SliceDevice::new(&image);
+
+        let mut buf = [0u8; 1024];
+        dev.read_sectors(1, 2, &mut buf).unwrap();
+        assert!(buf[..512].iter().all(|&b| b == 1));
+        assert!(buf[512..].iter().all(|&b| b == 2));
+
+        assert!(matches!(
+            dev.read_sectors(2, 2, &mut buf),
+            Err(BlockDeviceError::OutOfBounds)
+        ));
+        assert!(matches!(
+            dev.write_sectors(0, 1, &buf),
+            Err(BlockDeviceError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn test_slice_device_bounds() {
+        let image = [0u8; 2048];
+        let dev = SliceDevice::new(&image);
+        let mut buf = [0u8; 1024];
+
+        // Jusqu'au dernier secteur inclus, puis un secteur au-delà
+        dev.read_sectors(3, 1, &mut buf).unwrap();
+        dev.read_sectors(2, 2, &mut buf).unwrap();
+        for (lba, count) in [(4, 1), (3, 2), (u32::MAX, 1), (u32::MAX, u32::MAX)] {
+            assert!(matches!(
+                dev.read_sectors(lba, count, &mut buf),
+                Err(BlockDeviceError::OutOfBounds)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_slice_device_buffer_too_small() {
+        let image = [0u8; 1024];
+        let dev = SliceDevice::new(&image);
+        let mut buf = [0u8; 100];
+        assert!(matches!(
+            dev.read_sectors(0, 1, &mut buf),
+            Err(BlockDeviceError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn test_slice_device_sector_count() {
+        let image = [0u8; 1024];
+        assert_eq!(SliceDevice::new(&image).sector_count().unwrap(), 2);
+        // Le secteur partiel en fin d'image n'est pas compté
+        let image = [0u8; 1100];
+        assert_eq!(SliceDevice::new(&image).sector_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_ram_device_round_trip() {
+        let dev = RamDevice::new(4);
+        let data = [0x5Au8; 512];
+        dev.write_sectors(3, 1, &data).unwrap();
+
+        let mut buf = [0u8; 512];
+        dev.read_sectors(3, 1, &mut buf).unwrap();
+        assert_eq!(buf, data);
+        // Un secteur au-delà du dernier, et des LBA absurdes
+        for (lba, count) in [(4, 1), (3, 2), (u32::MAX, 1)] {
+            assert!(matches!(
+                dev.write_sectors(lba, count, &data),
+                Err(BlockDeviceError::OutOfBounds)
+            ));
+            assert!(matches!(
+                dev.read_sectors(lba, count, &mut buf),
+                Err(BlockDeviceError::OutOfBounds)
+            ));
+        }
+        assert_eq!(dev.sector_count().unwrap(), 4);
+
+        let image = dev.into_inner();
+        assert_eq!(image.len(), 2048);
+        assert!(image[1536..].iter().all(|&b| b == 0x5A));
+    }
+
+    #[test]
+    fn test_ram_device_mount_round_trip() {
+        use crate::filesystem::Mode;
+        use crate::testing::{fat32_image, UnsizedDevice};
+        use crate::Fat32Fs;
+
+        // Image complète : secteur de boot, FAT, racine et un fichier
+        let image = fat32_image();
+        let dev = UnsizedDevice(RamDevice::from_vec(image.clone()));
+        let fs = Fat32Fs::mount_with_mode(&dev, &image[..512], Mode::ReadWrite).unwrap();
+        assert_eq!(fs.read_file_to_vec("/HELLO.TXT").unwrap(), b"Hello, world!");
+
+        fs.create_file(0, "Notes du jour.txt").unwrap();
+        let mut file = fs.open("/Notes du jour.txt").unwrap();
+        let data = [0x42u8; 700];
+        assert_eq!(file.write(&fs, &data).unwrap(), 700);
+
+        // Les écritures sont dans l'image : un nouveau montage les relit
+        let image = dev.0.into_inner();
+        let dev = UnsizedDevice(RamDevice::from_vec(image.clone()));
+        let fs = Fat32Fs::mount(&dev, &image[..512]).unwrap();
+        assert_eq!(fs.read_file_to_vec("/notes du jour.txt").unwrap(), data);
+        let names: Vec<_> = fs
+            .read_root_dir()
+            .unwrap()
+            .map(|entry| std::string::String::from(entry.unwrap().name()))
+            .collect();
+        assert_eq!(names, ["HELLO.TXT", "Notes du jour.txt"]);
+    }
+
+    #[test]
+    fn test_file_device_round_trip() {
+        let path = std::env::temp_dir().join(std::format!(
+            "fat32_parser_file_device_{}.img",
+            std::process::id()
+        ));
+        std::fs::write(&path, [0u8; 2048]).unwrap();
+
+        let dev = FileDevice::open(&path).unwrap();
+        assert_eq!(dev.sector_count().unwrap(), 4);
+        let data = [0xC3u8; 512];
+        dev.write_sectors(2, 1, &data).unwrap();
+
+        let mut buf = [0u8; 1024];
+        dev.read_sectors(1, 2, &mut buf).unwrap();
+        assert!(buf[..512].iter().all(|&b| b == 0));
+        assert_eq!(&buf[512..], &data[..]);
+
+        // Dernier secteur du fichier, puis un secteur au-delà
+        dev.read_sectors(3, 1, &mut buf).unwrap();
+        for (lba, count) in [(4, 1), (3, 2), (u32::MAX, 2)] {
+            assert!(matches!(
+                dev.read_sectors(lba, count, &mut buf),
+                Err(BlockDeviceError::OutOfBounds)
+            ));
+        }
+        // Le fichier n'est pas agrandi par une écriture hors limites
+        assert!(matches!(dev.write_sectors(4, 1, &data), Err(BlockDeviceError::OutOfBounds)));
+        assert_eq!(dev.sector_count().unwrap(), 4);
+
+        drop(dev);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,6 +1,12 @@
+use crate::boot_sector::{read_u16, read_u32};
+use crate::error::{Fat32Error, Result};
+use crate::timestamp::{decode_date, Timestamp};
+
 // Représente une entrée de répertoire FAT32 brute sur 32 octets.
-// Ici on ne gère que les noms courts (8.3), pas les noms longs.
+// Elle ne porte que le nom court (8.3) : les noms longs sont stockés dans
+// des entrées LFN séparées, reconstruites par `DirectoryIterator::next_file`.
 #[repr(C, packed)]
+#[derive(Clone, Copy)]
 pub struct DirectoryEntryRaw {
     pub name: [u8; 11],
     pub attributes: u8,
@@ -16,15 +22,110 @@ pub struct DirectoryEntryRaw {
     pub file_size: u32,
 }
 
+// Champs copiés avant l'affichage : la structure est `packed` (voir le
+// `Debug` de `BiosParameterBlock`).
+impl core::fmt::Debug for DirectoryEntryRaw {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self {
+            name,
+            attributes,
+            reserved,
+            creation_time_tenth,
+            creation_time,
+            creation_date,
+            last_access_date,
+            first_cluster_high,
+            write_time,
+            write_date,
+            first_cluster_low,
+            file_size,
+        } = *self;
+        f.debug_struct("DirectoryEntryRaw")
+            .field("name", &ShortNameDebug(&name))
+            .field("attributes", &attributes)
+            .field("reserved", &reserved)
+            .field("creation_time_tenth", &creation_time_tenth)
+            .field("creation_time", &creation_time)
+            .field("creation_date", &creation_date)
+            .field("last_access_date", &last_access_date)
+            .field("first_cluster_high", &first_cluster_high)
+            .field("write_time", &write_time)
+            .field("write_date", &write_date)
+            .field("first_cluster_low", &first_cluster_low)
+            .field("file_size", &file_size)
+            .finish()
+    }
+}
+
+// Affiche un nom 8.3 brut comme une chaîne, les octets non ASCII en `\xNN`.
+struct ShortNameDebug<'n>(&'n [u8; 11]);
+
+impl core::fmt::Debug for ShortNameDebug<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "\"{}\"", self.0.escape_ascii())
+    }
+}
+
 impl DirectoryEntryRaw {
+    /// Décode une entrée de 32 octets champ par champ (little-endian),
+    /// sans cast de pointeur.
+    pub fn parse(bytes: &[u8; 32]) -> DirectoryEntryRaw {
+        let mut name = [0u8; 11];
+        name.copy_from_slice(&bytes[..11]);
+
+        DirectoryEntryRaw {
+            name,
+            attributes: bytes[11],
+            reserved: bytes[12],
+            creation_time_tenth: bytes[13],
+            creation_time: read_u16(bytes, 14),
+            creation_date: read_u16(bytes, 16),
+            last_access_date: read_u16(bytes, 18),
+            first_cluster_high: read_u16(bytes, 20),
+            write_time: read_u16(bytes, 22),
+            write_date: read_u16(bytes, 24),
+            first_cluster_low: read_u16(bytes, 26),
+            file_size: read_u32(bytes, 28),
+        }
+    }
+
+    /// Encode l'entrée en 32 octets (little-endian), inverse de
+    /// [`DirectoryEntryRaw::parse`].
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[..11].copy_from_slice(&self.name);
+        bytes[11] = self.attributes;
+        bytes[12] = self.reserved;
+        bytes[13] = self.creation_time_tenth;
+        bytes[14..16].copy_from_slice(&{ self.creation_time }.to_le_bytes());
+        bytes[16..18].copy_from_slice(&{ self.creation_date }.to_le_bytes());
+        bytes[18..20].copy_from_slice(&{ self.last_access_date }.to_le_bytes());
+        bytes[20..22].copy_from_slice(&{ self.first_cluster_high }.to_le_bytes());
+        bytes[22..24].copy_from_slice(&{ self.write_time }.to_le_bytes());
+        bytes[24..26].copy_from_slice(&{ self.write_date }.to_le_bytes());
+        bytes[26..28].copy_from_slice(&{ self.first_cluster_low }.to_le_bytes());
+        bytes[28..32].copy_from_slice(&{ self.file_size }.to_le_bytes());
+        bytes
+    }
+
     // True si l'entrée est libre ou marquée comme supprimée.
     pub fn is_unused(&self) -> bool {
         self.name[0] == 0x00 || self.name[0] == 0xE5
     }
 
+    /// Attributs de l'entrée, avec des accesseurs nommés.
+    pub fn attributes(&self) -> Attributes {
+        Attributes(self.attributes)
+    }
+
     // True si l'entrée correspond à un dossier.
     pub fn is_dir(&self) -> bool {
-        self.attributes & 0x10 != 0
+        self.attributes().is_directory()
+    }
+
+    // True si l'entrée est un fragment de nom long (attribut 0x0F).
+    pub fn is_lfn(&self) -> bool {
+        self.attributes().is_lfn()
     }
 
     // Récupère le numéro de premier cluster (high + low).
@@ -32,4 +133,1007 @@ impl DirectoryEntryRaw {
         ((self.first_cluster_high as u32) << 16)
             | (self.first_cluster_low as u32)
     }
-}
\ No newline at end of file
+
+    /// Premier cluster des données, ou `None` pour un fichier vide.
+    ///
+    /// Un fichier de taille nulle n'a aucun cluster alloué : FAT32 stocke
+    /// alors 0, qui n'est pas un numéro de cluster valide.
+    pub fn first_cluster_opt(&self) -> Option<u32> {
+        match self.first_cluster() {
+            0 => None,
+            cluster => Some(cluster),
+        }
+    }
+
+    /// Date et heure de création (précision au centième de seconde).
+    pub fn created(&self) -> Timestamp {
+        Timestamp::from_dos(self.creation_date, self.creation_time, self.creation_time_tenth)
+    }
+
+    /// Date et heure de dernière modification (précision de 2 secondes).
+    pub fn modified(&self) -> Timestamp {
+        Timestamp::from_dos(self.write_date, self.write_time, 0)
+    }
+
+    /// Date de dernier accès `(année, mois, jour)` : FAT32 ne stocke pas l'heure.
+    pub fn accessed(&self) -> (u16, u8, u8) {
+        decode_date(self.last_access_date)
+    }
+
+    /// True si l'entrée est `.` ou `..` (nom 8.3 exact, complété par des espaces).
+    pub fn is_dot_entry(&self) -> bool {
+        self.name == DOT_NAME || self.name == DOTDOT_NAME
+    }
+
+    /// Décode le nom court 8.3 sous la forme `"README.TXT"` dans `out`.
+    ///
+    /// Les espaces de remplissage sont retirés, le `.` n'est ajouté que si
+    /// l'extension existe, et un premier octet `0x05` est restitué en `0xE5`
+    /// (caractère réel que le format FAT ne peut pas stocker tel quel).
+    /// Les entrées `.` et `..` sont rendues telles quelles.
+    ///
+    /// Les drapeaux de casse de l'octet réservé (offset 12) sont respectés :
+    /// [`CASE_LOWER_BASE`] et [`CASE_LOWER_EXT`] mettent respectivement le nom
+    /// et l'extension en minuscules (lettres ASCII uniquement).
+    ///
+    /// Retourne le nombre d'octets écrits, ce qui évite toute allocation.
+    pub fn short_name(&self, out: &mut [u8; 12]) -> usize {
+        let mut name = self.name;
+        if name[0] == 0x05 {
+            name[0] = 0xE5;
+        }
+        if self.reserved & CASE_LOWER_BASE != 0 {
+            name[..8].make_ascii_lowercase();
+        }
+        if self.reserved & CASE_LOWER_EXT != 0 {
+            name[8..].make_ascii_lowercase();
+        }
+
+        let base = trim_spaces(&name[..8]);
+        let ext = trim_spaces(&name[8..]);
+
+        let mut len = base.len();
+        out[..len].copy_from_slice(base);
+        if !ext.is_empty() {
+            out[len] = b'.';
+            out[len + 1..len + 1 + ext.len()].copy_from_slice(ext);
+            len += 1 + ext.len();
+        }
+        len
+    }
+}
+
+/// Valeurs brutes des bits de l'octet d'attributs.
+pub mod attr {
+    /// Fichier en lecture seule.
+    pub const READ_ONLY: u8 = 0x01;
+    /// Fichier caché.
+    pub const HIDDEN: u8 = 0x02;
+    /// Fichier système.
+    pub const SYSTEM: u8 = 0x04;
+    /// Entrée portant le nom du volume (volume label).
+    pub const VOLUME_ID: u8 = 0x08;
+    /// Dossier.
+    pub const DIRECTORY: u8 = 0x10;
+    /// Fichier modifié depuis la dernière sauvegarde.
+    pub const ARCHIVE: u8 = 0x20;
+    /// Combinaison qui identifie un fragment de nom long.
+    pub const LONG_NAME: u8 = super::LFN_ATTRIBUTE;
+}
+
+/// Octet d'attributs d'une entrée de répertoire.
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::{attr, Attributes};
+///
+/// let attrs = Attributes(attr::DIRECTORY | attr::HIDDEN);
+/// assert!(attrs.is_directory());
+/// assert!(attrs.is_hidden());
+/// assert!(!attrs.is_lfn());
+/// assert!(Attributes(attr::LONG_NAME).is_lfn());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Attributes(pub u8);
+
+impl Attributes {
+    /// Valeur brute de l'octet.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// True si tous les bits de `mask` sont posés.
+    pub fn contains(&self, mask: u8) -> bool {
+        self.0 & mask == mask
+    }
+
+    /// True si le fichier est en lecture seule.
+    pub fn is_read_only(&self) -> bool {
+        self.contains(attr::READ_ONLY)
+    }
+
+    /// True si le fichier est caché.
+    pub fn is_hidden(&self) -> bool {
+        self.contains(attr::HIDDEN)
+    }
+
+    /// True si le fichier est un fichier système.
+    pub fn is_system(&self) -> bool {
+        self.contains(attr::SYSTEM)
+    }
+
+    /// True si l'entrée porte le nom du volume (les fragments LFN ont aussi ce bit).
+    pub fn is_volume_id(&self) -> bool {
+        self.contains(attr::VOLUME_ID)
+    }
+
+    /// True si l'entrée est un dossier.
+    pub fn is_directory(&self) -> bool {
+        self.contains(attr::DIRECTORY)
+    }
+
+    /// True si le bit d'archive est posé.
+    pub fn is_archive(&self) -> bool {
+        self.contains(attr::ARCHIVE)
+    }
+
+    /// True si l'entrée est un fragment de nom long (`0x0F`, bits réservés ignorés).
+    pub fn is_lfn(&self) -> bool {
+        self.0 & LFN_ATTRIBUTE_MASK == LFN_ATTRIBUTE
+    }
+}
+
+/// Drapeau de l'octet réservé (offset 12) : nom de base à afficher en minuscules.
+pub const CASE_LOWER_BASE: u8 = 0x08;
+
+/// Drapeau de l'octet réservé (offset 12) : extension à afficher en minuscules.
+pub const CASE_LOWER_EXT: u8 = 0x10;
+
+/// Valeur de l'octet d'attributs qui identifie une entrée de nom long (LFN).
+pub const LFN_ATTRIBUTE: u8 = 0x0F;
+
+/// Masque appliqué aux attributs avant de tester [`LFN_ATTRIBUTE`]
+/// (les deux bits de poids fort sont réservés).
+pub const LFN_ATTRIBUTE_MASK: u8 = 0x3F;
+
+/// Drapeau posé sur le numéro de séquence du dernier fragment LFN
+/// (qui est stocké en premier sur le disque).
+pub const LFN_LAST_ENTRY: u8 = 0x40;
+
+/// Nom 8.3 de l'entrée `.` d'un sous-répertoire (le répertoire lui-même).
+pub const DOT_NAME: [u8; 11] = *b".          ";
+
+/// Nom 8.3 de l'entrée `..` d'un sous-répertoire (son parent).
+pub const DOTDOT_NAME: [u8; 11] = *b"..         ";
+
+/// Nombre de caractères UTF-16 stockés dans un fragment LFN.
+pub const LFN_CHARS_PER_ENTRY: usize = 13;
+
+/// Nombre maximal de fragments LFN pour un seul nom (255 caractères).
+pub const LFN_MAX_ENTRIES: usize = 20;
+
+/// Taille maximale, en octets UTF-8, d'un nom décodé.
+///
+/// Un nom long fait au plus 255 unités UTF-16, et chaque unité donne au plus
+/// 3 octets en UTF-8 (une paire de substitution donne 4 octets pour 2 unités).
+pub const MAX_NAME_BYTES: usize = 255 * 3;
+
+/// Positions (en octets) des 13 caractères UTF-16 dans un fragment LFN.
+const LFN_CHAR_OFFSETS: [usize; LFN_CHARS_PER_ENTRY] =
+    [1, 3, 5, 7, 9, 14, 16, 18, 20, 22, 24, 28, 30];
+
+/// Extrait les 13 unités UTF-16LE d'un fragment LFN brut.
+pub(crate) fn lfn_units(slot: &[u8; 32]) -> [u16; LFN_CHARS_PER_ENTRY] {
+    let mut units = [0u16; LFN_CHARS_PER_ENTRY];
+    for (unit, &offset) in units.iter_mut().zip(LFN_CHAR_OFFSETS.iter()) {
+        *unit = u16::from_le_bytes([slot[offset], slot[offset + 1]]);
+    }
+    units
+}
+
+/// Construit les fragments LFN du nom `name`, dans l'ordre où ils sont
+/// écrits sur le disque (juste avant l'entrée 8.3 de nom court
+/// `short_name`), et retourne leur nombre.
+///
+/// Le nom est encodé en UTF-16LE et découpé en fragments de 13 caractères.
+/// Le dernier morceau du nom, écrit en premier, porte le drapeau
+/// [`LFN_LAST_ENTRY`] ; s'il est incomplet, il est terminé par `0x0000` puis
+/// complété par `0xFFFF`. Un nom dont la longueur est un multiple de 13 n'a
+/// ni terminateur ni remplissage. Chaque fragment porte la somme de contrôle
+/// de `short_name` (voir [`lfn_checksum`]).
+///
+/// # Errors
+///
+/// - [`Fat32Error::NameTooLong`] si le nom dépasse 255 unités UTF-16
+/// - [`Fat32Error::InvalidPath`] si le nom est vide
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::{build_lfn_entries, LFN_MAX_ENTRIES};
+///
+/// let mut slots = [[0u8; 32]; LFN_MAX_ENTRIES];
+/// let count = build_lfn_entries("Long File Name.html", b"LONGFI~1HTM", &mut slots).unwrap();
+/// assert_eq!(count, 2);
+/// assert_eq!(slots[0][0], 0x42);
+/// assert_eq!(slots[1][0], 0x01);
+/// ```
+pub fn build_lfn_entries(
+    name: &str,
+    short_name: &[u8; 11],
+    out: &mut [[u8; 32]; LFN_MAX_ENTRIES],
+) -> Result<usize> {
+    let mut units = [0xFFFFu16; LFN_MAX_ENTRIES * LFN_CHARS_PER_ENTRY];
+    let mut len = 0;
+    for unit in name.encode_utf16() {
+        if len == MAX_LONG_NAME_UNITS {
+            return Err(Fat32Error::NameTooLong);
+        }
+        units[len] = unit;
+        len += 1;
+    }
+    if len == 0 {
+        return Err(Fat32Error::InvalidPath);
+    }
+    if len % LFN_CHARS_PER_ENTRY != 0 {
+        units[len] = 0x0000;
+    }
+
+    let count = len.div_ceil(LFN_CHARS_PER_ENTRY);
+    let checksum = lfn_checksum(short_name);
+    for (i, slot) in out[..count].iter_mut().enumerate() {
+        let sequence = count - i;
+        *slot = [0u8; 32];
+        slot[0] = sequence as u8;
+        if i == 0 {
+            slot[0] |= LFN_LAST_ENTRY;
+        }
+        slot[11] = LFN_ATTRIBUTE;
+        slot[13] = checksum;
+        let chars = &units[(sequence - 1) * LFN_CHARS_PER_ENTRY..][..LFN_CHARS_PER_ENTRY];
+        for (unit, &offset) in chars.iter().zip(LFN_CHAR_OFFSETS.iter()) {
+            slot[offset..offset + 2].copy_from_slice(&unit.to_le_bytes());
+        }
+    }
+    Ok(count)
+}
+
+/// Calcule la somme de contrôle d'un nom court 8.3, telle que stockée
+/// dans chaque fragment LFN (octet 13).
+///
+/// Algorithme standard : rotation à droite d'un bit puis addition de l'octet suivant.
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::lfn_checksum;
+///
+/// assert_eq!(lfn_checksum(b"README  TXT"), 0x73);
+/// ```
+pub fn lfn_checksum(short_name: &[u8; 11]) -> u8 {
+    short_name
+        .iter()
+        .fold(0u8, |sum, &b| sum.rotate_right(1).wrapping_add(b))
+}
+
+/// Ramène un caractère à sa forme minuscule pour comparer des noms.
+///
+/// Seuls les lettres ASCII et les couples majuscule/minuscule du bloc
+/// Latin-1 (`À`–`Þ` ↔ `à`–`þ`, hors `×`/`÷`) sont traités : ce n'est pas
+/// un repli de casse Unicode complet (ni `Ÿ`/`ÿ`, ni grec, ni cyrillique...).
+pub fn fold_case(c: char) -> char {
+    match c {
+        'A'..='Z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{DE}' => {
+            char::from_u32(c as u32 + 0x20).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// Compare deux noms sans tenir compte de la casse, caractère par caractère
+/// (point de code par point de code), avec le repli de [`fold_case`].
+///
+/// Un nom long stocké en UTF-16 (`"Café.txt"`) et décodé par [`DirEntry`]
+/// correspond ainsi au même nom saisi en UTF-8 (`"CAFÉ.TXT"`).
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::names_equal;
+///
+/// assert!(names_equal("ReadMe.txt", "README.TXT"));
+/// assert!(names_equal("café.txt", "CAFÉ.TXT"));
+/// assert!(!names_equal("cafe.txt", "café.txt"));
+/// ```
+pub fn names_equal(a: &str, b: &str) -> bool {
+    chars_equal(a.chars(), b.chars())
+}
+
+fn chars_equal(a: impl Iterator<Item = char>, b: impl Iterator<Item = char>) -> bool {
+    a.map(fold_case).eq(b.map(fold_case))
+}
+
+/// Vérifie si `name` correspond au motif `pattern`, sans tenir compte de la
+/// casse (comme [`names_equal`]).
+///
+/// `*` correspond à une suite quelconque de caractères (éventuellement vide,
+/// points compris) et `?` à exactement un caractère ; les autres caractères
+/// se comparent un à un.
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::wildcard_match;
+///
+/// assert!(wildcard_match("*.txt", "README.TXT"));
+/// assert!(wildcard_match("data?.bin", "data1.bin"));
+/// assert!(!wildcard_match("*.txt", "archive.tar.gz"));
+/// ```
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let (mut p, mut n) = (pattern, name);
+    // Reprise après le dernier `*` : reste du motif et position dans le nom
+    let mut star: Option<(&str, &str)> = None;
+
+    loop {
+        match p.chars().next() {
+            Some('*') => {
+                p = &p[1..];
+                star = Some((p, n));
+                continue;
+            }
+            Some(c) => {
+                if let Some(d) = n.chars().next() {
+                    if c == '?' || fold_case(c) == fold_case(d) {
+                        p = &p[c.len_utf8()..];
+                        n = &n[d.len_utf8()..];
+                        continue;
+                    }
+                }
+            }
+            None if n.is_empty() => return true,
+            None => {}
+        }
+
+        // Échec : le dernier `*` absorbe un caractère de plus
+        let (star_p, star_n) = match star {
+            Some(resume) => resume,
+            None => return false,
+        };
+        let skipped = match star_n.chars().next() {
+            Some(d) => &star_n[d.len_utf8()..],
+            None => return false,
+        };
+        star = Some((star_p, skipped));
+        p = star_p;
+        n = skipped;
+    }
+}
+
+/// Entrée de répertoire décodée, avec son nom long reconstruit si présent.
+///
+/// Contrairement à [`DirectoryEntryRaw`], cette structure possède ses données :
+/// elle peut être conservée après l'appel suivant de l'itérateur.
+#[derive(Clone, Copy)]
+pub struct DirEntry {
+    name: [u8; MAX_NAME_BYTES],
+    name_len: usize,
+    long_name: bool,
+    raw: DirectoryEntryRaw,
+}
+
+impl DirEntry {
+    /// Construit une entrée à partir de l'entrée 8.3 et, éventuellement,
+    /// des unités UTF-16 du nom long (terminées par `0x0000` ou par la fin du slice).
+    pub(crate) fn new(raw: DirectoryEntryRaw, long_name: Option<&[u16]>) -> Self {
+        let mut entry = Self {
+            name: [0u8; MAX_NAME_BYTES],
+            name_len: 0,
+            long_name: long_name.is_some(),
+            raw,
+        };
+
+        match long_name {
+            Some(units) => {
+                let end = units.iter().position(|&u| u == 0x0000).unwrap_or(units.len());
+                for c in char::decode_utf16(units[..end].iter().copied()) {
+                    entry.push_char(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+            }
+            None => {
+                let mut short = [0u8; 12];
+                let len = raw.short_name(&mut short);
+                for &b in &short[..len] {
+                    entry.push_char(char::from(b));
+                }
+            }
+        }
+
+        entry
+    }
+
+    fn push_char(&mut self, c: char) {
+        let len = c.len_utf8();
+        if self.name_len + len <= MAX_NAME_BYTES {
+            c.encode_utf8(&mut self.name[self.name_len..]);
+            self.name_len += len;
+        }
+    }
+
+    /// Nom de l'entrée : le nom long s'il existe, sinon le nom court 8.3.
+    pub fn name(&self) -> &str {
+        core::str::from_utf8(&self.name[..self.name_len]).unwrap_or("")
+    }
+
+    /// Vérifie si `name` désigne cette entrée : comparaison avec le nom long
+    /// et avec le nom court 8.3, sans tenir compte de la casse (voir
+    /// [`names_equal`]).
+    pub fn matches_name(&self, name: &str) -> bool {
+        if names_equal(self.name(), name) {
+            return true;
+        }
+        let mut short = [0u8; 12];
+        let len = self.raw.short_name(&mut short);
+        chars_equal(short[..len].iter().map(|&b| char::from(b)), name.chars())
+    }
+
+    /// True si le nom provient d'une chaîne d'entrées LFN.
+    pub fn has_long_name(&self) -> bool {
+        self.long_name
+    }
+
+    /// Nom court brut (8 + 3 octets, complétés par des espaces).
+    pub fn short_name(&self) -> [u8; 11] {
+        self.raw.name
+    }
+
+    /// True si l'entrée est `.` ou `..`.
+    pub fn is_dot_entry(&self) -> bool {
+        self.raw.is_dot_entry()
+    }
+
+    /// Attributs de l'entrée.
+    pub fn attributes(&self) -> Attributes {
+        self.raw.attributes()
+    }
+
+    /// True si l'entrée correspond à un dossier.
+    pub fn is_dir(&self) -> bool {
+        self.raw.is_dir()
+    }
+
+    /// Premier cluster des données (0 pour un fichier vide).
+    pub fn first_cluster(&self) -> u32 {
+        self.raw.first_cluster()
+    }
+
+    /// Premier cluster des données, ou `None` pour un fichier vide.
+    pub fn first_cluster_opt(&self) -> Option<u32> {
+        self.raw.first_cluster_opt()
+    }
+
+    /// Taille du fichier en octets.
+    pub fn file_size(&self) -> u32 {
+        self.raw.file_size
+    }
+
+    /// Entrée 8.3 brute dont provient cette entrée.
+    pub fn raw(&self) -> &DirectoryEntryRaw {
+        &self.raw
+    }
+}
+
+/// Entrée vide (nom vide, tous les champs à zéro), pour initialiser les
+/// buffers passés à [`Fat32Fs::find_matching`](crate::Fat32Fs::find_matching).
+impl Default for DirEntry {
+    fn default() -> Self {
+        Self {
+            name: [0u8; MAX_NAME_BYTES],
+            name_len: 0,
+            long_name: false,
+            raw: DirectoryEntryRaw::parse(&[0u8; 32]),
+        }
+    }
+}
+
+/// True si l'octet `b` peut figurer dans un nom court 8.3 : lettre
+/// majuscule, chiffre ou l'un des caractères `! # $ % & ' ( ) - @ ^ _ ` { } ~`.
+pub fn is_short_name_char(b: u8) -> bool {
+    b.is_ascii_uppercase() || b.is_ascii_digit() || b"!#$%&'()-@^_`{}~".contains(&b)
+}
+
+/// Encode `name` en nom court 8.3 s'il peut être stocké sans nom long.
+///
+/// Le nom doit compter au plus 8 caractères, suivis éventuellement d'un
+/// point et d'au plus 3 caractères d'extension, tous valides une fois mis en
+/// majuscules (voir [`is_short_name_char`]). Une partie entièrement en
+/// minuscules est acceptée : les drapeaux [`CASE_LOWER_BASE`] /
+/// [`CASE_LOWER_EXT`] à stocker dans l'octet réservé sont retournés avec le
+/// nom, comme le fait Windows.
+///
+/// Retourne `None` si le nom nécessite une entrée LFN (trop long, plusieurs
+/// points, casse mixte dans une partie, caractère interdit ou non ASCII).
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::{encode_short_name, CASE_LOWER_BASE};
+///
+/// assert_eq!(encode_short_name("README.TXT"), Some((*b"README  TXT", 0)));
+/// assert_eq!(encode_short_name("readme.TXT"), Some((*b"README  TXT", CASE_LOWER_BASE)));
+/// assert_eq!(encode_short_name("ReadMe.txt"), None);
+/// assert_eq!(encode_short_name("archive.tar.gz"), None);
+/// ```
+pub fn encode_short_name(name: &str) -> Option<([u8; 11], u8)> {
+    let (base, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    if base.is_empty() || base.len() > 8 || ext.len() > 3 || name.ends_with('.') {
+        return None;
+    }
+
+    let mut short = [b' '; 11];
+    let mut flags = 0;
+    let (base_field, ext_field) = short.split_at_mut(8);
+    for (part, field, lower_flag) in [
+        (base, base_field, CASE_LOWER_BASE),
+        (ext, ext_field, CASE_LOWER_EXT),
+    ] {
+        let bytes = part.as_bytes();
+        let has_lower = bytes.iter().any(u8::is_ascii_lowercase);
+        if has_lower && bytes.iter().any(u8::is_ascii_uppercase) {
+            return None;
+        }
+        if has_lower {
+            flags |= lower_flag;
+        }
+        for (dst, &b) in field.iter_mut().zip(bytes) {
+            let b = b.to_ascii_uppercase();
+            if !is_short_name_char(b) {
+                return None;
+            }
+            *dst = b;
+        }
+    }
+    Some((short, flags))
+}
+
+/// Nombre maximal d'unités UTF-16 dans un nom long.
+pub const MAX_LONG_NAME_UNITS: usize = 255;
+
+/// Base d'un nom court généré à partir d'un nom long : nom (8 caractères au
+/// plus) et extension (3 au plus) convertis en caractères 8.3, avant ajout
+/// éventuel d'une queue numérique `~N`.
+///
+/// La conversion suit l'algorithme de Windows : passage en majuscules,
+/// suppression des espaces et des points initiaux, remplacement des
+/// caractères interdits en 8.3 par `_`, puis découpage au dernier point (les
+/// points précédents sont supprimés).
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::dir_entry::ShortNameBasis;
+///
+/// let basis = ShortNameBasis::new("archive.tar.gz").unwrap();
+/// assert!(!basis.is_exact());
+/// assert_eq!(basis.with_tail(1), *b"ARCHIV~1GZ ");
+/// assert_eq!(basis.tail_of(b"ARCHI~12GZ "), Some(12));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortNameBasis {
+    base: [u8; 8],
+    base_len: usize,
+    ext: [u8; 3],
+    exact: bool,
+}
+
+impl ShortNameBasis {
+    /// Calcule la base du nom court de `long`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::NameTooLong`] si `long` dépasse 255 unités UTF-16
+    /// - [`Fat32Error::InvalidPath`] si `long` contient un caractère interdit
+    ///   même dans un nom long (`" * / : < > ? \ |` ou caractère de contrôle)
+    ///   ou s'il ne reste aucun caractère une fois espaces et points retirés
+    pub fn new(long: &str) -> Result<Self> {
+        if long.encode_utf16().count() > MAX_LONG_NAME_UNITS {
+            return Err(Fat32Error::NameTooLong);
+        }
+        if long.chars().any(|c| c < ' ' || "\"*/:<>?\\|".contains(c)) {
+            return Err(Fat32Error::InvalidPath);
+        }
+
+        let mut basis = Self {
+            base: [b' '; 8],
+            base_len: 0,
+            ext: [b' '; 3],
+            exact: true,
+        };
+
+        // Les espaces et les points initiaux disparaissent du nom court
+        let trimmed = long.trim_start_matches([' ', '.']);
+        basis.exact = trimmed.len() == long.len();
+        let (base, ext) = trimmed.rsplit_once('.').unwrap_or((trimmed, ""));
+
+        for c in base.chars().filter(|&c| c != ' ' && c != '.') {
+            if basis.base_len == basis.base.len() {
+                basis.exact = false;
+                break;
+            }
+            basis.base[basis.base_len] = basis.convert(c);
+            basis.base_len += 1;
+        }
+        let mut ext_len = 0;
+        for c in ext.chars().filter(|&c| c != ' ') {
+            if ext_len == basis.ext.len() {
+                basis.exact = false;
+                break;
+            }
+            basis.ext[ext_len] = basis.convert(c);
+            ext_len += 1;
+        }
+
+        // Espaces ou points supprimés à l'intérieur du nom
+        let kept = basis.base_len + ext_len + usize::from(!ext.is_empty());
+        if basis.exact && kept != trimmed.chars().count() {
+            basis.exact = false;
+        }
+
+        if basis.base_len == 0 {
+            return Err(Fat32Error::InvalidPath);
+        }
+        Ok(basis)
+    }
+
+    // Convertit un caractère en caractère 8.3, `_` s'il n'y en a pas d'équivalent.
+    fn convert(&mut self, c: char) -> u8 {
+        let b = u8::try_from(c).unwrap_or(0).to_ascii_uppercase();
+        if is_short_name_char(b) {
+            b
+        } else {
+            self.exact = false;
+            b'_'
+        }
+    }
+
+    /// True si la conversion n'a rien perdu du nom long à part la casse :
+    /// le nom court peut alors être utilisé sans queue numérique.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+
+    /// Nom court sans queue numérique.
+    pub fn name(&self) -> [u8; 11] {
+        let mut name = [b' '; 11];
+        name[..8].copy_from_slice(&self.base);
+        name[8..].copy_from_slice(&self.ext);
+        name
+    }
+
+    /// Nom court avec la queue `~n`, la base étant tronquée pour que
+    /// l'ensemble tienne en 8 caractères.
+    pub fn with_tail(&self, n: u32) -> [u8; 11] {
+        let mut digits = [0u8; 10];
+        let mut len = 0;
+        let mut rest = n;
+        loop {
+            digits[len] = b'0' + (rest % 10) as u8;
+            len += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+
+        let mut name = [b' '; 11];
+        let prefix = self.base_len.min(8usize.saturating_sub(len + 1));
+        name[..prefix].copy_from_slice(&self.base[..prefix]);
+        name[prefix] = b'~';
+        for (dst, &digit) in name[prefix + 1..8].iter_mut().zip(digits[..len].iter().rev()) {
+            *dst = digit;
+        }
+        name[8..].copy_from_slice(&self.ext);
+        name
+    }
+
+    /// Numéro de la queue `~N` de `short` s'il s'agit de cette base suivie
+    /// d'une queue, `None` sinon.
+    pub fn tail_of(&self, short: &[u8; 11]) -> Option<u32> {
+        if short[8..] != self.ext {
+            return None;
+        }
+        let base = trim_spaces(&short[..8]);
+        let tilde = base.iter().rposition(|&b| b == b'~')?;
+        let digits = &base[tilde + 1..];
+        if digits.is_empty() || digits[0] == b'0' || !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let n = digits.iter().fold(0u32, |n, &d| n * 10 + (d - b'0') as u32);
+        (self.with_tail(n) == *short).then_some(n)
+    }
+}
+
+// Retire les espaces de remplissage en fin de champ.
+fn trim_spaces(field: &[u8]) -> &[u8] {
+    let end = field.iter().rposition(|&b| b != b' ').map_or(0, |i| i + 1);
+    &field[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &[u8; 11]) -> DirectoryEntryRaw {
+        DirectoryEntryRaw {
+            name: *name,
+            attributes: 0x20,
+            reserved: 0,
+            creation_time_tenth: 0,
+            creation_time: 0,
+            creation_date: 0,
+            last_access_date: 0,
+            first_cluster_high: 0,
+            write_time: 0,
+            write_date: 0,
+            first_cluster_low: 0,
+            file_size: 0,
+        }
+    }
+
+    fn decode(name: &[u8; 11]) -> ([u8; 12], usize) {
+        let mut out = [0u8; 12];
+        let len = entry(name).short_name(&mut out);
+        (out, len)
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let mut bytes = [0u8; 32];
+        bytes[..11].copy_from_slice(b"HELLO   TXT");
+        bytes[11] = 0x20;
+        bytes[20..22].copy_from_slice(&0x0001u16.to_le_bytes());
+        bytes[22..24].copy_from_slice(&0x4A21u16.to_le_bytes());
+        bytes[26..28].copy_from_slice(&0x0005u16.to_le_bytes());
+        bytes[28..32].copy_from_slice(&1234u32.to_le_bytes());
+
+        let raw = DirectoryEntryRaw::parse(&bytes);
+        assert_eq!(raw.name, *b"HELLO   TXT");
+        assert_eq!(raw.attributes, 0x20);
+        assert_eq!({ raw.write_time }, 0x4A21);
+        assert_eq!(raw.first_cluster(), 0x0001_0005);
+        assert_eq!({ raw.file_size }, 1234);
+        assert_eq!(raw.to_bytes(), bytes);
+        let debug = format!("{:?}", raw);
+        assert!(debug.starts_with("DirectoryEntryRaw { name: \"HELLO   TXT\", attributes: 32,"));
+        assert!(debug.ends_with("file_size: 1234 }"));
+    }
+
+    #[test]
+    fn test_attributes() {
+        let mut raw = entry(b"HELLO   TXT");
+        raw.attributes = attr::READ_ONLY | attr::SYSTEM | attr::ARCHIVE;
+        let attrs = raw.attributes();
+        assert!(attrs.is_read_only() && attrs.is_system() && attrs.is_archive());
+        assert!(!attrs.is_hidden() && !attrs.is_directory() && !attrs.is_volume_id());
+
+        // Un fragment LFN porte aussi le bit volume ; les bits réservés sont ignorés
+        let lfn = Attributes(0xC0 | attr::LONG_NAME);
+        assert!(lfn.is_lfn() && lfn.is_volume_id());
+        assert!(!Attributes(attr::VOLUME_ID).is_lfn());
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let mut raw = entry(b"HELLO   TXT");
+        raw.creation_date = 0x4A21;
+        raw.creation_time = 0x0021;
+        raw.creation_time_tenth = 101;
+        raw.write_date = 0x4A21;
+        raw.write_time = 0x0021;
+        raw.last_access_date = 0x4A22;
+
+        let created = raw.created();
+        assert_eq!((created.year, created.month, created.day), (2017, 1, 1));
+        assert_eq!((created.hour, created.minute), (0, 1));
+        assert_eq!((created.second, created.centiseconds), (3, 1));
+
+        let modified = raw.modified();
+        assert_eq!(modified.second, 2);
+        assert_eq!(modified.centiseconds, 0);
+
+        assert_eq!(raw.accessed(), (2017, 1, 2));
+    }
+
+    #[test]
+    fn test_first_cluster_empty_file() {
+        let raw = entry(b"EMPTY   TXT");
+        assert_eq!(raw.first_cluster(), 0);
+        assert_eq!(raw.first_cluster_opt(), None);
+
+        let mut raw = entry(b"DATA    BIN");
+        raw.first_cluster_high = 0x0001;
+        raw.first_cluster_low = 0x0002;
+        assert_eq!(raw.first_cluster_opt(), Some(0x0001_0002));
+    }
+
+    #[test]
+    fn test_short_name_with_extension() {
+        let (out, len) = decode(b"README  TXT");
+        assert_eq!(&out[..len], b"README.TXT");
+
+        let (out, len) = decode(b"ABCDEFGHIJK");
+        assert_eq!(&out[..len], b"ABCDEFGH.IJK");
+    }
+
+    #[test]
+    fn test_short_name_without_extension() {
+        let (out, len) = decode(b"MAKEFILE   ");
+        assert_eq!(&out[..len], b"MAKEFILE");
+    }
+
+    #[test]
+    fn test_short_name_all_spaces() {
+        let (_, len) = decode(b"           ");
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn test_short_name_dot_entries() {
+        let (out, len) = decode(b".          ");
+        assert_eq!(&out[..len], b".");
+
+        let (out, len) = decode(b"..         ");
+        assert_eq!(&out[..len], b"..");
+    }
+
+    #[test]
+    fn test_short_name_case_flags() {
+        let cases: [(u8, &[u8]); 4] = [
+            (0, b"README.TXT"),
+            (CASE_LOWER_BASE, b"readme.TXT"),
+            (CASE_LOWER_EXT, b"README.txt"),
+            (CASE_LOWER_BASE | CASE_LOWER_EXT, b"readme.txt"),
+        ];
+        for (flags, expected) in cases {
+            let mut raw = entry(b"README  TXT");
+            raw.reserved = flags;
+            let mut out = [0u8; 12];
+            let len = raw.short_name(&mut out);
+            assert_eq!(&out[..len], expected);
+        }
+    }
+
+    #[test]
+    fn test_names_equal() {
+        assert!(names_equal("ReadMe.TXT", "readme.txt"));
+        assert!(names_equal("CAFÉ.TXT", "café.txt"));
+        assert!(names_equal("ÀÉÎÕÜ", "àéîõü"));
+        assert!(!names_equal("café", "cafe"));
+        assert!(!names_equal("abc", "abcd"));
+        // × et ÷ ne forment pas une paire de casse
+        assert_eq!(fold_case('×'), '×');
+        assert!(!names_equal("×", "÷"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("*", "anything.at.all"));
+        assert!(wildcard_match("*.txt", "Notes.TXT"));
+        assert!(wildcard_match("*.TXT", "a.b.txt"));
+        assert!(wildcard_match("a*b*c", "aXXbYYbZc"));
+        assert!(wildcard_match("?", "é"));
+        assert!(wildcard_match("caf?.*", "CAFÉ.TXT"));
+        assert!(wildcard_match("**x", "x"));
+        assert!(!wildcard_match("?", ""));
+        assert!(!wildcard_match("??", "a"));
+        assert!(!wildcard_match("*.txt", "notes.txt.bak"));
+        assert!(!wildcard_match("a*b", "acb.c"));
+        assert!(!wildcard_match("", "a"));
+    }
+
+    #[test]
+    fn test_encode_short_name() {
+        assert_eq!(encode_short_name("MAKEFILE"), Some((*b"MAKEFILE   ", 0)));
+        assert_eq!(encode_short_name("a.c"), Some((*b"A       C  ", CASE_LOWER_BASE | CASE_LOWER_EXT)));
+        assert_eq!(encode_short_name("LOG_2024.tx~"), Some((*b"LOG_2024TX~", CASE_LOWER_EXT)));
+        assert_eq!(encode_short_name("1.2"), Some((*b"1       2  ", 0)));
+
+        for name in ["", ".", "..", ".hidden", "NAME.", "TOOLONGNAME", "A.TEXT", "A.B.C", "HAS SPACE", "A+B", "CAFÉ", "MiXed"] {
+            assert_eq!(encode_short_name(name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_short_name_basis() {
+        let cases: [(&str, &[u8; 11], bool); 9] = [
+            ("README.TXT", b"README  TXT", true),
+            ("ReadMe.txt", b"README  TXT", true),
+            ("Long File Name.html", b"LONGFILEHTM", false),
+            ("archive.tar.gz", b"ARCHIVETGZ ", false),
+            (".bashrc", b"BASHRC     ", false),
+            ("a+b,c.d", b"A_B_C   D  ", false),
+            ("été.txt", b"_T_     TXT", false),
+            ("no extension", b"NOEXTENS   ", false),
+            ("trailing.", b"TRAILING   ", false),
+        ];
+        for (long, short, exact) in cases {
+            let basis = ShortNameBasis::new(long).unwrap();
+            assert_eq!(&basis.name(), short, "{long}");
+            assert_eq!(basis.is_exact(), exact, "{long}");
+        }
+
+        let too_long = "x".repeat(256);
+        assert_eq!(ShortNameBasis::new(&too_long), Err(Fat32Error::NameTooLong));
+        assert!(ShortNameBasis::new(&too_long[..255]).is_ok());
+        for long in ["", "...", "   ", "a?b", "a:b", "tab\there"] {
+            assert_eq!(ShortNameBasis::new(long), Err(Fat32Error::InvalidPath), "{long}");
+        }
+    }
+
+    #[test]
+    fn test_short_name_tail() {
+        let basis = ShortNameBasis::new("Long File Name.html").unwrap();
+        assert_eq!(&basis.with_tail(1), b"LONGFI~1HTM");
+        assert_eq!(&basis.with_tail(12), b"LONGF~12HTM");
+        assert_eq!(&basis.with_tail(123456), b"L~123456HTM");
+
+        let short = ShortNameBasis::new("ab cd").unwrap();
+        assert_eq!(&short.with_tail(3), b"ABCD~3     ");
+        assert_eq!(short.tail_of(b"ABCD~3     "), Some(3));
+        assert_eq!(short.tail_of(b"ABCD~03    "), None);
+        assert_eq!(short.tail_of(b"ABCD~3  TXT"), None);
+        assert_eq!(short.tail_of(b"ABCE~3     "), None);
+        assert_eq!(short.tail_of(b"ABCD       "), None);
+        assert_eq!(basis.tail_of(b"LONGF~12HTM"), Some(12));
+        // Préfixe trop long pour une queue à deux chiffres
+        assert_eq!(basis.tail_of(b"LONGFI~12HT"), None);
+    }
+
+    #[test]
+    fn test_build_lfn_entries() {
+        let short = *b"LONGFI~1HTM";
+        let mut slots = [[0u8; 32]; LFN_MAX_ENTRIES];
+        assert_eq!(build_lfn_entries("Long File Name.html", &short, &mut slots).unwrap(), 2);
+
+        // Premier fragment sur le disque : fin du nom, terminateur puis remplissage
+        assert_eq!(slots[0][0], LFN_LAST_ENTRY | 2);
+        let units = lfn_units(&slots[0]);
+        let tail: Vec<u16> = "html".encode_utf16().collect();
+        assert_eq!(&units[..6], &[b'e' as u16, b'.' as u16, tail[0], tail[1], tail[2], tail[3]]);
+        assert_eq!(units[6], 0x0000);
+        assert!(units[7..].iter().all(|&u| u == 0xFFFF));
+
+        assert_eq!(slots[1][0], 1);
+        let head: Vec<u16> = "Long File Nam".encode_utf16().collect();
+        assert_eq!(&lfn_units(&slots[1])[..], &head[..]);
+        for slot in &slots[..2] {
+            assert!(Attributes(slot[11]).is_lfn());
+            assert_eq!(slot[13], lfn_checksum(&short));
+            assert_eq!((slot[12], slot[26], slot[27]), (0, 0, 0));
+        }
+    }
+
+    #[test]
+    fn test_build_lfn_entries_exact_multiple() {
+        let mut slots = [[0u8; 32]; LFN_MAX_ENTRIES];
+        let name = "abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(build_lfn_entries(name, b"ABCDEF~1   ", &mut slots).unwrap(), 2);
+        // Ni terminateur ni remplissage
+        let tail: Vec<u16> = "nopqrstuvwxyz".encode_utf16().collect();
+        assert_eq!(&lfn_units(&slots[0])[..], &tail[..]);
+
+        let name = "x".repeat(255);
+        assert_eq!(build_lfn_entries(&name, b"XXXXXX~1   ", &mut slots).unwrap(), 20);
+        assert_eq!(slots[0][0], LFN_LAST_ENTRY | 20);
+        let name = "x".repeat(256);
+        assert_eq!(build_lfn_entries(&name, b"XXXXXX~1   ", &mut slots), Err(Fat32Error::NameTooLong));
+        assert_eq!(build_lfn_entries("", b"XXXXXX~1   ", &mut slots), Err(Fat32Error::InvalidPath));
+    }
+
+    #[test]
+    fn test_short_name_kanji_escape() {
+        let (out, len) = decode(b"\x05ABC    TXT");
+        assert_eq!(&out[..len], b"\xE5ABC.TXT");
+    }
+}
@@ -30,6 +30,54 @@ pub enum Fat32Error {
     
     /// Buffer trop petit pour contenir les données demandées.
     BufferTooSmall,
+
+    /// Opération non supportée par le périphérique (ex: écriture en lecture seule).
+    Unsupported,
+
+    /// Secteur FSInfo absent ou signatures invalides.
+    InvalidFsInfo,
+
+    /// Structure de répertoire incohérente (ex: sous-répertoire sans entrée `..`).
+    CorruptedDirectory,
+
+    /// Chaîne de clusters invalide : elle boucle sur elle-même (cluster qui
+    /// pointe vers lui-même ou vers un cluster déjà parcouru), ou elle est
+    /// plus courte que la taille du fichier.
+    CorruptedChain,
+
+    /// Taille de secteur non supportée : absente de 512, 1024, 2048 et 4096,
+    /// ou différente de `BlockDevice::SECTOR_SIZE`.
+    UnsupportedSectorSize,
+
+    /// Champ de la BPB incohérent (nom du champ fautif).
+    InvalidBpb(&'static str),
+
+    /// Secteur 0 sans table de partitions MBR valide.
+    InvalidMbr,
+
+    /// Le volume annonce plus de secteurs que le périphérique n'en contient
+    /// (image tronquée).
+    DeviceTooSmall,
+
+    /// Plus aucun cluster libre sur le volume.
+    DiskFull,
+
+    /// Modification refusée : le volume est monté en lecture seule.
+    ReadOnly,
+
+    /// Une entrée du même nom existe déjà dans le répertoire.
+    AlreadyExists,
+
+    /// Nom trop long (plus de 255 unités UTF-16) ou impossible à ramener à
+    /// un nom court 8.3 valide.
+    NameTooLong,
+
+    /// Table FAT incohérente : une chaîne de clusters passe par un cluster
+    /// marqué libre.
+    CorruptedFat,
+
+    /// Suppression d'un répertoire qui contient encore des entrées.
+    DirectoryNotEmpty,
 }
 
 /// Type Result spécialisé pour le parser FAT32.
@@ -40,6 +88,7 @@ impl From<crate::block_device::BlockDeviceError> for Fat32Error {
         match err {
             crate::block_device::BlockDeviceError::IoError => Fat32Error::IoError,
             crate::block_device::BlockDeviceError::OutOfBounds => Fat32Error::OutOfBounds,
+            crate::block_device::BlockDeviceError::Unsupported => Fat32Error::Unsupported,
         }
     }
 }
@@ -58,6 +107,118 @@ impl core::fmt::Display for Fat32Error {
             Fat32Error::IsDirectory => write!(f, "Is a directory"),
             Fat32Error::IsNotDirectory => write!(f, "Not a directory"),
             Fat32Error::BufferTooSmall => write!(f, "Buffer too small"),
+            Fat32Error::Unsupported => write!(f, "Operation not supported by the device"),
+            Fat32Error::InvalidFsInfo => write!(f, "Invalid FSInfo sector"),
+            Fat32Error::CorruptedDirectory => write!(f, "Corrupted directory"),
+            Fat32Error::CorruptedChain => write!(f, "Corrupted cluster chain (cycle or shorter than the file size)"),
+            Fat32Error::UnsupportedSectorSize => write!(f, "Unsupported sector size"),
+            Fat32Error::InvalidBpb(field) => write!(f, "Invalid BPB field: {}", field),
+            Fat32Error::InvalidMbr => write!(f, "Invalid MBR partition table"),
+            Fat32Error::DeviceTooSmall => write!(f, "Volume is larger than the device"),
+            Fat32Error::DiskFull => write!(f, "No free cluster left on the volume"),
+            Fat32Error::ReadOnly => write!(f, "Volume is mounted read-only"),
+            Fat32Error::AlreadyExists => write!(f, "File or directory already exists"),
+            Fat32Error::NameTooLong => write!(f, "Name too long"),
+            Fat32Error::CorruptedFat => write!(f, "Corrupted file allocation table"),
+            Fat32Error::DirectoryNotEmpty => write!(f, "Directory not empty"),
+        }
+    }
+}
+
+// Les erreurs d'entrée/sortie viennent toujours du périphérique et exposent
+// la `BlockDeviceError` d'origine via `source()`. `OutOfBounds` et
+// `Unsupported` sont aussi produites par le parser lui-même (index de
+// partition, `File::seek`, `truncate`...) : sans savoir d'où elles viennent,
+// elles n'ont pas de source.
+impl core::error::Error for Fat32Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        use crate::block_device::BlockDeviceError;
+
+        match self {
+            Fat32Error::IoError => Some(&BlockDeviceError::IoError),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+}
+
+// Conversion vers `std::io::Error` (feature `std`), pour `FileReader` et les
+// appelants qui travaillent avec `std::io`. L'erreur d'origine reste
+// accessible via `get_ref()`.
+#[cfg(any(test, feature = "std"))]
+impl From<Fat32Error> for std::io::Error {
+    fn from(err: Fat32Error) -> Self {
+        use std::io::ErrorKind;
+
+        let kind = match err {
+            Fat32Error::NotFound => ErrorKind::NotFound,
+            Fat32Error::AlreadyExists => ErrorKind::AlreadyExists,
+            Fat32Error::IsDirectory => ErrorKind::IsADirectory,
+            Fat32Error::IsNotDirectory => ErrorKind::NotADirectory,
+            Fat32Error::DirectoryNotEmpty => ErrorKind::DirectoryNotEmpty,
+            Fat32Error::ReadOnly => ErrorKind::ReadOnlyFilesystem,
+            Fat32Error::DiskFull => ErrorKind::StorageFull,
+            Fat32Error::Unsupported => ErrorKind::Unsupported,
+            Fat32Error::OutOfBounds | Fat32Error::InvalidPath | Fat32Error::NameTooLong => {
+                ErrorKind::InvalidInput
+            }
+            Fat32Error::InvalidCluster(_)
+            | Fat32Error::CorruptedDirectory
+            | Fat32Error::CorruptedChain
+            | Fat32Error::CorruptedFat => ErrorKind::InvalidData,
+            _ => ErrorKind::Other,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::error::Error;
+    use std::string::ToString;
+
+    #[test]
+    fn test_error_source() {
+        let err: Fat32Error = crate::block_device::BlockDeviceError::IoError.into();
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), "Block device I/O error");
+        assert!(source.source().is_none());
+
+        assert!(Fat32Error::NotFound.source().is_none());
+        // Aussi produites hors du périphérique (ex: index de partition)
+        assert!(Fat32Error::OutOfBounds.source().is_none());
+        assert!(Fat32Error::Unsupported.source().is_none());
+        assert!(Fat32Error::InvalidBpb("num_fats").source().is_none());
+    }
+
+    #[test]
+    fn test_write_error_messages() {
+        assert_eq!(Fat32Error::DiskFull.to_string(), "No free cluster left on the volume");
+        assert_eq!(Fat32Error::ReadOnly.to_string(), "Volume is mounted read-only");
+        assert_eq!(Fat32Error::AlreadyExists.to_string(), "File or directory already exists");
+        assert!(Fat32Error::DiskFull.source().is_none());
+    }
+
+    #[test]
+    fn test_io_error_conversion() {
+        let err = std::io::Error::from(Fat32Error::NotFound);
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(err.get_ref().unwrap().to_string(), "File or directory not found");
+
+        let kind = |err: Fat32Error| std::io::Error::from(err).kind();
+        assert_eq!(kind(Fat32Error::ReadOnly), std::io::ErrorKind::ReadOnlyFilesystem);
+        assert_eq!(kind(Fat32Error::CorruptedChain), std::io::ErrorKind::InvalidData);
+        assert_eq!(kind(Fat32Error::IoError), std::io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_boxed_error() {
+        fn open() -> core::result::Result<(), std::boxed::Box<dyn Error>> {
+            Err(Fat32Error::InvalidCluster(1))?;
+            Ok(())
+        }
+        assert_eq!(open().unwrap_err().to_string(), "Invalid cluster: 1");
+    }
+}
@@ -1,3 +1,9 @@
+#[cfg(any(test, feature = "alloc"))]
+use alloc::vec::Vec;
+
+#[cfg(any(test, feature = "alloc"))]
+use crate::error::{Fat32Error, Result};
+
 /// Représente une entrée dans la table FAT (File Allocation Table).
 /// 
 /// En FAT32, chaque entrée fait 32 bits et pointe vers le cluster suivant
@@ -5,10 +11,16 @@
 /// 
 /// # Structure de la valeur
 /// 
-/// - `0x00000000` : cluster libre
+/// Seuls les 28 bits de poids faible sont significatifs :
+///
+/// - `0x00000000` : cluster libre ([`is_free`](Self::is_free))
 /// - `0x00000002..=0x0FFFFFEF` : numéro du cluster suivant
-/// - `0x0FFFFFF7` : secteur défectueux (bad cluster)
-/// - `0x0FFFFFF8..=0x0FFFFFFF` : fin de chaîne (End Of Chain)
+///   ([`next_cluster`](Self::next_cluster))
+/// - `0x0FFFFFF0..=0x0FFFFFF6` : valeurs réservées, jamais un cluster
+///   suivant ([`is_reserved`](Self::is_reserved))
+/// - `0x0FFFFFF7` : secteur défectueux (bad cluster, [`is_bad`](Self::is_bad))
+/// - `0x0FFFFFF8..=0x0FFFFFFF` : fin de chaîne (End Of Chain,
+///   [`is_end`](Self::is_end))
 /// 
 /// # Exemples
 /// 
@@ -43,6 +55,34 @@ impl FatEntry {
         Self { value }
     }
 
+    /// Crée une entrée qui pointe vers `cluster` (masqué sur 28 bits).
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use fat32_parser::fat::FatEntry;
+    ///
+    /// assert_eq!(FatEntry::set_next(42).next_cluster(), Some(42));
+    /// ```
+    pub const fn set_next(cluster: u32) -> Self {
+        Self::new(cluster & 0x0FFFFFFF)
+    }
+
+    /// Crée une entrée de fin de chaîne (`0x0FFFFFFF`).
+    pub const fn end_of_chain() -> Self {
+        Self::new(0x0FFFFFFF)
+    }
+
+    /// Crée une entrée de cluster libre (`0x00000000`).
+    pub const fn free() -> Self {
+        Self::new(0x00000000)
+    }
+
+    /// Crée une entrée de cluster défectueux (`0x0FFFFFF7`).
+    pub const fn bad() -> Self {
+        Self::new(0x0FFFFFF7)
+    }
+
     /// Vérifie si cette entrée indique la fin de la chaîne de clusters.
     /// 
     /// Les valeurs >= `0x0FFFFFF8` sont réservées pour marquer la fin d'une chaîne.
@@ -88,9 +128,25 @@ impl FatEntry {
         self.value == 0x0FFFFFF7
     }
 
+    /// Vérifie si l'entrée contient une valeur réservée
+    /// (`0x0FFFFFF0..=0x0FFFFFF6`), qui ne désigne aucun cluster.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use fat32_parser::fat::FatEntry;
+    ///
+    /// assert!(FatEntry::new(0x0FFFFFF0).is_reserved());
+    /// assert!(!FatEntry::new(0x0FFFFFEF).is_reserved());
+    /// ```
+    pub fn is_reserved(&self) -> bool {
+        (0x0FFFFFF0..=0x0FFFFFF6).contains(&self.value)
+    }
+
     /// Retourne le numéro du cluster suivant si l'entrée pointe vers un autre cluster.
     /// 
-    /// Retourne `None` si c'est la fin de chaîne, un cluster libre ou défectueux.
+    /// Retourne `None` si c'est la fin de chaîne, un cluster libre, défectueux
+    /// ou une valeur réservée.
     /// 
     /// # Exemples
     /// 
@@ -102,7 +158,7 @@ impl FatEntry {
     /// assert_eq!(FatEntry::new(0x00000000).next_cluster(), None);
     /// ```
     pub fn next_cluster(&self) -> Option<u32> {
-        if self.is_free() || self.is_bad() || self.is_end() {
+        if self.is_free() || self.is_reserved() || self.is_bad() || self.is_end() {
             None
         } else {
             Some(self.value & 0x0FFFFFFF)
@@ -110,6 +166,114 @@ impl FatEntry {
     }
 }
 
+/// Copie en mémoire de la FAT principale, créée par
+/// [`Fat32Fs::load_fat`](crate::Fat32Fs::load_fat) (feature `alloc`).
+///
+/// Une fois chargée, suivre une chaîne ou chercher un cluster libre ne
+/// demande plus aucune lecture sur le device. La table coûte 4 octets par
+/// cluster du volume (1 Mo pour 256 000 clusters) et n'est pas mise à jour
+/// par les écritures ultérieures.
+#[cfg(any(test, feature = "alloc"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FatTable {
+    // Valeurs des entrées 0 à `max_valid_cluster`, 4 bits réservés masqués
+    entries: Vec<u32>,
+}
+
+#[cfg(any(test, feature = "alloc"))]
+impl FatTable {
+    /// Construit une table à partir des valeurs brutes des entrées, en
+    /// commençant par l'entrée du cluster 0 (les 4 bits réservés sont masqués).
+    pub fn from_entries(mut entries: Vec<u32>) -> Self {
+        for value in entries.iter_mut() {
+            *value &= 0x0FFFFFFF;
+        }
+        Self { entries }
+    }
+
+    /// Nombre d'entrées de la table, clusters 0 et 1 compris.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True si la table ne contient aucune entrée.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entrée FAT de `cluster` ; un cluster hors de la table est vu comme
+    /// libre (sans cluster suivant).
+    pub fn next(&self, cluster: u32) -> FatEntry {
+        match self.entries.get(cluster as usize) {
+            Some(&value) => FatEntry::new(value),
+            None => FatEntry::free(),
+        }
+    }
+
+    /// Itère sur les clusters de la chaîne commençant à `start_cluster`,
+    /// comme [`Fat32Fs::cluster_chain`](crate::Fat32Fs::cluster_chain) mais
+    /// sans lecture.
+    ///
+    /// Un cluster hors du volume produit [`Fat32Error::InvalidCluster`], et
+    /// une chaîne plus longue que le nombre de clusters du volume (donc qui
+    /// boucle) [`Fat32Error::CorruptedChain`]. L'itérateur s'arrête après la
+    /// première erreur.
+    pub fn chain(&self, start_cluster: u32) -> FatTableChain<'_> {
+        FatTableChain {
+            table: self,
+            next: Some(start_cluster),
+            count: 0,
+        }
+    }
+
+    /// Cherche le premier cluster libre à partir de `start_hint`, en
+    /// reprenant au cluster 2 après le dernier cluster, comme
+    /// [`Fat32Fs::find_free_cluster`](crate::Fat32Fs::find_free_cluster).
+    pub fn find_free(&self, start_hint: u32) -> Option<u32> {
+        let end = self.entries.len() as u32;
+        if end <= 2 {
+            return None;
+        }
+        let start = if start_hint < 2 || start_hint >= end { 2 } else { start_hint };
+        (start..end)
+            .chain(2..start)
+            .find(|&cluster| self.entries[cluster as usize] == 0)
+    }
+
+    /// Nombre de clusters libres.
+    pub fn free_count(&self) -> u32 {
+        self.entries.iter().skip(2).filter(|&&value| value == 0).count() as u32
+    }
+}
+
+/// Itérateur sur une chaîne de clusters d'une [`FatTable`], créé par
+/// [`FatTable::chain`].
+#[cfg(any(test, feature = "alloc"))]
+pub struct FatTableChain<'t> {
+    table: &'t FatTable,
+    next: Option<u32>,
+    count: usize,
+}
+
+#[cfg(any(test, feature = "alloc"))]
+impl Iterator for FatTableChain<'_> {
+    type Item = Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cluster = self.next.take()?;
+        if cluster < 2 || cluster as usize >= self.table.len() {
+            return Some(Err(Fat32Error::InvalidCluster(cluster)));
+        }
+        // Une chaîne valide ne passe pas deux fois par le même cluster
+        if self.count >= self.table.len() - 2 {
+            return Some(Err(Fat32Error::CorruptedChain));
+        }
+        self.count += 1;
+        self.next = self.table.next(cluster).next_cluster();
+        Some(Ok(cluster))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +303,65 @@ mod tests {
         assert_eq!(FatEntry::new(0x0FFFFFF8).next_cluster(), None);
         assert_eq!(FatEntry::new(0x00000000).next_cluster(), None);
     }
+
+    #[test]
+    fn test_reserved_values() {
+        // Dernière valeur de cluster suivant
+        assert!(!FatEntry::new(0x0FFFFFEF).is_reserved());
+        assert_eq!(FatEntry::new(0x0FFFFFEF).next_cluster(), Some(0x0FFFFFEF));
+        // Bornes de la plage réservée
+        for value in [0x0FFFFFF0, 0x0FFFFFF6] {
+            let entry = FatEntry::new(value);
+            assert!(entry.is_reserved());
+            assert!(!entry.is_bad() && !entry.is_end() && !entry.is_free());
+            assert_eq!(entry.next_cluster(), None);
+        }
+        assert!(!FatEntry::new(0x0FFFFFF7).is_reserved());
+    }
+
+    #[test]
+    fn test_fat_table() {
+        // Chaîne 2 -> 5 -> 3, cluster 4 libre, cluster 6 défectueux, 7 libre
+        let table = FatTable::from_entries(alloc::vec![
+            0x0FFFFFF8, 0xFFFFFFFF, 5, 0x0FFFFFFF, 0, 0xF0000003, 0x0FFFFFF7, 0,
+        ]);
+        assert_eq!(table.len(), 8);
+        assert_eq!(table.next(5), FatEntry::set_next(3));
+        assert!(table.next(1).is_end());
+        assert!(table.next(100).is_free());
+
+        let chain: Result<Vec<u32>> = table.chain(2).collect();
+        assert_eq!(chain.unwrap(), [2, 5, 3]);
+        assert_eq!(table.chain(9).next(), Some(Err(Fat32Error::InvalidCluster(9))));
+
+        assert_eq!(table.find_free(0), Some(4));
+        assert_eq!(table.find_free(5), Some(7));
+        assert_eq!(table.free_count(), 2);
+
+        // Boucle 2 -> 3 -> 2
+        let looping = FatTable::from_entries(alloc::vec![0, 0, 3, 2]);
+        let chain: Result<Vec<u32>> = looping.chain(2).collect();
+        assert_eq!(chain, Err(Fat32Error::CorruptedChain));
+        assert_eq!(looping.find_free(2), None);
+    }
+
+    #[test]
+    fn test_constructors() {
+        let next = FatEntry::set_next(0xF000_0005);
+        assert_eq!(next.value, 5);
+        assert_eq!(next.next_cluster(), Some(5));
+        assert!(!next.is_end() && !next.is_free() && !next.is_bad());
+
+        let end = FatEntry::end_of_chain();
+        assert!(end.is_end());
+        assert_eq!(end.next_cluster(), None);
+
+        let free = FatEntry::free();
+        assert!(free.is_free());
+        assert_eq!(free.next_cluster(), None);
+
+        let bad = FatEntry::bad();
+        assert!(bad.is_bad() && !bad.is_end());
+        assert_eq!(bad.next_cluster(), None);
+    }
 }
\ No newline at end of file
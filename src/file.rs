@@ -0,0 +1,562 @@
+use crate::block_device::BlockDevice;
+use crate::boot_sector::MAX_SECTOR_SIZE;
+use crate::error::{Fat32Error, Result};
+use crate::fat::FatEntry;
+use crate::filesystem::{EntryPosition, Fat32Fs};
+
+/// Fichier ouvert sur un volume FAT32.
+///
+/// Un `File` ne garde qu'une copie des métadonnées utiles (premier cluster et
+/// taille), sa position de lecture/écriture et l'emplacement de son entrée de
+/// répertoire : il ne référence pas le système de fichiers, qu'il faut
+/// repasser à chaque lecture ou écriture.
+///
+/// # Exemples
+///
+/// ```no_run
+/// use fat32_parser::{Fat32Fs, BlockDevice};
+///
+/// fn read_example<D: BlockDevice>(fs: &Fat32Fs<'_, D>) -> fat32_parser::Result<usize> {
+///     let mut file = fs.open("/docs/readme.txt")?;
+///     let mut buf = [0u8; 64];
+///     file.read(fs, &mut buf)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct File {
+    first_cluster: u32,
+    size: u32,
+    position: u64,
+    // Dernier cluster visité (index dans la chaîne, numéro) pour éviter de
+    // reparcourir la FAT depuis le début à chaque lecture séquentielle.
+    cursor: Option<(u32, u32)>,
+    // Entrée 8.3 du fichier, mise à jour par `write` (`None` pour un handle
+    // créé avec `File::new`).
+    entry: Option<EntryPosition>,
+}
+
+impl File {
+    /// Crée un handle à partir du premier cluster et de la taille du fichier.
+    pub fn new(first_cluster: u32, size: u32) -> Self {
+        Self {
+            first_cluster,
+            size,
+            position: 0,
+            cursor: None,
+            entry: None,
+        }
+    }
+
+    // Handle d'un fichier dont l'entrée 8.3 se trouve à `entry`.
+    pub(crate) fn with_entry(first_cluster: u32, size: u32, entry: EntryPosition) -> Self {
+        Self {
+            entry: Some(entry),
+            ..Self::new(first_cluster, size)
+        }
+    }
+
+    /// Premier cluster de la chaîne de données (0 pour un fichier vide).
+    pub fn first_cluster(&self) -> u32 {
+        self.first_cluster
+    }
+
+    /// Taille du fichier en octets.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Position courante de lecture, en octets depuis le début du fichier.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Déplace la position de lecture à `pos` octets du début du fichier.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::OutOfBounds`] si `pos` dépasse la taille du fichier.
+    pub fn seek(&mut self, pos: u64) -> Result<u64> {
+        if pos > self.size as u64 {
+            return Err(Fat32Error::OutOfBounds);
+        }
+        self.position = pos;
+        Ok(pos)
+    }
+
+    /// Lit des données à partir de la position courante et avance celle-ci.
+    ///
+    /// La lecture s'arrête exactement à la taille du fichier, même si la
+    /// chaîne de clusters continue physiquement au-delà. Seuls les secteurs
+    /// nécessaires sont lus.
+    ///
+    /// Retourne le nombre d'octets lus (0 en fin de fichier). Un fichier sans
+    /// cluster alloué (premier cluster à 0) est lu comme un fichier vide.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture échoue, et
+    /// [`Fat32Error::CorruptedChain`] si la chaîne de clusters se termine
+    /// avant la taille annoncée.
+    pub fn read<D: BlockDevice>(&mut self, fs: &Fat32Fs<'_, D>, buf: &mut [u8]) -> Result<usize> {
+        if self.first_cluster == 0 {
+            return Ok(0);
+        }
+
+        let bytes_per_sector = fs.geom.bytes_per_sector as u64;
+        let cluster_size = fs.geom.cluster_size_bytes() as u64;
+        let mut buf_sector = [0u8; MAX_SECTOR_SIZE];
+        let sector = fs.sector_buf(&mut buf_sector)?;
+        let mut total = 0;
+
+        while total < buf.len() && self.position < self.size as u64 {
+            let index = (self.position / cluster_size) as u32;
+            let cluster = self.cluster_at(fs, index)?;
+
+            // Position dans le cluster, puis dans le secteur
+            let in_cluster = self.position % cluster_size;
+            let sector_index = (in_cluster / bytes_per_sector) as u32;
+            let in_sector = (in_cluster % bytes_per_sector) as usize;
+
+            let lba = fs.geom.cluster_to_lba(cluster)? + sector_index;
+            fs.read_sectors(lba, 1, sector)?;
+
+            let remaining_file = (self.size as u64 - self.position) as usize;
+            let count = (bytes_per_sector as usize - in_sector)
+                .min(buf.len() - total)
+                .min(remaining_file);
+
+            buf[total..total + count].copy_from_slice(&sector[in_sector..in_sector + count]);
+            total += count;
+            self.position += count as u64;
+        }
+
+        Ok(total)
+    }
+
+    /// Écrit `data` à la position courante et avance celle-ci.
+    ///
+    /// La chaîne de clusters est allongée si nécessaire (allouée au premier
+    /// appel pour un fichier vide) et la taille du fichier augmente si
+    /// l'écriture dépasse la fin. Un secteur écrit en partie est d'abord relu
+    /// pour préserver les octets voisins. Le premier cluster et la taille
+    /// sont ensuite reportés dans l'entrée de répertoire du fichier, s'il a
+    /// été ouvert avec [`Fat32Fs::open`], avec la date de modification
+    /// donnée par [`Fat32Fs::now`].
+    ///
+    /// Retourne le nombre d'octets écrits, toujours `data.len()`. Si
+    /// l'écriture échoue, les clusters alloués par cet appel sont libérés et
+    /// la position et la taille du handle sont restaurées (les données déjà
+    /// écrites dans les clusters existants ne sont pas annulées).
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::OutOfBounds`] si le fichier dépasserait 4 Go - 1 octet
+    /// - [`Fat32Error::DiskFull`] s'il n'y a plus assez de clusters libres
+    /// - les erreurs de lecture ou d'écriture du device
+    pub fn write<D: BlockDevice>(&mut self, fs: &Fat32Fs<'_, D>, data: &[u8]) -> Result<usize> {
+        fs.check_writable()?;
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        let end = self.position + data.len() as u64;
+        if end > u32::MAX as u64 {
+            return Err(Fat32Error::OutOfBounds);
+        }
+
+        let cluster_size = fs.geom.cluster_size_bytes() as u64;
+        let previous_last = self.reserve_clusters(fs, end.div_ceil(cluster_size) as u32)?;
+
+        let (position, size) = (self.position, self.size);
+        let written = self.write_reserved(fs, data);
+        if written.is_err() {
+            // Les clusters ajoutés ne seraient reliés à aucune entrée
+            if let Some(previous_last) = previous_last {
+                self.release_reserved(fs, previous_last);
+            }
+            self.position = position;
+            self.size = size;
+        }
+        written
+    }
+
+    // Écrit `data` à la position courante, dans des clusters déjà réservés,
+    // puis met à jour l'entrée de répertoire.
+    fn write_reserved<D: BlockDevice>(&mut self, fs: &Fat32Fs<'_, D>, data: &[u8]) -> Result<usize> {
+        let bytes_per_sector = fs.geom.bytes_per_sector as u64;
+        let cluster_size = fs.geom.cluster_size_bytes() as u64;
+        let mut buf_sector = [0u8; MAX_SECTOR_SIZE];
+        let sector = fs.sector_buf(&mut buf_sector)?;
+        let mut total = 0;
+
+        while total < data.len() {
+            let index = (self.position / cluster_size) as u32;
+            let cluster = self.cluster_at(fs, index)?;
+
+            let in_cluster = self.position % cluster_size;
+            let sector_index = (in_cluster / bytes_per_sector) as u32;
+            let in_sector = (in_cluster % bytes_per_sector) as usize;
+            let lba = fs.geom.cluster_to_lba(cluster)? + sector_index;
+            let count = (bytes_per_sector as usize - in_sector).min(data.len() - total);
+
+            if count == sector.len() {
+                // Secteur entier : pas besoin de le relire
+                fs.write_sectors(lba, 1, &data[total..total + count])?;
+            } else {
+                fs.read_sectors(lba, 1, sector)?;
+                sector[in_sector..in_sector + count].copy_from_slice(&data[total..total + count]);
+                fs.write_sectors(lba, 1, sector)?;
+            }
+
+            total += count;
+            self.position += count as u64;
+        }
+
+        if self.position > self.size as u64 {
+            self.size = self.position as u32;
+        }
+        if let Some(entry) = self.entry {
+            fs.update_entry_metadata(entry, self.first_cluster, self.size)?;
+        }
+        Ok(total)
+    }
+
+    // Garantit que la chaîne compte au moins `needed` clusters, en
+    // l'allouant ou en l'allongeant. Retourne le dernier cluster de la chaîne
+    // avant l'ajout (0 si elle vient d'être créée), ou `None` si aucun
+    // cluster n'a été ajouté.
+    fn reserve_clusters<D: BlockDevice>(
+        &mut self,
+        fs: &Fat32Fs<'_, D>,
+        needed: u32,
+    ) -> Result<Option<u32>> {
+        if self.first_cluster == 0 {
+            self.first_cluster = fs.allocate_chain(needed)?;
+            self.cursor = None;
+            return Ok(Some(0));
+        }
+
+        // Dernier cluster de la chaîne, en repartant du curseur
+        let (mut last_index, start) = self.cursor.unwrap_or((0, self.first_cluster));
+        let mut last = start;
+        for cluster in fs.cluster_chain(start).skip(1) {
+            last = cluster?;
+            last_index += 1;
+        }
+        self.cursor = Some((last_index, last));
+
+        if last_index + 1 < needed {
+            let new_last = fs.extend_chain(last, needed - last_index - 1)?;
+            self.cursor = Some((needed - 1, new_last));
+            return Ok(Some(last));
+        }
+        Ok(None)
+    }
+
+    // Rend les clusters ajoutés par `reserve_clusters` après `previous_last`,
+    // ou toute la chaîne si elle vient d'être créée. Les erreurs de ce
+    // nettoyage sont ignorées : c'est celle de l'écriture qui est retournée.
+    fn release_reserved<D: BlockDevice>(&mut self, fs: &Fat32Fs<'_, D>, previous_last: u32) {
+        if previous_last == 0 {
+            let _ = fs.free_chain(self.first_cluster);
+            self.first_cluster = 0;
+        } else if let Ok(Some(added)) = fs.read_fat_entry(previous_last).map(|e| e.next_cluster()) {
+            if fs.write_fat_entry(previous_last, FatEntry::end_of_chain()).is_ok() {
+                let _ = fs.free_chain(added);
+            }
+        }
+        self.cursor = None;
+    }
+
+    // Retourne le numéro du `index`-ième cluster de la chaîne, en repartant
+    // du curseur quand c'est possible.
+    fn cluster_at<D: BlockDevice>(&mut self, fs: &Fat32Fs<'_, D>, index: u32) -> Result<u32> {
+        let (current_index, mut cluster) = match self.cursor {
+            Some((cached_index, cached_cluster)) if cached_index <= index => {
+                (cached_index, cached_cluster)
+            }
+            _ => (0, self.first_cluster),
+        };
+
+        if current_index < index {
+            cluster = fs
+                .nth_cluster(cluster, index - current_index)?
+                .ok_or(Fat32Error::CorruptedChain)?;
+        }
+
+        self.cursor = Some((index, cluster));
+        Ok(cluster)
+    }
+}
+
+/// Adaptateur qui associe un [`File`] à son système de fichiers pour
+/// implémenter [`std::io::Read`] et [`std::io::Seek`] (feature `std`).
+///
+/// Les erreurs sont converties en [`std::io::Error`], l'erreur
+/// [`Fat32Error`] d'origine restant accessible via `get_ref()`.
+///
+/// # Exemples
+///
+/// ```no_run
+/// use fat32_parser::{file::FileReader, BlockDevice, Fat32Fs};
+///
+/// fn copy_out<D: BlockDevice>(fs: &Fat32Fs<'_, D>) -> std::io::Result<Vec<u8>> {
+///     let mut reader = FileReader::new(fs, fs.open("/docs/readme.txt")?);
+///     let mut out = Vec::new();
+///     std::io::copy(&mut reader, &mut out)?;
+///     Ok(out)
+/// }
+/// ```
+#[cfg(any(test, feature = "std"))]
+pub struct FileReader<'fs, 'a, D: BlockDevice> {
+    fs: &'fs Fat32Fs<'a, D>,
+    file: File,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<'fs, 'a, D: BlockDevice> FileReader<'fs, 'a, D> {
+    /// Associe `file` au système de fichiers `fs`.
+    pub fn new(fs: &'fs Fat32Fs<'a, D>, file: File) -> Self {
+        Self { fs, file }
+    }
+
+    /// Fichier lu, avec sa position courante.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Récupère le fichier.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<D: BlockDevice> std::io::Read for FileReader<'_, '_, D> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.file.read(self.fs, buf)?)
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<D: BlockDevice> std::io::Seek for FileReader<'_, '_, D> {
+    /// Déplace la position ; contrairement à un fichier du système hôte, elle
+    /// ne peut pas dépasser la fin du fichier (`InvalidInput`).
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            std::io::SeekFrom::Start(offset) => Some(offset),
+            std::io::SeekFrom::End(delta) => (self.file.size() as u64).checked_add_signed(delta),
+            std::io::SeekFrom::Current(delta) => self.file.position().checked_add_signed(delta),
+        };
+        let target = target.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "seek before start of file")
+        })?;
+        Ok(self.file.seek(target)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boot_sector::Fat32Geometry;
+    use crate::filesystem::Mode;
+    use crate::testing::*;
+    use crate::timestamp::{FixedClock, Timestamp, DOS_EPOCH};
+
+    #[test]
+    fn test_read_small_file_in_large_cluster() {
+        // Clusters de 8 secteurs : le cluster 2 occupe les secteurs 2 à 9
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.write_data(2, b"abcdefgh");
+        let geom = Fat32Geometry {
+            sectors_per_cluster: 8,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new(&dev, geom);
+
+        let mut file = File::new(2, 3);
+        let mut buf = [0u8; 4096];
+        assert_eq!(file.read(&fs, &mut buf).unwrap(), 3);
+        assert_eq!(&buf[..3], b"abc");
+        assert_eq!(file.read(&fs, &mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_read_across_clusters_and_seek() {
+        // Chaîne fragmentée 2 -> 4 -> 3
+        let mut dev = MemDevice::new(3);
+        dev.set_fat(2, 4);
+        dev.set_fat(4, 3);
+        dev.set_fat(3, 0x0FFFFFFF);
+        dev.write_data(2, &[b'a'; 512]);
+        dev.write_data(4, &[b'b'; 512]);
+        dev.write_data(3, &[b'c'; 512]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut file = File::new(2, 1100);
+        let mut buf = [0u8; 2048];
+        assert_eq!(file.read(&fs, &mut buf).unwrap(), 1100);
+        assert!(buf[..512].iter().all(|&b| b == b'a'));
+        assert!(buf[512..1024].iter().all(|&b| b == b'b'));
+        assert!(buf[1024..1100].iter().all(|&b| b == b'c'));
+
+        assert_eq!(file.seek(1020).unwrap(), 1020);
+        let mut small = [0u8; 8];
+        assert_eq!(file.read(&fs, &mut small).unwrap(), 8);
+        assert_eq!(&small, b"bbbbcccc");
+        assert_eq!(file.position(), 1028);
+
+        assert_eq!(file.seek(1101), Err(Fat32Error::OutOfBounds));
+    }
+
+    #[test]
+    fn test_read_chain_shorter_than_size() {
+        let dev = device_with_root(&[]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        // Le cluster 2 termine la chaîne alors que la taille en demande deux
+        let mut file = File::new(2, 1000);
+        let mut buf = [0u8; 1000];
+        assert_eq!(file.read(&fs, &mut buf), Err(Fat32Error::CorruptedChain));
+    }
+
+    #[test]
+    fn test_write_appends_across_clusters() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.write_entries(2, &[file_entry(b"DATA    BIN", 0x20, 3, 500)]);
+        dev.set_fat(3, 0x0FFFFFFF);
+        dev.write_data(3, &[b'a'; 500]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let mut file = fs.open("/DATA.BIN").unwrap();
+        file.seek(500).unwrap();
+        assert_eq!(file.write(&fs, &[b'b'; 600]).unwrap(), 600);
+        assert_eq!(file.size(), 1100);
+        assert_eq!(fs.chain_length(3).unwrap(), 3);
+
+        // L'entrée de répertoire reflète la nouvelle taille
+        let mut file = fs.open("/DATA.BIN").unwrap();
+        assert_eq!(file.size(), 1100);
+        let mut buf = [0u8; 1100];
+        assert_eq!(file.read(&fs, &mut buf).unwrap(), 1100);
+        assert!(buf[..500].iter().all(|&b| b == b'a'));
+        assert!(buf[500..].iter().all(|&b| b == b'b'));
+    }
+
+    #[test]
+    fn test_write_preserves_rest_of_sector() {
+        let mut dev = MemDevice::new(2);
+        dev.set_fat(3, 0x0FFFFFFF);
+        dev.write_data(3, &[b'x'; 512]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let mut file = File::new(3, 512);
+        file.seek(10).unwrap();
+        file.write(&fs, b"hello").unwrap();
+        assert_eq!(file.size(), 512);
+
+        let data = dev.data.borrow();
+        let sector = &data[3 * 512..4 * 512];
+        assert!(sector[..10].iter().all(|&b| b == b'x'));
+        assert_eq!(&sector[10..15], b"hello");
+        assert!(sector[15..].iter().all(|&b| b == b'x'));
+    }
+
+    #[test]
+    fn test_write_to_empty_file_allocates() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.write_entries(2, &[file_entry(b"EMPTY   TXT", 0x20, 0, 0)]);
+        let clock = FixedClock(Timestamp { year: 2023, hour: 18, second: 42, ..DOS_EPOCH });
+        let mut fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        fs.set_clock(&clock);
+
+        let mut file = fs.open("/EMPTY.TXT").unwrap();
+        file.write(&fs, b"first").unwrap();
+        file.write(&fs, b" second").unwrap();
+
+        let entry = fs.find_in_dir(2, "EMPTY.TXT").unwrap().unwrap();
+        assert_ne!(entry.first_cluster(), 0);
+        assert_eq!(entry.raw().modified(), clock.0);
+        assert_eq!(entry.file_size(), 12);
+        assert_eq!(fs.read_file_to_vec("/EMPTY.TXT").unwrap(), b"first second");
+    }
+
+    #[test]
+    fn test_write_failure_releases_new_clusters() {
+        // Les données du cluster 3 (secteur 3) ne peuvent pas être écrites
+        let mut inner = MemDevice::new(8);
+        inner.set_fat(2, 0x0FFFFFFF);
+        inner.write_entries(2, &[file_entry(b"EMPTY   TXT", 0x20, 0, 0)]);
+        let dev = FailingWrites { inner, fail_lba: 3 };
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let mut file = fs.open("/EMPTY.TXT").unwrap();
+        assert_eq!(file.write(&fs, b"data"), Err(Fat32Error::IoError));
+        assert!(fs.read_fat_entry(3).unwrap().is_free());
+        assert_eq!((file.first_cluster, file.size(), file.position()), (0, 0, 0));
+        let entry = fs.find_in_dir(2, "EMPTY.TXT").unwrap().unwrap();
+        assert_eq!((entry.first_cluster(), entry.file_size()), (0, 0));
+
+        // Chaîne allongée : le cluster 4 est retiré, le 3 redevient le dernier
+        let mut inner = MemDevice::new(8);
+        inner.set_fat(2, 0x0FFFFFFF);
+        inner.set_fat(3, 0x0FFFFFFF);
+        inner.write_entries(2, &[file_entry(b"DATA    BIN", 0x20, 3, 500)]);
+        let dev = FailingWrites { inner, fail_lba: 4 };
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let mut file = fs.open("/DATA.BIN").unwrap();
+        file.seek(500).unwrap();
+        assert_eq!(file.write(&fs, &[b'b'; 600]), Err(Fat32Error::IoError));
+        assert!(fs.read_fat_entry(3).unwrap().is_end());
+        assert!(fs.read_fat_entry(4).unwrap().is_free());
+        assert_eq!((file.size(), file.position()), (500, 500));
+        assert_eq!(fs.find_in_dir(2, "DATA.BIN").unwrap().unwrap().file_size(), 500);
+    }
+
+    #[test]
+    fn test_file_reader_copy_and_seek() {
+        use std::io::{Read, Seek, SeekFrom};
+
+        // Chaîne fragmentée 2 -> 4 -> 3, 1100 octets
+        let mut dev = MemDevice::new(3);
+        dev.set_fat(2, 4);
+        dev.set_fat(4, 3);
+        dev.set_fat(3, 0x0FFFFFFF);
+        let content: Vec<u8> = (0..1100u32).map(|i| (i % 251) as u8).collect();
+        dev.write_data(2, &content[..512]);
+        dev.write_data(4, &content[512..1024]);
+        dev.write_data(3, &content[1024..]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut reader = FileReader::new(&fs, File::new(2, 1100));
+        let mut out = Vec::new();
+        assert_eq!(std::io::copy(&mut reader, &mut out).unwrap(), 1100);
+        assert_eq!(out, content);
+
+        assert_eq!(reader.seek(SeekFrom::End(-100)).unwrap(), 1000);
+        assert_eq!(reader.seek(SeekFrom::Current(-500)).unwrap(), 500);
+        let mut buf = [0u8; 20];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf[..], &content[500..520]);
+
+        let err = reader.seek(SeekFrom::Current(-1000)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        let err = reader.seek(SeekFrom::Start(2000)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(reader.into_inner().position(), 520);
+    }
+
+    #[test]
+    fn test_write_read_only() {
+        let dev = device_with_root(&[]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let mut file = File::new(0, 0);
+        assert_eq!(file.write(&fs, b"data"), Err(Fat32Error::ReadOnly));
+        assert_eq!(file.size(), 0);
+    }
+}
@@ -1,8 +1,26 @@
-use crate::block_device::BlockDevice;
-use crate::boot_sector::{BiosParameterBlock, Fat32Geometry};
+#[cfg(any(test, feature = "alloc"))]
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::ops::ControlFlow;
+
+use crate::block_device::{BlockDevice, BlockDeviceError};
+use crate::boot_sector::{
+    detect_fat_type, is_valid_sector_size, BootSector, FatType, Fat32Geometry,
+    BACKUP_BOOT_SECTOR, MAX_SECTOR_SIZE,
+};
 use crate::error::{Fat32Error, Result};
+#[cfg(any(test, feature = "alloc"))]
+use crate::fat::FatTable;
 use crate::fat::FatEntry;
-use crate::dir_entry::DirectoryEntryRaw;
+use crate::file::File;
+use crate::fsinfo::FsInfo;
+use crate::mbr::parse_mbr;
+use crate::timestamp::{Clock, Timestamp, DOS_EPOCH};
+use crate::dir_entry::{
+    attr, build_lfn_entries, encode_short_name, is_short_name_char, lfn_checksum, lfn_units,
+    wildcard_match, Attributes, DirEntry, DirectoryEntryRaw, ShortNameBasis, DOTDOT_NAME, DOT_NAME,
+    LFN_CHARS_PER_ENTRY, LFN_LAST_ENTRY, LFN_MAX_ENTRIES,
+};
 
 /// Représente un système de fichiers FAT32 monté sur un périphérique bloc.
 /// 
@@ -23,45 +41,692 @@ use crate::dir_entry::DirectoryEntryRaw;
 pub struct Fat32Fs<'a, D: BlockDevice> {
     pub device: &'a D,
     pub geom: Fat32Geometry,
+    // Dernier secteur de FAT lu (LBA, `NO_SECTOR` si vide) et son contenu :
+    // un secteur couvre 128 clusters consécutifs, ce qui évite presque toutes
+    // les lectures lors du parcours d'une chaîne.
+    fat_cache_lba: Cell<u32>,
+    fat_cache: RefCell<[u8; MAX_SECTOR_SIZE]>,
+    // Longueur maximale d'une chaîne de clusters (`None` : nombre de clusters
+    // du volume).
+    max_chain_length: Option<u32>,
+    // Premier secteur du volume sur le device (0 sans table de partitions) :
+    // ajouté à chaque LBA lu ou écrit.
+    partition_start: u32,
+    // Lecture seule ou lecture/écriture : vérifié avant chaque écriture.
+    mode: Mode,
+    // Horloge qui date les entrées créées ou modifiées (`None` : `DOS_EPOCH`).
+    clock: Option<&'a dyn Clock>,
+    // Remplit de zéros chaque cluster libéré avant de le rendre à la FAT.
+    zero_on_free: bool,
+    // Copie de secours du secteur de boot (relative au volume), lue dans la
+    // BPB au montage.
+    backup_boot_sector: Option<u32>,
+}
+
+/// Mode de montage d'un volume.
+///
+/// En lecture seule (le mode par défaut), toute méthode qui modifierait le
+/// volume retourne [`Fat32Error::ReadOnly`], même si le device accepte
+/// l'écriture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Aucune écriture sur le device.
+    #[default]
+    ReadOnly,
+    /// Écritures autorisées (si le device les supporte).
+    ReadWrite,
+}
+
+/// Occupation du volume, calculée en parcourant toute la FAT
+/// (voir [`Fat32Fs::statfs`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatFs {
+    /// Nombre de clusters de la zone de données.
+    pub total: u32,
+    /// Clusters libres.
+    pub free: u32,
+    /// Clusters utilisés (y compris les clusters défectueux ou réservés).
+    pub used: u32,
+    /// Taille d'un cluster en octets.
+    pub cluster_size: u32,
+}
+
+impl StatFs {
+    /// Capacité de la zone de données, en octets.
+    pub fn total_bytes(&self) -> u64 {
+        self.total as u64 * self.cluster_size as u64
+    }
+
+    /// Espace libre, en octets.
+    pub fn free_bytes(&self) -> u64 {
+        self.free as u64 * self.cluster_size as u64
+    }
+}
+
+/// Informations sur un fichier ou un répertoire, retournées par
+/// [`Fat32Fs::metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    /// True pour un répertoire.
+    pub is_dir: bool,
+    /// Taille en octets (0 pour un répertoire).
+    pub size: u32,
+    /// Premier cluster des données (0 pour un fichier vide).
+    pub first_cluster: u32,
+    /// Attributs de l'entrée.
+    pub attributes: Attributes,
+    /// Date et heure de création.
+    pub created: Timestamp,
+    /// Date et heure de dernière modification.
+    pub modified: Timestamp,
+    /// Date de dernier accès (l'heure n'est pas stockée et vaut minuit).
+    pub accessed: Timestamp,
+}
+
+impl Metadata {
+    fn from_entry(entry: &DirEntry) -> Self {
+        let raw = entry.raw();
+        Self {
+            is_dir: entry.is_dir(),
+            size: entry.file_size(),
+            first_cluster: entry.first_cluster(),
+            attributes: entry.attributes(),
+            created: raw.created(),
+            modified: raw.modified(),
+            accessed: Timestamp::from_dos(raw.last_access_date, 0, 0),
+        }
+    }
+}
+
+/// Emplacement sur le disque d'une entrée de répertoire, retourné par
+/// [`Fat32Fs::locate`] et [`DirectoryIterator::location`].
+///
+/// Les offsets sont en octets depuis le début du cluster du répertoire. Les
+/// fragments LFN précèdent l'entrée 8.3 et peuvent commencer dans le cluster
+/// précédent de la chaîne du répertoire ; sans nom long, le premier slot est
+/// l'entrée 8.3 elle-même.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EntryLocation {
+    /// Cluster du répertoire qui contient l'entrée 8.3.
+    pub cluster: u32,
+    /// Offset de l'entrée 8.3 dans ce cluster.
+    pub offset: u32,
+    /// Cluster du premier slot (premier fragment LFN).
+    pub first_slot_cluster: u32,
+    /// Offset du premier slot dans son cluster.
+    pub first_slot_offset: u32,
+    /// Nombre d'entrées de 32 octets, fragments LFN et entrée 8.3 compris.
+    pub slots: u32,
+}
+
+impl From<EntrySpan> for EntryLocation {
+    fn from(span: EntrySpan) -> Self {
+        Self {
+            cluster: span.short.cluster,
+            offset: span.short.offset,
+            first_slot_cluster: span.first.cluster,
+            first_slot_offset: span.first.offset,
+            slots: span.slots,
+        }
+    }
+}
+
+/// Entrée supprimée retrouvée par [`Fat32Fs::undelete_candidates`].
+#[derive(Clone, Copy, Default)]
+pub struct DeletedEntry {
+    /// Entrée décodée, avec le premier cluster et la taille enregistrés. Son
+    /// nom est le nom long quand ses fragments LFN ont été retrouvés, sinon
+    /// le nom 8.3 dont le premier caractère, perdu, est remplacé par `_`.
+    pub entry: DirEntry,
+    /// True si le premier caractère du nom 8.3 a été retrouvé (grâce à la
+    /// somme de contrôle des fragments LFN).
+    pub first_char_recovered: bool,
+    /// Emplacement de l'entrée 8.3 et des fragments LFN retrouvés.
+    pub location: EntryLocation,
+}
+
+/// Options de montage d'un volume, appliquées par
+/// [`Fat32FsBuilder::build`].
+///
+/// Par défaut, le volume est monté en lecture seule, sans horloge, avec la
+/// longueur de chaîne maximale par défaut et en validation tolérante : c'est
+/// ce que fait [`Fat32Fs::mount`].
+///
+/// # Exemples
+///
+/// ```no_run
+/// use fat32_parser::{BlockDevice, Fat32Fs, Fat32FsBuilder};
+/// use fat32_parser::timestamp::Clock;
+///
+/// fn mount_rw<'a, D: BlockDevice>(
+///     device: &'a D,
+///     boot_sector: &[u8],
+///     clock: &'a dyn Clock,
+/// ) -> fat32_parser::Result<Fat32Fs<'a, D>> {
+///     Fat32FsBuilder::new()
+///         .read_write()
+///         .with_clock(clock)
+///         .max_chain(4096)
+///         .build(device, boot_sector)
+/// }
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Fat32FsBuilder<'a> {
+    mode: Mode,
+    clock: Option<&'a dyn Clock>,
+    max_chain_length: Option<u32>,
+    strict: bool,
+    zero_on_free: bool,
+}
+
+impl<'a> Fat32FsBuilder<'a> {
+    /// Options par défaut (lecture seule).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Monte le volume dans le mode `mode`.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Monte le volume en lecture/écriture ([`Mode::ReadWrite`]).
+    pub fn read_write(self) -> Self {
+        self.mode(Mode::ReadWrite)
+    }
+
+    /// Date les fichiers créés ou modifiés avec `clock`.
+    pub fn with_clock(mut self, clock: &'a dyn Clock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Limite les chaînes de clusters à `limit` clusters (voir
+    /// [`Fat32Fs::set_max_chain_length`]).
+    pub fn max_chain(mut self, limit: u32) -> Self {
+        self.max_chain_length = Some(limit);
+        self
+    }
+
+    /// Active ou non la validation stricte du secteur de boot.
+    ///
+    /// Sont toujours fatals : la signature `0x55AA`, les champs qui
+    /// déterminent la géométrie (voir [`BiosParameterBlock::validate`]), le
+    /// type de FAT et la taille de secteur. En mode strict, les écarts sans
+    /// effet sur la lecture (`fs_version`, `media`, `root_entry_count` et
+    /// type de système de fichiers, voir [`BootSector::validate_strict`])
+    /// produisent aussi [`Fat32Error::InvalidBpb`] ; en mode tolérant (par
+    /// défaut), ils sont ignorés, ce qui permet de lire des images
+    /// légèrement non conformes.
+    ///
+    /// [`BiosParameterBlock::validate`]: crate::boot_sector::BiosParameterBlock::validate
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Efface ou non les clusters libérés (voir
+    /// [`Fat32Fs::set_zero_on_free`]).
+    pub fn zero_on_free(mut self, enabled: bool) -> Self {
+        self.zero_on_free = enabled;
+        self
+    }
+
+    /// Monte le volume décrit par `boot_sector` avec ces options.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::mount`], plus [`Fat32Error::InvalidBpb`]
+    /// pour un champ non conforme en mode strict.
+    pub fn build<D: BlockDevice>(&self, device: &'a D, boot_sector: &[u8]) -> Result<Fat32Fs<'a, D>> {
+        self.build_at(device, boot_sector, 0)
+    }
+
+    /// Monte avec ces options la partition FAT32 numéro `index` (0 à 3) de
+    /// la table MBR du device, comme [`Fat32Fs::mount_partition`].
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::mount_partition`], plus
+    /// [`Fat32Error::InvalidBpb`] pour un champ non conforme en mode strict.
+    pub fn build_partition<D: BlockDevice>(&self, device: &'a D, index: usize) -> Result<Fat32Fs<'a, D>> {
+        if index >= 4 {
+            return Err(Fat32Error::OutOfBounds);
+        }
+
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = buf
+            .get_mut(..D::SECTOR_SIZE)
+            .ok_or(Fat32Error::UnsupportedSectorSize)?;
+        device.read_sectors(0, 1, sector).map_err(Fat32Error::from)?;
+        let mut sector0 = [0u8; 512];
+        sector0.copy_from_slice(&sector[..512]);
+
+        let partition = parse_mbr(&sector0)?[index];
+        if !partition.is_fat32() {
+            return Err(Fat32Error::NotFat32);
+        }
+
+        device
+            .read_sectors(partition.start_lba, 1, sector)
+            .map_err(Fat32Error::from)?;
+        self.build_at(device, sector, partition.start_lba)
+    }
+
+    // Monte le volume dont le secteur de boot se trouve au secteur
+    // `partition_start` du device.
+    fn build_at<D: BlockDevice>(
+        &self,
+        device: &'a D,
+        boot_sector: &[u8],
+        partition_start: u32,
+    ) -> Result<Fat32Fs<'a, D>> {
+        let geom = volume_geometry(boot_sector, D::SECTOR_SIZE, self.strict)?;
+        check_device_size(&geom, device.sector_count(), partition_start)?;
+
+        let mut fs = Fat32Fs::new_with_mode(device, geom, self.mode);
+        fs.partition_start = partition_start;
+        fs.clock = self.clock;
+        fs.max_chain_length = self.max_chain_length;
+        fs.zero_on_free = self.zero_on_free;
+        fs.backup_boot_sector = BootSector::parse(boot_sector)
+            .and_then(|boot| backup_boot_lba(&boot))
+            .filter(|&lba| lba < fs.geom.fat_start_lba);
+        Ok(fs)
+    }
+}
+
+// Secteur de secours annoncé par la BPB, `None` si le volume n'en a pas
+// (0 ou 0xFFFF).
+fn backup_boot_lba(boot: &BootSector) -> Option<u32> {
+    match boot.bpb.backup_boot_sector() {
+        0 | 0xFFFF => None,
+        sector => Some(sector as u32),
+    }
 }
 
+// Premier octet d'une entrée de répertoire supprimée.
+const DELETED_ENTRY: u8 = 0xE5;
+
+// Bits d'attributs modifiables après la création d'une entrée ; les autres
+// (répertoire, nom de volume, bits réservés) sont conservés.
+const MODIFIABLE_ATTRIBUTES: u8 = attr::READ_ONLY | attr::HIDDEN | attr::SYSTEM | attr::ARCHIVE;
+
+// `bits` avec les bits de `mask` posés ou retirés.
+fn with_bit(bits: u8, mask: u8, set: bool) -> u8 {
+    if set {
+        bits | mask
+    } else {
+        bits & !mask
+    }
+}
+
+// Emplacement d'une entrée de 32 octets : cluster du répertoire et offset en
+// octets dans ce cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct EntryPosition {
+    pub(crate) cluster: u32,
+    pub(crate) offset: u32,
+}
+
+// Emplacement d'une entrée de répertoire et de ses fragments LFN : premier
+// fragment, nombre d'entrées de 32 octets et entrée 8.3 (la dernière).
+#[derive(Debug, Clone, Copy, Default)]
+struct EntrySpan {
+    first: EntryPosition,
+    slots: u32,
+    short: EntryPosition,
+}
+
+// Valeur de `fat_cache_lba` quand le cache de FAT est vide.
+const NO_SECTOR: u32 = u32::MAX;
+
+// Taille des buffers de lecture sur la pile. Les clusters plus grands (jusqu'à
+// 64 Ko en FAT32) sont lus par morceaux de cette taille.
+const CHUNK_SIZE: usize = 4096;
+
 impl<'a, D: BlockDevice> Fat32Fs<'a, D> {
-    /// Crée une nouvelle instance avec un périphérique et une géométrie donnés.
+    /// Crée une nouvelle instance en lecture seule avec un périphérique et une
+    /// géométrie donnés.
     pub fn new(device: &'a D, geom: Fat32Geometry) -> Self {
-        Self { device, geom }
+        Self::new_with_mode(device, geom, Mode::ReadOnly)
+    }
+
+    /// Comme [`Fat32Fs::new`], avec le mode de montage `mode`.
+    pub fn new_with_mode(device: &'a D, geom: Fat32Geometry, mode: Mode) -> Self {
+        Self {
+            device,
+            geom,
+            fat_cache_lba: Cell::new(NO_SECTOR),
+            fat_cache: RefCell::new([0u8; MAX_SECTOR_SIZE]),
+            max_chain_length: None,
+            partition_start: 0,
+            mode,
+            clock: None,
+            zero_on_free: false,
+            backup_boot_sector: None,
+        }
+    }
+
+    /// Fixe l'horloge utilisée pour dater les fichiers créés ou modifiés.
+    pub fn set_clock(&mut self, clock: &'a dyn Clock) {
+        self.clock = Some(clock);
+    }
+
+    /// Date et heure courantes selon l'horloge du volume, [`DOS_EPOCH`] si
+    /// aucune horloge n'a été fournie.
+    pub fn now(&self) -> Timestamp {
+        self.clock.map_or(DOS_EPOCH, |clock| clock.now())
+    }
+
+    /// Mode de montage du volume.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    // Refuse toute modification d'un volume monté en lecture seule.
+    pub(crate) fn check_writable(&self) -> Result<()> {
+        match self.mode {
+            Mode::ReadOnly => Err(Fat32Error::ReadOnly),
+            Mode::ReadWrite => Ok(()),
+        }
+    }
+
+    /// Premier secteur du volume sur le device (non nul pour un volume monté
+    /// avec [`Fat32Fs::mount_partition`]).
+    pub fn partition_start(&self) -> u32 {
+        self.partition_start
+    }
+
+    /// Secteur de la copie de secours du secteur de boot, relatif au début du
+    /// volume, ou `None` si la BPB n'en annonce pas (ou si le volume a été
+    /// créé avec [`Fat32Fs::new`]).
+    pub fn backup_boot_sector(&self) -> Option<u32> {
+        self.backup_boot_sector
+    }
+
+    /// Nombre maximal de clusters suivis dans une chaîne avant d'abandonner.
+    ///
+    /// Par défaut, c'est le nombre de clusters du volume : une chaîne valide
+    /// ne peut pas être plus longue.
+    pub fn max_chain_length(&self) -> u32 {
+        self.max_chain_length.unwrap_or_else(|| self.geom.total_clusters())
+    }
+
+    /// Fixe la longueur maximale d'une chaîne de clusters (`None` pour revenir
+    /// à la valeur par défaut, le nombre de clusters du volume).
+    pub fn set_max_chain_length(&mut self, limit: Option<u32>) {
+        self.max_chain_length = limit;
+    }
+
+    /// True si les clusters libérés sont remplis de zéros (voir
+    /// [`Fat32Fs::set_zero_on_free`]).
+    pub fn zero_on_free(&self) -> bool {
+        self.zero_on_free
+    }
+
+    /// Active ou non l'effacement des clusters libérés.
+    ///
+    /// Quand il est actif, chaque cluster rendu libre
+    /// ([`Fat32Fs::free_chain`], [`Fat32Fs::truncate`], [`Fat32Fs::remove_dir`]...) est rempli de zéros
+    /// avant que son entrée FAT ne soit remise à 0 : les anciennes données ne
+    /// restent pas lisibles sur le support. Chaque cluster libéré coûte alors
+    /// une écriture de plus ; désactivé par défaut.
+    pub fn set_zero_on_free(&mut self, enabled: bool) {
+        self.zero_on_free = enabled;
     }
 
-    /// Monte un volume FAT32 à partir du secteur de boot.
+    /// Monte un volume FAT32 en lecture seule à partir du secteur de boot.
+    ///
+    /// Utiliser [`Fat32Fs::mount_with_mode`] pour pouvoir le modifier.
     /// 
     /// # Errors
     /// 
-    /// Retourne une erreur si le secteur de boot n'est pas valide.
-    /// 
-    /// # Safety
-    /// 
-    /// Cette fonction utilise du code unsafe pour caster les octets bruts
-    /// en structure BPB. Le secteur fourni doit contenir un boot sector valide.
+    /// Retourne une erreur si le secteur de boot n'est pas valide, notamment
+    /// [`Fat32Error::NotFat32`] si le nombre de clusters correspond à un volume
+    /// FAT12 ou FAT16 (voir [`detect_fat_type`]), [`Fat32Error::InvalidBpb`]
+    /// si un champ de la BPB est incohérent (voir
+    /// [`BiosParameterBlock::validate`]) et
+    /// [`Fat32Error::UnsupportedSectorSize`] si la taille de secteur de la BPB
+    /// n'est pas autorisée ou diffère de `D::SECTOR_SIZE`.
+    ///
+    /// Si le device connaît sa taille ([`BlockDevice::sector_count`]), le
+    /// volume doit y tenir entièrement, sinon [`Fat32Error::DeviceTooSmall`]
+    /// est retourné (image tronquée).
+    ///
+    /// [`BiosParameterBlock::validate`]: crate::boot_sector::BiosParameterBlock::validate
     pub fn mount(device: &'a D, boot_sector: &[u8]) -> Result<Self> {
-        // Vérifie la signature du boot sector (octets 510-511 = 0x55AA)
-        if boot_sector.len() < 512 {
-            return Err(Fat32Error::InvalidBootSector);
+        Fat32FsBuilder::new().build(device, boot_sector)
+    }
+
+    /// Monte un volume FAT32 comme [`Fat32Fs::mount`], dans le mode `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::mount`].
+    pub fn mount_with_mode(device: &'a D, boot_sector: &[u8], mode: Mode) -> Result<Self> {
+        Fat32FsBuilder::new().mode(mode).build(device, boot_sector)
+    }
+
+    /// Monte un volume FAT32 comme [`Fat32Fs::mount_with_mode`], en datant
+    /// les fichiers créés ou modifiés avec `clock`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::mount`].
+    pub fn mount_with_clock(
+        device: &'a D,
+        boot_sector: &[u8],
+        mode: Mode,
+        clock: &'a dyn Clock,
+    ) -> Result<Self> {
+        Fat32FsBuilder::new()
+            .mode(mode)
+            .with_clock(clock)
+            .build(device, boot_sector)
+    }
+
+    /// Monte en lecture seule la partition FAT32 numéro `index` (0 à 3) de la
+    /// table MBR du device.
+    ///
+    /// Le secteur 0 est lu comme un MBR, puis le secteur de boot de la
+    /// partition. Tous les secteurs du volume sont ensuite relatifs au début
+    /// de la partition.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::InvalidMbr`] si le secteur 0 n'est pas un MBR
+    /// - [`Fat32Error::OutOfBounds`] si `index` est supérieur à 3
+    /// - [`Fat32Error::NotFat32`] si la partition n'est pas de type `0x0B` ou `0x0C`
+    /// - les erreurs de [`Fat32Fs::mount`] pour le secteur de boot de la partition
+    ///
+    /// Utiliser [`Fat32Fs::mount_partition_with_mode`] pour pouvoir la
+    /// modifier, ou [`Fat32FsBuilder::build_partition`] pour les autres
+    /// options.
+    pub fn mount_partition(device: &'a D, index: usize) -> Result<Self> {
+        Fat32FsBuilder::new().build_partition(device, index)
+    }
+
+    /// Monte une partition comme [`Fat32Fs::mount_partition`], dans le mode
+    /// `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::mount_partition`].
+    pub fn mount_partition_with_mode(device: &'a D, index: usize, mode: Mode) -> Result<Self> {
+        Fat32FsBuilder::new().mode(mode).build_partition(device, index)
+    }
+
+    /// Monte le volume comme [`Fat32Fs::mount`] et, si `boot_sector` est
+    /// refusé, réessaie avec sa copie de secours.
+    ///
+    /// La copie est lue au secteur indiqué par la BPB de `boot_sector` quand
+    /// ce champ est plausible (dans la zone réservée), sinon au secteur 6,
+    /// l'emplacement standard. Les erreurs de lecture ne déclenchent pas le
+    /// repli.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`Fat32Fs::mount`] pour `boot_sector` si elles ne
+    /// viennent pas de sa validation, sinon celles du montage de la copie.
+    pub fn mount_from_backup(device: &'a D, boot_sector: &[u8]) -> Result<Self> {
+        match Self::mount(device, boot_sector) {
+            Err(
+                Fat32Error::InvalidBootSector
+                | Fat32Error::InvalidBpb(_)
+                | Fat32Error::NotFat32
+                | Fat32Error::UnsupportedSectorSize,
+            ) => {}
+            result => return result,
+        }
+
+        let lba = BootSector::parse(boot_sector)
+            .and_then(|boot| {
+                backup_boot_lba(&boot).filter(|&lba| lba < boot.bpb.reserved_sector_count() as u32)
+            })
+            .unwrap_or(BACKUP_BOOT_SECTOR as u32);
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let backup = buf
+            .get_mut(..D::SECTOR_SIZE)
+            .ok_or(Fat32Error::UnsupportedSectorSize)?;
+        device.read_sectors(lba, 1, backup).map_err(Fat32Error::from)?;
+        Self::mount(device, backup)
+    }
+
+    // Lit `count` secteurs à partir du secteur `lba` du volume.
+    pub(crate) fn read_sectors(&self, lba: u32, count: u32, buf: &mut [u8]) -> Result<()> {
+        let lba = self
+            .partition_start
+            .checked_add(lba)
+            .ok_or(Fat32Error::OutOfBounds)?;
+        self.device
+            .read_sectors(lba, count, buf)
+            .map_err(Fat32Error::from)
+    }
+
+    // Écrit `count` secteurs à partir du secteur `lba` du volume. Toutes les
+    // écritures passent par ici : c'est le dernier rempart du mode lecture seule.
+    pub(crate) fn write_sectors(&self, lba: u32, count: u32, buf: &[u8]) -> Result<()> {
+        self.check_writable()?;
+        let lba = self
+            .partition_start
+            .checked_add(lba)
+            .ok_or(Fat32Error::OutOfBounds)?;
+        self.device
+            .write_sectors(lba, count, buf)
+            .map_err(Fat32Error::from)
+    }
+
+    // Partie de `buf` correspondant à un secteur du volume, ou
+    // `UnsupportedSectorSize` si la géométrie annonce une taille de secteur
+    // invalide (possible avec une géométrie construite à la main).
+    pub(crate) fn sector_buf<'b>(&self, buf: &'b mut [u8; MAX_SECTOR_SIZE]) -> Result<&'b mut [u8]> {
+        if !is_valid_sector_size(self.geom.bytes_per_sector) {
+            return Err(Fat32Error::UnsupportedSectorSize);
         }
-        
-        if boot_sector[510] != 0x55 || boot_sector[511] != 0xAA {
-            return Err(Fat32Error::InvalidBootSector);
+        Ok(&mut buf[..self.geom.bytes_per_sector as usize])
+    }
+
+    // Vérifie que `cluster` désigne un cluster de la zone de données
+    // (entre 2 et `max_valid_cluster` inclus).
+    fn check_cluster(&self, cluster: u32) -> Result<()> {
+        if cluster < 2 || cluster > self.geom.max_valid_cluster() {
+            return Err(Fat32Error::InvalidCluster(cluster));
         }
+        Ok(())
+    }
 
-        // SAFETY: On a vérifié que boot_sector fait au moins 512 octets
-        // et contient la signature valide
-        let bpb = unsafe { BiosParameterBlock::from_sector(boot_sector) };
-        
-        // Vérifie que c'est bien FAT32 (fat_size_16 doit être 0)
-        if bpb.fat_size_16 != 0 || bpb.fat_size_32 == 0 {
-            return Err(Fat32Error::NotFat32);
+    /// Compare le secteur de boot à sa copie de secours.
+    ///
+    /// Retourne `false` si les deux secteurs diffèrent ou si le volume n'a
+    /// pas de copie (voir [`Fat32Fs::backup_boot_sector`]).
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture d'un des deux secteurs échoue.
+    pub fn verify_backup_boot(&self) -> Result<bool> {
+        let Some(backup_lba) = self.backup_boot_sector else {
+            return Ok(false);
+        };
+
+        let mut primary_buf = [0u8; MAX_SECTOR_SIZE];
+        let mut backup_buf = [0u8; MAX_SECTOR_SIZE];
+        let primary = self.sector_buf(&mut primary_buf)?;
+        let backup = self.sector_buf(&mut backup_buf)?;
+        self.read_sectors(0, 1, primary)?;
+        self.read_sectors(backup_lba, 1, backup)?;
+        Ok(primary == backup)
+    }
+
+    /// Lit le secteur FSInfo du volume (nombre de clusters libres et indice
+    /// du prochain cluster libre), sans parcourir la FAT.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::InvalidFsInfo`] si le volume n'a pas de secteur
+    /// FSInfo ou si ses signatures sont invalides.
+    pub fn read_fsinfo(&self) -> Result<FsInfo> {
+        if self.geom.fs_info_sector == 0 {
+            return Err(Fat32Error::InvalidFsInfo);
+        }
+
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+        self.read_sectors(self.geom.fs_info_sector, 1, sector)?;
+        FsInfo::parse(sector)
+    }
+
+    /// Écrit le nombre de clusters libres (offset 488) et l'indice de
+    /// prochain cluster libre (offset 492) de `info` dans le secteur FSInfo.
+    ///
+    /// Le secteur est relu et seuls ces deux champs sont remplacés : les
+    /// signatures et le reste du secteur sont conservés. [`fsinfo::UNKNOWN`]
+    /// (`0xFFFFFFFF`) est écrit tel quel pour marquer un champ inconnu.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::ReadOnly`] si le volume est monté en lecture
+    /// seule, [`Fat32Error::InvalidFsInfo`] si le volume n'a pas de secteur
+    /// FSInfo ou si ses signatures sont invalides, ou une erreur si la
+    /// lecture ou l'écriture échoue.
+    ///
+    /// [`fsinfo::UNKNOWN`]: crate::fsinfo::UNKNOWN
+    pub fn write_fsinfo(&self, info: &FsInfo) -> Result<()> {
+        self.check_writable()?;
+        if self.geom.fs_info_sector == 0 {
+            return Err(Fat32Error::InvalidFsInfo);
         }
 
-        let geom = Fat32Geometry::from_bpb(bpb);
-        Ok(Fat32Fs::new(device, geom))
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+        self.read_sectors(self.geom.fs_info_sector, 1, sector)?;
+        FsInfo::parse(sector)?;
+
+        sector[488..492].copy_from_slice(&info.free_count.to_le_bytes());
+        sector[492..496].copy_from_slice(&info.next_free.to_le_bytes());
+        self.write_sectors(self.geom.fs_info_sector, 1, sector)
+    }
+
+    // Ajoute `delta` au nombre de clusters libres du secteur FSInfo, s'il est
+    // présent, valide et si ce nombre est connu, et remplace l'indice de
+    // prochain cluster libre par `next_free` s'il est fourni. Ce ne sont que
+    // des indications : un secteur FSInfo absent ou invalide est ignoré.
+    fn update_fsinfo(&self, delta: i64, next_free: Option<u32>) -> Result<()> {
+        let info = match self.read_fsinfo() {
+            Ok(info) => info,
+            Err(Fat32Error::InvalidFsInfo) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let free_count = match info.free_clusters() {
+            Some(free) => (free as i64 + delta).clamp(0, self.geom.total_clusters() as i64) as u32,
+            None => info.free_count,
+        };
+        self.write_fsinfo(&FsInfo {
+            free_count,
+            next_free: next_free.unwrap_or(info.next_free),
+        })
     }
 
     /// Lit une entrée de la table FAT.
@@ -72,22 +737,21 @@ impl<'a, D: BlockDevice> Fat32Fs<'a, D> {
     /// 
     /// # Errors
     /// 
-    /// Retourne une erreur si la lecture échoue ou si le cluster est invalide.
+    /// Retourne une erreur si la lecture échoue ou si le cluster est invalide
+    /// (inférieur à 2 ou au-delà de la zone de données).
     pub fn read_fat_entry(&self, cluster: u32) -> Result<FatEntry> {
-        if cluster < 2 {
-            return Err(Fat32Error::InvalidCluster(cluster));
-        }
+        self.check_cluster(cluster)?;
 
-        // Calcul de l'offset dans la FAT (4 octets par entrée en FAT32)
-        let fat_offset = cluster * 4;
-        let fat_sector = self.geom.fat_start_lba + (fat_offset / self.geom.bytes_per_sector);
-        let entry_offset = (fat_offset % self.geom.bytes_per_sector) as usize;
+        let (fat_sector, entry_offset) = self.geom.fat_entry_location(cluster);
 
-        // Lit le secteur contenant l'entrée FAT
-        let mut sector = [0u8; 512];
-        self.device
-            .read_sectors(fat_sector, 1, &mut sector)
-            .map_err(Fat32Error::from)?;
+        // Lit le secteur contenant l'entrée FAT, sauf s'il est déjà en cache
+        let mut cache = self.fat_cache.borrow_mut();
+        let sector = self.sector_buf(&mut cache)?;
+        if self.fat_cache_lba.get() != fat_sector {
+            self.fat_cache_lba.set(NO_SECTOR);
+            self.read_sectors(fat_sector, 1, sector)?;
+            self.fat_cache_lba.set(fat_sector);
+        }
 
         // Extrait la valeur 32 bits (little-endian)
         let value = u32::from_le_bytes([
@@ -100,226 +764,5629 @@ impl<'a, D: BlockDevice> Fat32Fs<'a, D> {
         Ok(FatEntry::new(value & 0x0FFFFFFF)) // Masque les 4 bits de poids fort
     }
 
-    /// Lit un cluster entier dans un buffer.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `cluster` - Numéro du cluster à lire
-    /// * `buf` - Buffer de destination (doit être >= taille_cluster)
-    /// 
+    /// Écrit une entrée de la table FAT, dans toutes les copies de la FAT.
+    ///
+    /// Le secteur contenant l'entrée est lu depuis la FAT principale, modifié
+    /// (les 4 bits de poids fort, réservés, de la valeur existante sont
+    /// conservés) puis réécrit à la même position dans chacune des
+    /// `num_fats` copies.
+    ///
     /// # Errors
-    /// 
-    /// Retourne une erreur si le buffer est trop petit ou si la lecture échoue.
-    pub fn read_cluster(&self, cluster: u32, buf: &mut [u8]) -> Result<()> {
-        if cluster < 2 {
-            return Err(Fat32Error::InvalidCluster(cluster));
-        }
+    ///
+    /// Retourne [`Fat32Error::ReadOnly`] si le volume est monté en lecture
+    /// seule, ou une erreur si le cluster est invalide, si le device ne
+    /// supporte pas l'écriture ou si une lecture/écriture échoue.
+    pub fn write_fat_entry(&self, cluster: u32, entry: FatEntry) -> Result<()> {
+        self.check_writable()?;
+        self.check_cluster(cluster)?;
 
-        let cluster_size = (self.geom.sectors_per_cluster * self.geom.bytes_per_sector) as usize;
-        if buf.len() < cluster_size {
-            return Err(Fat32Error::BufferTooSmall);
+        let fat_offset = cluster * 4;
+        let sector_in_fat = fat_offset / self.geom.bytes_per_sector;
+        let entry_offset = (fat_offset % self.geom.bytes_per_sector) as usize;
+
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+        self.read_sectors(self.geom.fat_start_lba + sector_in_fat, 1, sector)?;
+
+        let old = u32::from_le_bytes([
+            sector[entry_offset],
+            sector[entry_offset + 1],
+            sector[entry_offset + 2],
+            sector[entry_offset + 3],
+        ]);
+        let value = (old & 0xF0000000) | (entry.value & 0x0FFFFFFF);
+        sector[entry_offset..entry_offset + 4].copy_from_slice(&value.to_le_bytes());
+
+        // Le secteur en cache ne reflète plus la FAT
+        self.invalidate_fat_cache();
+
+        // Recopie le secteur modifié dans chaque FAT
+        for fat in 0..self.geom.num_fats {
+            let lba = self.geom.fat_start_lba + fat * self.geom.fat_size + sector_in_fat;
+            self.write_sectors(lba, 1, sector)?;
         }
 
-        let lba = self.geom.cluster_to_lba(cluster);
-        self.device
-            .read_sectors(lba, self.geom.sectors_per_cluster, buf)
-            .map_err(Fat32Error::from)
+        Ok(())
     }
 
-    /// Lit la chaîne complète de clusters (utile pour lire un fichier entier).
-    /// 
-    /// # Arguments
-    /// 
-    /// * `start_cluster` - Premier cluster de la chaîne
-    /// * `callback` - Fonction appelée pour chaque cluster lu
-    /// 
-    /// # Errors
-    /// 
-    /// Retourne une erreur si la lecture échoue.
-    pub fn read_cluster_chain<F>(&self, start_cluster: u32, mut callback: F) -> Result<()>
-    where
-        F: FnMut(u32, &[u8]) -> Result<()>,
-    {
-        let cluster_size = (self.geom.sectors_per_cluster * self.geom.bytes_per_sector) as usize;
-        let mut buf = [0u8; 4096]; // Suppose cluster <= 4KB
-        
-        if cluster_size > buf.len() {
-            return Err(Fat32Error::BufferTooSmall);
-        }
+    /// Vide le cache du dernier secteur de FAT lu.
+    ///
+    /// [`Fat32Fs::write_fat_entry`] s'en charge déjà ; à appeler seulement si
+    /// la FAT est modifiée directement par le device.
+    pub fn invalidate_fat_cache(&self) {
+        self.fat_cache_lba.set(NO_SECTOR);
+    }
 
-        let mut current_cluster = start_cluster;
-        let mut cluster_count = 0;
-        const MAX_CLUSTERS: u32 = 100000; // Protection contre boucles infinies
+    // Premier numéro de cluster hors de la zone parcourue par les recherches
+    // dans la FAT : la fin du volume, ou la fin de la FAT si la BPB annonce
+    // plus de clusters qu'elle ne peut en décrire (les secteurs suivants sont
+    // des données, pas des entrées).
+    fn fat_scan_end(&self) -> u32 {
+        let fat_entries = self.geom.fat_size.saturating_mul(self.geom.bytes_per_sector / 4);
+        (self.geom.total_clusters() + 2).min(fat_entries)
+    }
 
-        loop {
-            // Protection contre boucles infinies
-            if cluster_count >= MAX_CLUSTERS {
-                return Err(Fat32Error::InvalidCluster(current_cluster));
-            }
+    /// Cherche le premier cluster libre à partir de `start_hint`.
+    ///
+    /// La recherche parcourt la FAT jusqu'au dernier cluster du volume puis
+    /// reprend au cluster 2, jusqu'à revenir au point de départ. Un indice
+    /// hors du volume est ramené au cluster 2. La FAT est lue secteur par
+    /// secteur plutôt qu'entrée par entrée.
+    ///
+    /// Retourne `None` si le volume est plein.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de la FAT échoue.
+    pub fn find_free_cluster(&self, start_hint: u32) -> Result<Option<u32>> {
+        let end = self.fat_scan_end();
+        let total = end.saturating_sub(2);
+        let start = if start_hint < 2 || start_hint >= end { 2 } else { start_hint };
 
-            // Lit le cluster
-            self.read_cluster(current_cluster, &mut buf[..cluster_size])?;
-            callback(current_cluster, &buf[..cluster_size])?;
+        let entries_per_sector = self.geom.bytes_per_sector / 4;
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+        let mut loaded = None;
 
-            // Lit l'entrée FAT pour trouver le cluster suivant
-            let fat_entry = self.read_fat_entry(current_cluster)?;
-            
-            if fat_entry.is_end() {
-                break;
+        for i in 0..total {
+            let mut cluster = start + i;
+            if cluster >= end {
+                cluster -= total;
             }
 
-            match fat_entry.next_cluster() {
-                Some(next) => current_cluster = next,
-                None => break,
+            let sector_index = cluster / entries_per_sector;
+            if loaded != Some(sector_index) {
+                self.read_sectors(self.geom.fat_start_lba + sector_index, 1, sector)?;
+                loaded = Some(sector_index);
             }
 
-            cluster_count += 1;
+            let offset = ((cluster % entries_per_sector) * 4) as usize;
+            let value = u32::from_le_bytes([
+                sector[offset],
+                sector[offset + 1],
+                sector[offset + 2],
+                sector[offset + 3],
+            ]);
+            if FatEntry::new(value & 0x0FFFFFFF).is_free() {
+                return Ok(Some(cluster));
+            }
         }
 
-        Ok(())
+        Ok(None)
     }
 
-    /// Lit le répertoire racine.
-    /// 
-    /// # Returns
-    /// 
-    /// Un itérateur sur les entrées de répertoire du root.
-    pub fn read_root_dir(&self) -> Result<DirectoryIterator<'_, 'a, D>> {
-        DirectoryIterator::new(self, self.geom.root_cluster)
+    /// Compte les clusters libres en parcourant toute la FAT.
+    ///
+    /// Contrairement au compteur du secteur FSInfo, souvent périmé ou marqué
+    /// inconnu, le résultat fait foi. La FAT est lue par blocs de plusieurs
+    /// secteurs : le coût reste raisonnable sur un grand volume (environ
+    /// 1 Mo de FAT pour 256 000 clusters). Si la BPB annonce plus de clusters
+    /// que la FAT ne peut en décrire, les clusters sans entrée ne sont pas
+    /// comptés comme libres.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de la FAT échoue.
+    pub fn free_clusters(&self) -> Result<u32> {
+        let bps = self.geom.bytes_per_sector;
+        if !is_valid_sector_size(bps) {
+            return Err(Fat32Error::UnsupportedSectorSize);
+        }
+        let end = self.fat_scan_end();
+        let entries_per_sector = bps / 4;
+        let fat_sectors = end.div_ceil(entries_per_sector);
+        let sectors_per_chunk = CHUNK_SIZE as u32 / bps;
+
+        let mut buf = [0u8; CHUNK_SIZE];
+        let mut free = 0;
+        let mut sector = 0;
+        while sector < fat_sectors {
+            let count = sectors_per_chunk.min(fat_sectors - sector);
+            let chunk = &mut buf[..(count * bps) as usize];
+            self.read_sectors(self.geom.fat_start_lba + sector, count, chunk)?;
+
+            let first = sector * entries_per_sector;
+            for (cluster, raw) in (first..).zip(chunk.chunks_exact(4)) {
+                if cluster < 2 || cluster >= end {
+                    continue;
+                }
+                let value = u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]);
+                if FatEntry::new(value & 0x0FFFFFFF).is_free() {
+                    free += 1;
+                }
+            }
+            sector += count;
+        }
+        Ok(free)
     }
-}
 
-/// Itérateur sur les entrées d'un répertoire FAT32.
-pub struct DirectoryIterator<'fs, 'a, D: BlockDevice> {
-    fs: &'fs Fat32Fs<'a, D>,
-    cluster: u32,
-    offset: usize,
-    buffer: [u8; 4096],
-    done: bool,
-}
+    /// Nombre de clusters utilisés : tous ceux qui ne sont pas libres.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de la FAT échoue.
+    pub fn used_clusters(&self) -> Result<u32> {
+        Ok(self.geom.total_clusters() - self.free_clusters()?)
+    }
 
-impl<'fs, 'a, D: BlockDevice> DirectoryIterator<'fs, 'a, D> {
-    fn new(fs: &'fs Fat32Fs<'a, D>, start_cluster: u32) -> Result<Self> {
-        let mut iter = Self {
-            fs,
-            cluster: start_cluster,
-            offset: 0,
-            buffer: [0u8; 4096],
-            done: false,
-        };
-        
-        // Charge le premier cluster
-        let cluster_size = (fs.geom.sectors_per_cluster * fs.geom.bytes_per_sector) as usize;
-        fs.read_cluster(start_cluster, &mut iter.buffer[..cluster_size])?;
-        
-        Ok(iter)
+    /// Occupation du volume (total, libre, utilisé), pour un outil de type `df`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de la FAT échoue.
+    pub fn statfs(&self) -> Result<StatFs> {
+        let total = self.geom.total_clusters();
+        let free = self.free_clusters()?;
+        Ok(StatFs {
+            total,
+            free,
+            used: total - free,
+            cluster_size: self.geom.cluster_size_bytes(),
+        })
     }
 
-    /// Retourne la prochaine entrée de répertoire valide.
-    pub fn next_entry(&mut self) -> Result<Option<&DirectoryEntryRaw>> {
-        if self.done {
-            return Ok(None);
+    /// Charge toute la FAT principale en mémoire (feature `alloc`), pour
+    /// parcourir de nombreuses chaînes sans relire la FAT sur le device.
+    ///
+    /// La table occupe 4 octets par cluster du volume : environ 1 Mo pour
+    /// 256 000 clusters. Elle est lue par blocs de plusieurs secteurs, comme
+    /// dans [`Fat32Fs::free_clusters`], et ne suit pas les modifications
+    /// ultérieures de la FAT. Comme dans [`Fat32Fs::free_clusters`], la
+    /// lecture s'arrête à la fin de la FAT si la BPB annonce plus de clusters.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de la FAT échoue.
+    #[cfg(any(test, feature = "alloc"))]
+    pub fn load_fat(&self) -> Result<FatTable> {
+        let bps = self.geom.bytes_per_sector;
+        if !is_valid_sector_size(bps) {
+            return Err(Fat32Error::UnsupportedSectorSize);
         }
+        let end = self.fat_scan_end();
+        let fat_sectors = end.div_ceil(bps / 4);
+        let sectors_per_chunk = CHUNK_SIZE as u32 / bps;
 
-        loop {
-            // Vérifie si on est à la fin du cluster actuel
-            if self.offset >= 4096 {
-                // Charge le cluster suivant
-                let fat_entry = self.fs.read_fat_entry(self.cluster)?;
-                
-                if fat_entry.is_end() {
-                    self.done = true;
-                    return Ok(None);
+        let mut entries = Vec::with_capacity(end as usize);
+        let mut buf = [0u8; CHUNK_SIZE];
+        let mut sector = 0;
+        while sector < fat_sectors {
+            let count = sectors_per_chunk.min(fat_sectors - sector);
+            let chunk = &mut buf[..(count * bps) as usize];
+            self.read_sectors(self.geom.fat_start_lba + sector, count, chunk)?;
+            for raw in chunk.chunks_exact(4) {
+                if entries.len() == end as usize {
+                    break;
                 }
+                entries.push(u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]));
+            }
+            sector += count;
+        }
+        Ok(FatTable::from_entries(entries))
+    }
 
-                match fat_entry.next_cluster() {
-                    Some(next) => {
-                        self.cluster = next;
-                        self.offset = 0;
-                        let cluster_size = (self.fs.geom.sectors_per_cluster 
-                                          * self.fs.geom.bytes_per_sector) as usize;
-                        self.fs.read_cluster(next, &mut self.buffer[..cluster_size])?;
+    /// Compare les copies de la FAT à la FAT principale, secteur par secteur.
+    ///
+    /// Retourne le numéro du premier cluster dont l'entrée diffère entre la
+    /// FAT principale et l'une des copies, ou `None` si toutes les copies
+    /// sont identiques (ou s'il n'y a qu'une FAT). La comparaison s'arrête à
+    /// la première différence.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de la FAT échoue.
+    pub fn verify_fats(&self) -> Result<Option<u32>> {
+        if self.geom.num_fats < 2 {
+            return Ok(None);
+        }
+
+        let entries_per_sector = self.geom.bytes_per_sector / 4;
+        let mut primary_buf = [0u8; MAX_SECTOR_SIZE];
+        let mut copy_buf = [0u8; MAX_SECTOR_SIZE];
+        let primary = self.sector_buf(&mut primary_buf)?;
+        let copy = self.sector_buf(&mut copy_buf)?;
+
+        for sector in 0..self.geom.fat_size {
+            self.read_sectors(self.geom.fat_start_lba + sector, 1, primary)?;
+            for fat in 1..self.geom.num_fats {
+                let lba = self.geom.fat_start_lba + fat * self.geom.fat_size + sector;
+                self.read_sectors(lba, 1, copy)?;
+
+                let mismatch = primary
+                    .chunks_exact(4)
+                    .zip(copy.chunks_exact(4))
+                    .position(|(a, b)| a != b);
+                if let Some(index) = mismatch {
+                    return Ok(Some(sector * entries_per_sector + index as u32));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Recopie la FAT principale sur toutes les autres copies de la FAT.
+    ///
+    /// Opération destructive : le contenu des copies est écrasé sans
+    /// vérification, même s'il était plus juste que celui de la FAT
+    /// principale. À utiliser après [`Fat32Fs::verify_fats`], une fois la
+    /// FAT principale jugée fiable.
+    ///
+    /// Retourne le nombre de secteurs réécrits (0 s'il n'y a qu'une FAT).
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::ReadOnly`] si le volume est monté en lecture
+    /// seule, ou une erreur si une lecture ou une écriture échoue.
+    pub fn repair_fats(&self) -> Result<u32> {
+        self.check_writable()?;
+        if self.geom.num_fats < 2 {
+            return Ok(0);
+        }
+
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector_data = self.sector_buf(&mut buf)?;
+        let mut written = 0;
+        for sector in 0..self.geom.fat_size {
+            self.read_sectors(self.geom.fat_start_lba + sector, 1, sector_data)?;
+            for fat in 1..self.geom.num_fats {
+                let lba = self.geom.fat_start_lba + fat * self.geom.fat_size + sector;
+                self.write_sectors(lba, 1, sector_data)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Itère sur les clusters marqués défectueux (`0x0FFFFFF7`) dans la FAT.
+    ///
+    /// Seule la FAT est lue, secteur par secteur grâce au cache de
+    /// [`Fat32Fs::read_fat_entry`] : la zone de données n'est pas touchée.
+    /// L'itérateur s'arrête après la première erreur de lecture.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::UnsupportedSectorSize`] si la taille de
+    /// secteur de la géométrie n'est pas prise en charge.
+    pub fn scan_bad_clusters(&self) -> Result<BadClusterIter<'_, 'a, D>> {
+        if !is_valid_sector_size(self.geom.bytes_per_sector) {
+            return Err(Fat32Error::UnsupportedSectorSize);
+        }
+        Ok(BadClusterIter {
+            fs: self,
+            next: 2,
+            end: self.geom.max_valid_cluster() + 1,
+        })
+    }
+
+    /// Lit le secteur `lba` du volume (relatif au début de la partition)
+    /// dans les `bytes_per_sector` premiers octets de `buf`.
+    ///
+    /// Pratique pour les secteurs de la zone réservée : secteur de boot de
+    /// secours, FSInfo...
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::BufferTooSmall`] si `buf` ne contient pas un
+    /// secteur entier, ou une erreur de lecture.
+    pub fn read_sector(&self, lba: u32, buf: &mut [u8]) -> Result<()> {
+        let sector_size = self.geom.bytes_per_sector as usize;
+        if buf.len() < sector_size {
+            return Err(Fat32Error::BufferTooSmall);
+        }
+        self.read_sectors(lba, 1, &mut buf[..sector_size])
+    }
+
+    /// Comme [`Fat32Fs::read_sector`], mais retourne le secteur dans un
+    /// tableau de `N` octets (complété par des zéros si `N` dépasse la
+    /// taille de secteur).
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::BufferTooSmall`] si `N` est plus petit que la
+    /// taille de secteur, ou une erreur de lecture.
+    ///
+    /// # Exemples
+    ///
+    /// ```no_run
+    /// # use fat32_parser::{BlockDevice, Fat32Fs, Result};
+    /// # fn example<D: BlockDevice>(fs: &Fat32Fs<'_, D>) -> Result<()> {
+    /// let boot: [u8; 512] = fs.read_sector_into(0)?;
+    /// assert_eq!(&boot[510..], &[0x55, 0xAA]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_sector_into<const N: usize>(&self, lba: u32) -> Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.read_sector(lba, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Lit un cluster entier dans un buffer.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cluster` - Numéro du cluster à lire
+    /// * `buf` - Buffer de destination (doit être >= taille_cluster)
+    /// 
+    /// # Errors
+    /// 
+    /// Retourne une erreur si le cluster est hors de la zone de données, si le
+    /// buffer est trop petit ou si la lecture échoue.
+    pub fn read_cluster(&self, cluster: u32, buf: &mut [u8]) -> Result<()> {
+        self.check_cluster(cluster)?;
+
+        let cluster_size = self.geom.cluster_size_bytes() as usize;
+        if buf.len() < cluster_size {
+            return Err(Fat32Error::BufferTooSmall);
+        }
+
+        let lba = self.geom.cluster_to_lba(cluster)?;
+        self.read_sectors(lba, self.geom.sectors_per_cluster, buf)
+    }
+
+    /// Lit une partie d'un cluster : `buf.len() / bytes_per_sector` secteurs
+    /// à partir du secteur `first_sector` du cluster.
+    ///
+    /// Permet de parcourir un grand cluster (16, 32 ou 64 Ko) avec un buffer
+    /// de quelques secteurs.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::InvalidCluster`] si le cluster est hors de la
+    /// zone de données, [`Fat32Error::OutOfBounds`] si la plage demandée
+    /// dépasse la fin du cluster, ou une erreur de lecture.
+    pub fn read_cluster_sectors(&self, cluster: u32, first_sector: u32, buf: &mut [u8]) -> Result<()> {
+        self.check_cluster(cluster)?;
+
+        let count = (buf.len() / self.geom.bytes_per_sector as usize) as u32;
+        if first_sector as u64 + count as u64 > self.geom.sectors_per_cluster as u64 {
+            return Err(Fat32Error::OutOfBounds);
+        }
+
+        let lba = self.geom.cluster_to_lba(cluster)? + first_sector;
+        self.read_sectors(lba, count, buf)
+    }
+
+    /// Lit en un seul appel au device les clusters physiquement consécutifs
+    /// de la chaîne qui commence à `start_cluster`, et retourne leur nombre.
+    ///
+    /// La lecture s'arrête au premier cluster dont l'entrée FAT ne pointe pas
+    /// vers le cluster suivant sur le disque (fin de chaîne ou fragment), à
+    /// `max_clusters` clusters ou quand `buf` est plein. Pour lire une chaîne
+    /// entière, rappeler la méthode avec le cluster qui suit le dernier lu
+    /// (voir [`Fat32Fs::nth_cluster`]).
+    ///
+    /// Les entrées FAT consultées sont celles du cache d'un secteur : un
+    /// fichier contigu est lu avec un appel pour la FAT par secteur de FAT
+    /// traversé, plus un seul appel pour les données.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le cluster est hors de la zone de données,
+    /// [`Fat32Error::BufferTooSmall`] si `buf` ne contient pas un cluster
+    /// entier (et que `max_clusters` n'est pas nul), ou si une lecture échoue.
+    pub fn read_contiguous(&self, start_cluster: u32, max_clusters: u32, buf: &mut [u8]) -> Result<u32> {
+        self.check_cluster(start_cluster)?;
+        if max_clusters == 0 {
+            return Ok(0);
+        }
+
+        let cluster_size = self.geom.cluster_size_bytes() as usize;
+        let capacity = (buf.len() / cluster_size).min(max_clusters as usize) as u32;
+        if capacity == 0 {
+            return Err(Fat32Error::BufferTooSmall);
+        }
+
+        let mut count = 1;
+        let mut cluster = start_cluster;
+        while count < capacity && self.read_fat_entry(cluster)?.next_cluster() == Some(cluster + 1) {
+            cluster += 1;
+            count += 1;
+        }
+
+        let lba = self.geom.cluster_to_lba(start_cluster)?;
+        self.read_sectors(lba, count * self.geom.sectors_per_cluster, buf)?;
+        Ok(count)
+    }
+
+    /// Lit la chaîne complète de clusters (utile pour lire un fichier entier).
+    ///
+    /// Le callback reçoit chaque cluster en entier s'il fait au plus 4 Ko ;
+    /// un cluster plus grand est transmis en plusieurs morceaux consécutifs de
+    /// 4 Ko, avec le même numéro de cluster.
+    ///
+    /// L'entrée FAT qui donne le cluster suivant est lue avant l'appel du
+    /// callback sur le cluster courant : après le callback, seule la lecture
+    /// des données suivantes reste à faire.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `start_cluster` - Premier cluster de la chaîne
+    /// * `callback` - Fonction appelée pour chaque cluster (ou morceau) lu
+    /// 
+    /// # Errors
+    /// 
+    /// Retourne une erreur si la lecture échoue, et [`Fat32Error::CorruptedChain`]
+    /// si la chaîne boucle sur elle-même.
+    pub fn read_cluster_chain<F>(&self, start_cluster: u32, mut callback: F) -> Result<()>
+    where
+        F: FnMut(u32, &[u8]) -> Result<()>,
+    {
+        self.for_each_cluster(start_cluster, |cluster, data| {
+            callback(cluster, data)?;
+            Ok(ControlFlow::Continue(()))
+        })
+    }
+
+    /// Lit les données d'un fichier en s'arrêtant exactement à sa taille.
+    ///
+    /// Contrairement à [`read_cluster_chain`](Self::read_cluster_chain), le
+    /// dernier appel du callback ne reçoit que les octets restants
+    /// (`file_size % taille_cluster`, ou le cluster entier si la taille en est
+    /// un multiple) et les clusters au-delà de la taille ne sont pas lus.
+    /// Un fichier de taille nulle ne provoque aucune lecture.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_cluster` - Premier cluster du fichier
+    /// * `file_size` - Taille du fichier en octets (champ de l'entrée de répertoire)
+    /// * `callback` - Fonction appelée pour chaque cluster lu
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture échoue, et
+    /// [`Fat32Error::CorruptedChain`] si la chaîne se termine avant
+    /// `file_size` octets (le callback a alors reçu les octets disponibles),
+    /// ou si `start_cluster` vaut 0 pour une taille non nulle.
+    pub fn read_file_chain<F>(&self, start_cluster: u32, file_size: u32, mut callback: F) -> Result<()>
+    where
+        F: FnMut(u32, &[u8]) -> Result<()>,
+    {
+        if file_size == 0 {
+            return Ok(());
+        }
+        if start_cluster == 0 {
+            return Err(Fat32Error::CorruptedChain);
+        }
+
+        let mut remaining = file_size as usize;
+        self.for_each_cluster(start_cluster, |cluster, data| {
+            let len = remaining.min(data.len());
+            callback(cluster, &data[..len])?;
+            remaining -= len;
+
+            if remaining == 0 {
+                Ok(ControlFlow::Break(()))
+            } else {
+                Ok(ControlFlow::Continue(()))
+            }
+        })?;
+
+        if remaining > 0 {
+            return Err(Fat32Error::CorruptedChain);
+        }
+        Ok(())
+    }
+
+    /// Lit jusqu'à `buf.len()` octets d'un fichier à partir de l'octet
+    /// `offset`, sans [`File`] : pratique pour une lecture ponctuelle (en-tête
+    /// d'un fichier...).
+    ///
+    /// La chaîne est suivie dans la FAT jusqu'au cluster qui contient
+    /// `offset`, puis la lecture continue d'un cluster à l'autre et s'arrête
+    /// à `file_size`.
+    ///
+    /// Retourne le nombre d'octets lus : 0 si `offset` est au-delà de la fin
+    /// du fichier, moins que `buf.len()` si la fin du fichier est atteinte.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`File::read`].
+    pub fn read_range(&self, start_cluster: u32, file_size: u32, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        if offset >= file_size as u64 {
+            return Ok(0);
+        }
+        let mut file = File::new(start_cluster, file_size);
+        file.seek(offset)?;
+        file.read(self, buf)
+    }
+
+    // Parcourt la chaîne de clusters à partir de `start_cluster` en lisant
+    // chaque cluster par morceaux d'au plus `CHUNK_SIZE` octets, jusqu'à la
+    // fin de chaîne ou jusqu'à ce que `visit` demande l'arrêt.
+    fn for_each_cluster<F>(&self, start_cluster: u32, mut visit: F) -> Result<()>
+    where
+        F: FnMut(u32, &[u8]) -> Result<ControlFlow<()>>,
+    {
+        let bytes_per_sector = self.geom.bytes_per_sector as usize;
+        if bytes_per_sector == 0 || bytes_per_sector > CHUNK_SIZE {
+            return Err(Fat32Error::BufferTooSmall);
+        }
+
+        let mut buf = [0u8; CHUNK_SIZE];
+        let sectors_per_chunk = (CHUNK_SIZE / bytes_per_sector) as u32;
+
+        let mut chain = self.cluster_chain(start_cluster).peekable();
+        while let Some(cluster) = chain.next() {
+            let cluster = cluster?;
+            let mut sector = 0;
+            while sector < self.geom.sectors_per_cluster {
+                let count = sectors_per_chunk.min(self.geom.sectors_per_cluster - sector);
+                let chunk = &mut buf[..count as usize * bytes_per_sector];
+                self.read_cluster_sectors(cluster, sector, chunk)?;
+                // Cherche le cluster suivant dans la FAT avant de rendre la
+                // main au callback : la lecture de la FAT ne s'intercale plus
+                // entre le callback et la lecture des données suivantes. Une
+                // erreur de la FAT n'est remontée que si la chaîne est
+                // effectivement poursuivie.
+                let _ = chain.peek();
+                if visit(cluster, chunk)?.is_break() {
+                    return Ok(());
+                }
+                sector += count;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Itère sur les numéros de clusters de la chaîne commençant à
+    /// `start_cluster`, sans lire les données.
+    ///
+    /// Les entrées FAT sont lues au fur et à mesure. Chaque cluster est
+    /// vérifié comme par [`Fat32Fs::read_cluster`]. Une chaîne qui boucle
+    /// produit [`Fat32Error::CorruptedChain`], et une entrée libre avant la
+    /// fin de chaîne [`Fat32Error::CorruptedFat`]. L'itérateur s'arrête après
+    /// la première erreur.
+    pub fn cluster_chain(&self, start_cluster: u32) -> ClusterChain<'_, 'a, D> {
+        ClusterChain {
+            fs: self,
+            state: ChainState::Start(start_cluster),
+            saved: start_cluster,
+            power: 1,
+            steps: 0,
+            count: 0,
+            limit: self.max_chain_length(),
+        }
+    }
+
+    /// Retourne le cluster situé `index` maillons après `start` dans sa chaîne
+    /// (`start` lui-même pour un index nul), ou `None` si la chaîne se termine
+    /// avant.
+    ///
+    /// Permet de retrouver le cluster contenant un offset donné d'un fichier
+    /// (`offset / taille_cluster`) sans lire les données.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::InvalidCluster`] si `start` ou un cluster de la
+    /// chaîne est hors de la zone de données, et les mêmes erreurs que
+    /// [`Fat32Fs::cluster_chain`] sinon.
+    pub fn nth_cluster(&self, start: u32, index: u32) -> Result<Option<u32>> {
+        self.cluster_chain(start).nth(index as usize).transpose()
+    }
+
+    /// Nombre de clusters de la chaîne commençant à `start_cluster`.
+    ///
+    /// # Errors
+    ///
+    /// Mêmes erreurs que [`Fat32Fs::cluster_chain`].
+    pub fn chain_length(&self, start_cluster: u32) -> Result<u32> {
+        let mut length = 0;
+        for cluster in self.cluster_chain(start_cluster) {
+            cluster?;
+            length += 1;
+        }
+        Ok(length)
+    }
+
+    /// Espace occupé sur le disque par la chaîne commençant à `start_cluster`,
+    /// en octets : nombre de clusters multiplié par la taille d'un cluster.
+    ///
+    /// Toujours supérieur ou égal à la taille logique du fichier, arrondie au
+    /// cluster supérieur ; un écart plus grand signale une chaîne trop longue.
+    /// Un premier cluster à 0 (fichier vide) donne 0.
+    ///
+    /// # Errors
+    ///
+    /// Mêmes erreurs que [`Fat32Fs::cluster_chain`].
+    pub fn allocated_size(&self, start_cluster: u32) -> Result<u64> {
+        if start_cluster == 0 {
+            return Ok(0);
+        }
+        let clusters = self.chain_length(start_cluster)?;
+        Ok(clusters as u64 * self.geom.cluster_size_bytes() as u64)
+    }
+
+    /// Vérifie si chaque cluster de la chaîne suit immédiatement le précédent
+    /// (`n`, `n + 1`, `n + 2`...). Le parcours s'arrête à la première rupture.
+    ///
+    /// Un premier cluster à 0 (fichier vide) est considéré comme contigu.
+    ///
+    /// # Errors
+    ///
+    /// Mêmes erreurs que [`Fat32Fs::cluster_chain`].
+    pub fn is_contiguous(&self, start_cluster: u32) -> Result<bool> {
+        if start_cluster == 0 {
+            return Ok(true);
+        }
+        let mut previous = None;
+        for cluster in self.cluster_chain(start_cluster) {
+            let cluster = cluster?;
+            if previous.is_some_and(|p: u32| p.checked_add(1) != Some(cluster)) {
+                return Ok(false);
+            }
+            previous = Some(cluster);
+        }
+        Ok(true)
+    }
+
+    /// Nombre de fragments de la chaîne : suites de clusters consécutifs.
+    ///
+    /// Une chaîne contiguë compte un fragment ; un premier cluster à 0
+    /// (fichier vide), aucun.
+    ///
+    /// # Errors
+    ///
+    /// Mêmes erreurs que [`Fat32Fs::cluster_chain`].
+    pub fn fragment_count(&self, start_cluster: u32) -> Result<u32> {
+        if start_cluster == 0 {
+            return Ok(0);
+        }
+        let mut fragments = 0;
+        let mut previous = None;
+        for cluster in self.cluster_chain(start_cluster) {
+            let cluster = cluster?;
+            if previous.map_or(true, |p: u32| p.checked_add(1) != Some(cluster)) {
+                fragments += 1;
+            }
+            previous = Some(cluster);
+        }
+        Ok(fragments)
+    }
+
+    /// Libère tous les clusters de la chaîne commençant à `start_cluster` en
+    /// mettant leurs entrées FAT à 0, et retourne le nombre de clusters libérés.
+    ///
+    /// La chaîne est d'abord parcourue en entier : une chaîne qui boucle ou
+    /// qui sort du volume est refusée avant toute écriture, ce qui évite de
+    /// libérer des clusters appartenant à un autre fichier. Le nombre de
+    /// clusters libres du secteur FSInfo est ensuite mis à jour s'il est connu.
+    ///
+    /// Les données et l'entrée de répertoire ne sont pas modifiées. Un premier
+    /// cluster à 0 (fichier vide) ne libère rien.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::CorruptedChain`] si la chaîne boucle
+    /// - les erreurs de [`Fat32Fs::cluster_chain`] et de [`Fat32Fs::write_fat_entry`]
+    pub fn free_chain(&self, start_cluster: u32) -> Result<u32> {
+        self.check_writable()?;
+        if start_cluster == 0 {
+            return Ok(0);
+        }
+
+        let freed = self.release_chain(start_cluster)?;
+        self.update_fsinfo(freed as i64, None)?;
+        Ok(freed)
+    }
+
+    // Remet à 0 les entrées FAT de la chaîne, vérifiée au préalable, sans
+    // toucher au secteur FSInfo. Les clusters sont d'abord effacés si
+    // `zero_on_free` est actif.
+    fn release_chain(&self, start_cluster: u32) -> Result<u32> {
+        let length = self.chain_length(start_cluster)?;
+        let mut cluster = start_cluster;
+        let mut freed = 0;
+        while freed < length {
+            // Le maillon suivant est lu avant d'écraser l'entrée
+            let entry = self.read_fat_entry(cluster)?;
+            if self.zero_on_free {
+                self.zero_cluster(cluster)?;
+            }
+            self.write_fat_entry(cluster, FatEntry::free())?;
+            freed += 1;
+
+            match entry.next_cluster() {
+                Some(next) => cluster = next,
+                None => break,
+            }
+        }
+        Ok(freed)
+    }
+
+    /// Alloue une chaîne de `count` clusters libres et retourne son premier
+    /// cluster (0 si `count` est nul).
+    ///
+    /// Les clusters sont cherchés avec [`Fat32Fs::find_free_cluster`] à partir
+    /// de l'indice du secteur FSInfo, puis chacun à partir du précédent :
+    /// la chaîne est contiguë quand l'espace libre le permet. Chaque cluster
+    /// est marqué fin de chaîne avant d'être relié au précédent. Le secteur
+    /// FSInfo (nombre de clusters libres, prochain cluster libre) est mis à
+    /// jour à la fin.
+    ///
+    /// Les données des clusters ne sont pas effacées.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::DiskFull`] s'il y a moins de `count` clusters libres :
+    ///   les clusters déjà réservés sont alors libérés
+    /// - les erreurs de lecture ou d'écriture de la FAT et du secteur FSInfo
+    ///   (la chaîne réservée est alors libérée)
+    pub fn allocate_chain(&self, count: u32) -> Result<u32> {
+        self.check_writable()?;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let hint = self
+            .read_fsinfo()
+            .ok()
+            .and_then(|info| info.next_free_hint())
+            .unwrap_or(2);
+        let (first, last) = self.claim_clusters(hint, count)?;
+        if let Err(err) = self.update_fsinfo(-(count as i64), Some(self.next_free_after(last))) {
+            // Rien ne référence encore la chaîne : elle serait perdue
+            let _ = self.release_chain(first);
+            return Err(err);
+        }
+        Ok(first)
+    }
+
+    /// Alloue `count` clusters physiquement consécutifs et retourne le premier
+    /// (0 si `count` est nul), ou `None` si aucune plage libre assez grande
+    /// n'existe.
+    ///
+    /// La FAT est parcourue depuis le cluster 2 jusqu'à la première plage de
+    /// `count` clusters libres consécutifs, qui est ensuite chaînée comme
+    /// dans [`Fat32Fs::allocate_chain`]. Un fichier contigu se lit en un
+    /// seul appel au device (voir [`Fat32Fs::read_contiguous`]). Avec `None`,
+    /// rien n'est modifié : l'appelant peut se rabattre sur
+    /// [`Fat32Fs::allocate_chain`].
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - les erreurs de lecture ou d'écriture de la FAT et du secteur FSInfo
+    ///   (la plage réservée est alors libérée)
+    pub fn allocate_contiguous(&self, count: u32) -> Result<Option<u32>> {
+        self.check_writable()?;
+        if count == 0 {
+            return Ok(Some(0));
+        }
+
+        let Some(start) = self.find_free_run(count)? else {
+            return Ok(None);
+        };
+        let (first, last) = self.claim_clusters(start, count)?;
+        if let Err(err) = self.update_fsinfo(-(count as i64), Some(self.next_free_after(last))) {
+            let _ = self.release_chain(first);
+            return Err(err);
+        }
+        Ok(Some(first))
+    }
+
+    // Premier cluster de la première plage de `count` clusters libres
+    // consécutifs, en lisant la FAT secteur par secteur.
+    fn find_free_run(&self, count: u32) -> Result<Option<u32>> {
+        let end = self.fat_scan_end();
+        let entries_per_sector = self.geom.bytes_per_sector / 4;
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+        let mut loaded = None;
+        let mut run_start = 2;
+        let mut run_len = 0;
+
+        for cluster in 2..end {
+            let sector_index = cluster / entries_per_sector;
+            if loaded != Some(sector_index) {
+                self.read_sectors(self.geom.fat_start_lba + sector_index, 1, sector)?;
+                loaded = Some(sector_index);
+            }
+
+            let offset = ((cluster % entries_per_sector) * 4) as usize;
+            let value = u32::from_le_bytes([
+                sector[offset],
+                sector[offset + 1],
+                sector[offset + 2],
+                sector[offset + 3],
+            ]);
+            if !FatEntry::new(value & 0x0FFFFFFF).is_free() {
+                run_len = 0;
+                continue;
+            }
+
+            if run_len == 0 {
+                run_start = cluster;
+            }
+            run_len += 1;
+            if run_len == count {
+                return Ok(Some(run_start));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Ajoute `additional` clusters à la fin d'une chaîne dont `last_cluster`
+    /// est le dernier maillon, et retourne le nouveau dernier cluster
+    /// (`last_cluster` lui-même si `additional` est nul).
+    ///
+    /// Les nouveaux clusters sont cherchés à partir de `last_cluster + 1`
+    /// pour garder le fichier contigu, puis reliés à `last_cluster` dans
+    /// chaque copie de la FAT. Le secteur FSInfo est mis à jour.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::InvalidCluster`] si l'entrée FAT de `last_cluster` n'est
+    ///   pas une fin de chaîne : l'étendre détacherait la suite de la chaîne
+    /// - [`Fat32Error::DiskFull`] s'il y a moins de `additional` clusters
+    ///   libres, sans rien modifier
+    /// - les erreurs d'écriture du secteur FSInfo : les nouveaux clusters
+    ///   sont alors libérés et `last_cluster` redevient la fin de la chaîne
+    pub fn extend_chain(&self, last_cluster: u32, additional: u32) -> Result<u32> {
+        self.check_writable()?;
+        if !self.read_fat_entry(last_cluster)?.is_end() {
+            return Err(Fat32Error::InvalidCluster(last_cluster));
+        }
+        if additional == 0 {
+            return Ok(last_cluster);
+        }
+
+        let (first, last) = self.claim_clusters(last_cluster + 1, additional)?;
+        if let Err(err) = self.write_fat_entry(last_cluster, FatEntry::set_next(first)) {
+            let _ = self.release_chain(first);
+            return Err(err);
+        }
+        if let Err(err) = self.update_fsinfo(-(additional as i64), Some(self.next_free_after(last))) {
+            // La chaîne retrouve sa fin d'origine
+            let _ = self.write_fat_entry(last_cluster, FatEntry::end_of_chain());
+            let _ = self.release_chain(first);
+            return Err(err);
+        }
+        Ok(last)
+    }
+
+    // Réserve `count` clusters libres chaînés entre eux en cherchant à partir
+    // de `hint`, et retourne le premier et le dernier. En cas d'échec, les
+    // clusters déjà réservés sont rendus avant de retourner l'erreur.
+    fn claim_clusters(&self, hint: u32, count: u32) -> Result<(u32, u32)> {
+        let mut first = 0;
+        let mut last = 0;
+        let mut hint = hint;
+
+        for _ in 0..count {
+            let claimed = self.find_free_cluster(hint).and_then(|free| {
+                let cluster = free.ok_or(Fat32Error::DiskFull)?;
+                self.write_fat_entry(cluster, FatEntry::end_of_chain())?;
+                if last != 0 {
+                    if let Err(err) = self.write_fat_entry(last, FatEntry::set_next(cluster)) {
+                        let _ = self.write_fat_entry(cluster, FatEntry::free());
+                        return Err(err);
                     }
-                    None => {
-                        self.done = true;
-                        return Ok(None);
+                }
+                Ok(cluster)
+            });
+
+            match claimed {
+                Ok(cluster) => {
+                    if first == 0 {
+                        first = cluster;
+                    }
+                    last = cluster;
+                    hint = cluster + 1;
+                }
+                Err(err) => {
+                    if first != 0 {
+                        // Meilleur effort : l'erreur d'origine est plus utile
+                        let _ = self.release_chain(first);
                     }
+                    return Err(err);
                 }
             }
+        }
+        Ok((first, last))
+    }
+
+    // Indice de prochain cluster libre à enregistrer après une allocation
+    // terminée par `last`.
+    fn next_free_after(&self, last: u32) -> u32 {
+        if last < self.geom.max_valid_cluster() {
+            last + 1
+        } else {
+            2
+        }
+    }
+
+    /// Lit le répertoire racine.
+    /// 
+    /// # Returns
+    /// 
+    /// Un itérateur sur les entrées de répertoire du root.
+    pub fn read_root_dir(&self) -> Result<DirectoryIterator<'_, 'a, D>> {
+        DirectoryIterator::new(self, self.geom.root_cluster)
+    }
+
+    /// Lit le répertoire qui commence au cluster `cluster`.
+    ///
+    /// Le cluster 0 (valeur stockée dans `..` quand le parent est la racine)
+    /// désigne le répertoire racine.
+    pub fn read_dir(&self, cluster: u32) -> Result<DirectoryIterator<'_, 'a, D>> {
+        if cluster == 0 {
+            return self.read_root_dir();
+        }
+        DirectoryIterator::new(self, cluster)
+    }
+
+    /// Itérateur sur toutes les entrées de 32 octets du répertoire
+    /// `cluster` (0 pour la racine), sans filtrage.
+    ///
+    /// Contrairement à [`Fat32Fs::read_dir`], les entrées supprimées, les
+    /// fragments LFN, le label de volume et `.`/`..` sont retournés tels
+    /// quels, avec leur emplacement : utile pour diagnostiquer la
+    /// reconstruction des noms longs ou retrouver des fichiers supprimés. Le
+    /// parcours s'arrête à la première entrée `0x00`, ou continue jusqu'à la
+    /// fin de la chaîne avec [`RawDirIter::include_unused`].
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::read_dir`].
+    pub fn raw_entries(&self, cluster: u32) -> Result<RawDirIter<'_, 'a, D>> {
+        let start = if cluster == 0 { self.geom.root_cluster } else { cluster };
+        Ok(RawDirIter {
+            inner: DirectoryIterator::new(self, start)?,
+        })
+    }
+
+    /// Retourne le nom du volume (volume label).
+    ///
+    /// Cherche d'abord l'entrée portant l'attribut volume dans le répertoire
+    /// racine, puis, à défaut, le champ `BS_VolLab` du secteur de boot (s'il
+    /// est présent et différent de `"NO NAME"`). Les espaces de fin sont
+    /// remplacés par des octets nuls.
+    ///
+    /// Retourne `None` si le volume n'a pas de nom.
+    pub fn volume_label(&self) -> Result<Option<[u8; 11]>> {
+        let mut dir = self.read_root_dir()?;
+        while let Some(offset) = dir.next_slot()? {
+            let raw = DirectoryEntryRaw::parse(&dir.slot(offset));
+            let attributes = raw.attributes();
+            if raw.name[0] != 0xE5 && attributes.is_volume_id() && !attributes.is_lfn() {
+                return Ok(Some(trim_label(raw.name)));
+            }
+        }
+
+        // Repli sur le secteur de boot : le label n'est valide qu'avec la
+        // signature de boot étendue (0x29)
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let boot_sector = self.sector_buf(&mut buf)?;
+        self.read_sectors(0, 1, boot_sector)?;
+        Ok(BootSector::parse(boot_sector)
+            .and_then(|boot| boot.label())
+            .map(trim_label))
+    }
+
+    /// Ouvre un fichier à partir de son chemin (ex: `"/docs/readme.txt"`).
+    ///
+    /// Les composants sont séparés par `/` et résolus depuis la racine ; un
+    /// `/` initial est facultatif. Chaque composant est cherché avec
+    /// [`Fat32Fs::find_in_dir`].
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::InvalidPath`] si un composant est vide, `.` ou `..`
+    /// - [`Fat32Error::NotFound`] si un composant n'existe pas
+    /// - [`Fat32Error::IsNotDirectory`] si un composant intermédiaire est un fichier
+    /// - [`Fat32Error::IsDirectory`] si le dernier composant est un dossier
+    pub fn open(&self, path: &str) -> Result<File> {
+        let (entry, span) = self.resolve_path(path)?;
+        if entry.is_dir() {
+            return Err(Fat32Error::IsDirectory);
+        }
+        Ok(File::with_entry(entry.first_cluster(), entry.file_size(), span.short))
+    }
+
+    /// Vérifie si `path` désigne un fichier ou un répertoire existant.
+    ///
+    /// `/` (la racine) existe toujours.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `Ok(false)` si le chemin n'existe pas, mais les autres
+    /// erreurs (chemin invalide, composant intermédiaire qui n'est pas un
+    /// répertoire, erreur de lecture) sont propagées.
+    pub fn exists(&self, path: &str) -> Result<bool> {
+        match self.metadata(path) {
+            Ok(_) => Ok(true),
+            Err(Fat32Error::NotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Informations sur le fichier ou le répertoire `path` (taille, premier
+    /// cluster, attributs, dates).
+    ///
+    /// La racine `/` n'a pas d'entrée de répertoire : ses dates valent
+    /// [`DOS_EPOCH`] et son premier cluster est `root_cluster`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::open`], sauf [`Fat32Error::IsDirectory`].
+    pub fn metadata(&self, path: &str) -> Result<Metadata> {
+        if path == "/" {
+            return Ok(Metadata {
+                is_dir: true,
+                size: 0,
+                first_cluster: self.geom.root_cluster,
+                attributes: Attributes(attr::DIRECTORY),
+                created: DOS_EPOCH,
+                modified: DOS_EPOCH,
+                accessed: DOS_EPOCH,
+            });
+        }
+        let (entry, _) = self.resolve_path(path)?;
+        Ok(Metadata::from_entry(&entry))
+    }
+
+    // Résout un chemin absolu ou relatif à la racine en son entrée de
+    // répertoire. Tous les composants sauf le dernier doivent être des
+    // répertoires.
+    fn resolve_path(&self, path: &str) -> Result<(DirEntry, EntrySpan)> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let mut components = path.split('/').peekable();
+        let mut dir_cluster = self.geom.root_cluster;
+
+        while let Some(component) = components.next() {
+            if component.is_empty() || component == "." || component == ".." {
+                return Err(Fat32Error::InvalidPath);
+            }
+
+            let (entry, span) = self
+                .find_entry(dir_cluster, component)?
+                .ok_or(Fat32Error::NotFound)?;
+
+            if components.peek().is_none() {
+                return Ok((entry, span));
+            }
+
+            if !entry.is_dir() {
+                return Err(Fat32Error::IsNotDirectory);
+            }
+            dir_cluster = entry.first_cluster();
+        }
+
+        Err(Fat32Error::InvalidPath)
+    }
+
+    /// Lit le contenu complet du fichier `path` dans un `Vec`.
+    ///
+    /// Le vecteur est alloué d'emblée à la taille du fichier ; un fichier vide
+    /// donne un vecteur vide.
+    ///
+    /// # Errors
+    ///
+    /// Mêmes erreurs que [`Fat32Fs::open`] (dont [`Fat32Error::IsDirectory`]
+    /// si `path` désigne un répertoire), ainsi que les erreurs de lecture de
+    /// la chaîne de clusters. Un fichier dont la chaîne contient moins
+    /// d'octets que sa taille (ou qui n'a pas de premier cluster) donne
+    /// [`Fat32Error::CorruptedChain`] plutôt qu'un vecteur tronqué.
+    #[cfg(any(test, feature = "alloc"))]
+    pub fn read_file_to_vec(&self, path: &str) -> Result<Vec<u8>> {
+        let file = self.open(path)?;
+        let mut data = Vec::with_capacity(file.size() as usize);
+        self.read_file_chain(file.first_cluster(), file.size(), |_, bytes| {
+            data.extend_from_slice(bytes);
+            Ok(())
+        })?;
+        Ok(data)
+    }
+
+    /// Retourne le cluster du répertoire parent de `dir_cluster`, lu dans son
+    /// entrée `..` (`None` pour la racine, qui n'en a pas).
+    ///
+    /// Une entrée `..` à 0 désigne la racine : le cluster racine du volume
+    /// est alors retourné.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::CorruptedDirectory`] si un sous-répertoire n'a
+    /// pas d'entrée `..`, ou une erreur de lecture.
+    pub fn parent_cluster(&self, dir_cluster: u32) -> Result<Option<u32>> {
+        if dir_cluster == 0 || dir_cluster == self.geom.root_cluster {
+            return Ok(None);
+        }
+
+        let mut dir = self.read_dir(dir_cluster)?.include_dot_entries(true);
+        while let Some(entry) = dir.next_entry()? {
+            if entry.name == DOTDOT_NAME {
+                return Ok(Some(match entry.first_cluster() {
+                    0 => self.geom.root_cluster,
+                    cluster => cluster,
+                }));
+            }
+        }
+        Err(Fat32Error::CorruptedDirectory)
+    }
+
+    /// Parcourt récursivement le répertoire `start_cluster` (0 pour la racine)
+    /// et ses sous-répertoires, en appelant `visit` pour chaque entrée.
+    ///
+    /// `visit` reçoit le chemin des répertoires parents (relatif au
+    /// répertoire de départ, vide pour ses entrées directes) et l'entrée ; sa
+    /// valeur de retour permet de continuer, d'ignorer le contenu d'un
+    /// sous-répertoire ou d'arrêter le parcours. Comme pour
+    /// [`DirectoryIterator`], les entrées `.` et `..` ne sont pas visitées.
+    ///
+    /// La descente s'arrête à `depth_limit` niveaux sous le répertoire de
+    /// départ (0 : seulement ses entrées directes), et au plus à
+    /// [`MAX_WALK_DEPTH`]. La limite protège aussi contre les répertoires qui
+    /// se contiennent eux-mêmes.
+    ///
+    /// Le parcours n'est pas récursif et n'alloue rien : un seul répertoire
+    /// est lu à la fois, et chaque parent ne garde sur la pile que la
+    /// position où reprendre sa lecture. Les noms du chemin courant sont
+    /// empilés dans un buffer unique de [`MAX_WALK_PATH`] octets (voir
+    /// [`WalkPath`]).
+    ///
+    /// # Errors
+    ///
+    /// Retourne la première erreur de lecture, ou celle renvoyée par `visit`,
+    /// et [`Fat32Error::NameTooLong`] si les noms des répertoires parents ne
+    /// tiennent pas dans [`MAX_WALK_PATH`] octets.
+    ///
+    /// # Exemple
+    ///
+    /// ```no_run
+    /// use fat32_parser::{BlockDevice, Fat32Fs};
+    /// use fat32_parser::filesystem::WalkAction;
+    ///
+    /// // Taille cumulée de tous les fichiers du volume
+    /// fn total_size<D: BlockDevice>(fs: &Fat32Fs<'_, D>) -> fat32_parser::Result<u64> {
+    ///     let mut total = 0u64;
+    ///     fs.walk(0, 8, |_path, entry| {
+    ///         total += entry.file_size() as u64;
+    ///         Ok(WalkAction::Continue)
+    ///     })?;
+    ///     Ok(total)
+    /// }
+    /// ```
+    pub fn walk<F>(&self, start_cluster: u32, depth_limit: u32, mut visit: F) -> Result<()>
+    where
+        F: FnMut(&WalkPath, &DirEntry) -> Result<WalkAction>,
+    {
+        let limit = (depth_limit as usize).min(MAX_WALK_DEPTH);
+        let mut path = WalkPath::new();
+        // Position de l'entrée qui suit, dans chaque parent, le sous-répertoire
+        // en cours de parcours
+        let mut resume = [EntryPosition::default(); MAX_WALK_DEPTH];
+        let mut dir = self.read_dir(start_cluster)?;
+
+        loop {
+            let Some(entry) = dir.next_file()? else {
+                // Fin du répertoire : reprise du parent
+                if !path.pop() {
+                    return Ok(());
+                }
+                dir = DirectoryIterator::resume(self, resume[path.len()])?;
+                continue;
+            };
+
+            match visit(&path, &entry)? {
+                WalkAction::Stop => return Ok(()),
+                WalkAction::SkipDir => continue,
+                WalkAction::Continue => {}
+            }
+
+            // Un sous-répertoire au cluster 0 désignerait la racine : ignoré
+            if entry.is_dir() && path.len() < limit && entry.first_cluster() != 0 {
+                let short = dir.last_span.short;
+                resume[path.len()] = EntryPosition {
+                    cluster: short.cluster,
+                    offset: short.offset + 32,
+                };
+                path.push(entry.name(), entry.first_cluster())?;
+                dir = self.read_dir(entry.first_cluster())?;
+            }
+        }
+    }
+
+    /// Taille cumulée, en octets, des fichiers du répertoire `dir_cluster`
+    /// (0 pour la racine) et de ses sous-répertoires, comme `du`.
+    ///
+    /// Le parcours utilise [`Fat32Fs::walk`] : les entrées `.` et `..` sont
+    /// ignorées et la descente s'arrête à `depth_limit` niveaux. Seule la
+    /// taille déclarée des fichiers est comptée, pas les clusters occupés
+    /// par les répertoires.
+    ///
+    /// # Errors
+    ///
+    /// Retourne la première erreur de lecture d'un répertoire.
+    pub fn dir_size(&self, dir_cluster: u32, depth_limit: u32) -> Result<u64> {
+        let mut total = 0u64;
+        self.walk(dir_cluster, depth_limit, |_path, entry| {
+            if !entry.is_dir() {
+                total += entry.file_size() as u64;
+            }
+            Ok(WalkAction::Continue)
+        })?;
+        Ok(total)
+    }
+
+    /// Cherche l'entrée `name` dans le répertoire `dir_cluster` (0 pour la
+    /// racine), sans descendre dans les sous-répertoires.
+    ///
+    /// `name` est comparé au nom long et au nom court 8.3 de chaque entrée,
+    /// sans tenir compte de la casse (ASCII et Latin-1, voir
+    /// [`DirEntry::matches_name`]) : `"README.TXT"` trouve un fichier dont le
+    /// nom long est `"ReadMe.txt"`. La recherche s'arrête à la
+    /// première correspondance.
+    ///
+    /// Retourne `None` si aucune entrée ne correspond.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture du répertoire échoue ou s'il est
+    /// corrompu.
+    pub fn find_in_dir(&self, dir_cluster: u32, name: &str) -> Result<Option<DirEntry>> {
+        Ok(self.find_entry(dir_cluster, name)?.map(|(entry, _)| entry))
+    }
+
+    /// Cherche dans le répertoire `dir_cluster` les entrées dont le nom
+    /// correspond au motif `pattern` (`*` et `?`, sans tenir compte de la
+    /// casse, voir [`wildcard_match`]), comme `ls *.txt`.
+    ///
+    /// Les correspondances sont copiées dans `out` dans l'ordre du
+    /// répertoire, sans allocation. Retourne le nombre total d'entrées
+    /// correspondantes : s'il dépasse `out.len()`, seules les `out.len()`
+    /// premières ont été copiées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture du répertoire échoue.
+    pub fn find_matching(&self, dir_cluster: u32, pattern: &str, out: &mut [DirEntry]) -> Result<usize> {
+        let mut count = 0;
+        let mut dir = self.read_dir(dir_cluster)?;
+        while let Some(entry) = dir.next_file()? {
+            if wildcard_match(pattern, entry.name()) {
+                if let Some(slot) = out.get_mut(count) {
+                    *slot = entry;
+                }
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Cherche dans le répertoire `dir_cluster` (0 pour la racine) les
+    /// entrées supprimées (`0xE5`), candidates à une récupération.
+    ///
+    /// La suppression écrase le premier octet du nom 8.3 et le numéro de
+    /// séquence des fragments LFN, mais pas les caractères du nom long :
+    /// quand des fragments supprimés précèdent l'entrée, le nom long est
+    /// reconstruit et la somme de contrôle qu'ils portent redonne le premier
+    /// caractère du nom 8.3. Sinon, ce caractère reste inconnu.
+    ///
+    /// Les candidates sont copiées dans `out` dans l'ordre du répertoire,
+    /// sans allocation ; le nombre total est retourné, comme avec
+    /// [`Fat32Fs::find_matching`]. Le volume n'est pas modifié.
+    ///
+    /// Le premier cluster et la taille sont ceux enregistrés dans l'entrée :
+    /// la suppression a libéré la chaîne dans la FAT, si bien que seules les
+    /// données d'un fichier contigu dont les clusters n'ont pas été réutilisés
+    /// depuis peuvent être récupérées.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::raw_entries`].
+    pub fn undelete_candidates(&self, dir_cluster: u32, out: &mut [DeletedEntry]) -> Result<usize> {
+        let mut count = 0;
+        // Fragments LFN supprimés qui précèdent l'entrée courante, dans
+        // l'ordre du disque (dernière partie du nom en premier)
+        let mut fragments = [[0u16; LFN_CHARS_PER_ENTRY]; LFN_MAX_ENTRIES];
+        let mut pending = 0;
+        let mut checksum = 0;
+        let mut first = EntryPosition::default();
+
+        for slot in self.raw_entries(dir_cluster)? {
+            let slot = slot?;
+            let bytes = slot.entry.to_bytes();
+            let position = EntryPosition {
+                cluster: slot.cluster,
+                offset: slot.offset,
+            };
+            if bytes[0] != DELETED_ENTRY {
+                pending = 0;
+                continue;
+            }
+            let attributes = slot.entry.attributes();
+            if attributes.is_lfn() {
+                if pending == LFN_MAX_ENTRIES || (pending != 0 && bytes[13] != checksum) {
+                    pending = 0;
+                }
+                if pending == 0 {
+                    checksum = bytes[13];
+                    first = position;
+                }
+                fragments[pending] = lfn_units(&bytes);
+                pending += 1;
+                continue;
+            }
+            if attributes.is_volume_id() {
+                pending = 0;
+                continue;
+            }
+
+            // La somme de contrôle dépend de façon bijective du premier
+            // octet : une seule valeur convient, à garder si elle est valide
+            let mut raw = slot.entry;
+            let recovered = match pending {
+                0 => None,
+                _ => (0..=u8::MAX)
+                    .find(|&b| {
+                        raw.name[0] = b;
+                        lfn_checksum(&raw.name) == checksum
+                    })
+                    .filter(|&b| is_short_name_char(b)),
+            };
+            raw.name[0] = recovered.unwrap_or(b'_');
+
+            let mut units = [0u16; LFN_MAX_ENTRIES * LFN_CHARS_PER_ENTRY];
+            let chunks = units.chunks_exact_mut(LFN_CHARS_PER_ENTRY);
+            let ordered = fragments[..pending].iter().rev();
+            for (chunk, fragment) in chunks.zip(ordered) {
+                chunk.copy_from_slice(fragment);
+            }
+            let (long_name, span) = match recovered {
+                Some(_) => (
+                    Some(&units[..pending * LFN_CHARS_PER_ENTRY]),
+                    EntrySpan {
+                        first,
+                        slots: pending as u32 + 1,
+                        short: position,
+                    },
+                ),
+                None => (
+                    None,
+                    EntrySpan {
+                        first: position,
+                        slots: 1,
+                        short: position,
+                    },
+                ),
+            };
+
+            if let Some(candidate) = out.get_mut(count) {
+                *candidate = DeletedEntry {
+                    entry: DirEntry::new(raw, long_name),
+                    first_char_recovered: recovered.is_some(),
+                    location: EntryLocation::from(span),
+                };
+            }
+            count += 1;
+            pending = 0;
+        }
+        Ok(count)
+    }
+
+    // Comme `find_in_dir`, en retournant aussi l'emplacement des entrées.
+    fn find_entry(&self, dir_cluster: u32, name: &str) -> Result<Option<(DirEntry, EntrySpan)>> {
+        let mut dir = self.read_dir(dir_cluster)?;
+        while let Some(entry) = dir.next_file()? {
+            if entry.matches_name(name) {
+                return Ok(Some((entry, dir.last_span)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Cherche l'entrée `name` dans le répertoire `dir_cluster` (0 pour la
+    /// racine) comme [`Fat32Fs::find_in_dir`], et retourne son emplacement
+    /// sur le disque : entrée 8.3 et fragments LFN qui la précèdent.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::find_in_dir`].
+    pub fn locate(&self, dir_cluster: u32, name: &str) -> Result<Option<EntryLocation>> {
+        Ok(self
+            .find_entry(dir_cluster, name)?
+            .map(|(_, span)| EntryLocation::from(span)))
+    }
+
+    /// Supprime l'entrée `name` du répertoire `dir_cluster` (0 pour la racine).
+    ///
+    /// Le premier octet de l'entrée 8.3 et de chacun de ses fragments LFN est
+    /// remplacé par `0xE5`, sans déplacer les autres entrées. Seuls les
+    /// secteurs concernés du répertoire sont réécrits.
+    ///
+    /// La chaîne de clusters de l'entrée n'est pas libérée : retrouver son
+    /// premier cluster avec [`Fat32Fs::find_in_dir`] avant la suppression, puis
+    /// appeler [`Fat32Fs::free_chain`].
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::NotFound`] si aucune entrée ne correspond à `name`
+    /// - [`Fat32Error::DirectoryNotEmpty`] si l'entrée est un répertoire qui
+    ///   contient autre chose que `.` et `..`
+    pub fn delete_entry(&self, dir_cluster: u32, name: &str) -> Result<()> {
+        self.check_writable()?;
+        let (entry, span) = self
+            .find_entry(dir_cluster, name)?
+            .ok_or(Fat32Error::NotFound)?;
+
+        if entry.is_dir() && !self.is_dir_empty(entry.first_cluster())? {
+            return Err(Fat32Error::DirectoryNotEmpty);
+        }
+
+        self.mark_slots_deleted(span)
+    }
+
+    /// Renomme l'entrée `old` du répertoire `dir_cluster` en `new`, dans le
+    /// même répertoire.
+    ///
+    /// De nouvelles entrées (fragments LFN et entrée 8.3, avec un nom court
+    /// régénéré si nécessaire) sont écrites pour `new`, avec les attributs,
+    /// les dates, le premier cluster et la taille de `old` : les données ne
+    /// sont ni copiées ni déplacées. Les entrées de `old` sont ensuite
+    /// marquées comme supprimées. Changer seulement la casse d'un nom
+    /// (`readme.txt` en `README.TXT`) est permis.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::NotFound`] si aucune entrée ne correspond à `old`
+    /// - [`Fat32Error::AlreadyExists`] si une autre entrée s'appelle déjà `new`
+    /// - les erreurs de [`Fat32Fs::create_file`] pour le nouveau nom
+    pub fn rename(&self, dir_cluster: u32, old: &str, new: &str) -> Result<()> {
+        self.check_writable()?;
+        let (entry, span) = self
+            .find_entry(dir_cluster, old)?
+            .ok_or(Fat32Error::NotFound)?;
+
+        let template = entry.raw().to_bytes();
+        self.insert_entry(dir_cluster, new, template, Some(span.short))?;
+        self.mark_slots_deleted(span)
+    }
+
+    /// Déplace l'entrée `name` du répertoire `src_dir` vers le répertoire
+    /// `dst_dir` sous le nom `new_name` (0 désigne la racine pour les deux).
+    ///
+    /// Seules les entrées de répertoire changent : la nouvelle entrée pointe
+    /// vers la même chaîne de clusters, avec la même taille, les mêmes
+    /// attributs et les mêmes dates. Pour un répertoire, l'entrée `..` est
+    /// mise à jour vers `dst_dir` ; si cette écriture échoue ou si la
+    /// deuxième entrée du répertoire n'est pas `..`, la nouvelle entrée est
+    /// retirée. L'entrée source est supprimée en dernier ; dans un
+    /// même répertoire, le déplacement est un [`Fat32Fs::rename`].
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::NotFound`] si `name` n'existe pas dans `src_dir`
+    /// - [`Fat32Error::AlreadyExists`] si `new_name` existe déjà dans `dst_dir`
+    /// - [`Fat32Error::InvalidPath`] si un répertoire serait déplacé dans
+    ///   lui-même ou dans l'un de ses descendants
+    /// - [`Fat32Error::CorruptedDirectory`] si le répertoire déplacé n'a pas
+    ///   d'entrée `..` en deuxième position
+    pub fn move_entry(&self, src_dir: u32, name: &str, dst_dir: u32, new_name: &str) -> Result<()> {
+        self.check_writable()?;
+        let root = self.geom.root_cluster;
+        let src_dir = if src_dir == 0 { root } else { src_dir };
+        let dst_dir = if dst_dir == 0 { root } else { dst_dir };
+        if src_dir == dst_dir {
+            return self.rename(src_dir, name, new_name);
+        }
+
+        let (entry, span) = self.find_entry(src_dir, name)?.ok_or(Fat32Error::NotFound)?;
+        let moved_dir = entry.first_cluster_opt().filter(|_| entry.is_dir());
+        if let Some(cluster) = moved_dir {
+            if self.is_same_or_descendant(dst_dir, cluster)? {
+                return Err(Fat32Error::InvalidPath);
+            }
+        }
+
+        let (_, inserted) = self.insert_entry(dst_dir, new_name, entry.raw().to_bytes(), None)?;
+        if let Some(cluster) = moved_dir {
+            // `..` vaut 0 quand le parent est la racine
+            let parent = if dst_dir == root { 0 } else { dst_dir };
+            let dotdot = EntryPosition { cluster, offset: 32 };
+            let mut found = false;
+            let updated = self.update_slots(dotdot, 1, |_, slot| {
+                if slot[..11] == DOTDOT_NAME {
+                    slot[20..22].copy_from_slice(&((parent >> 16) as u16).to_le_bytes());
+                    slot[26..28].copy_from_slice(&(parent as u16).to_le_bytes());
+                    found = true;
+                }
+            });
+            let updated = match updated {
+                Ok(()) if !found => Err(Fat32Error::CorruptedDirectory),
+                other => other,
+            };
+            if let Err(err) = updated {
+                // Le répertoire ne doit pas rester référencé depuis deux parents
+                let _ = self.mark_slots_deleted(inserted);
+                return Err(err);
+            }
+        }
+        self.mark_slots_deleted(span)
+    }
+
+    /// Réduit le fichier `name` du répertoire `dir_cluster` à `new_len`
+    /// octets.
+    ///
+    /// La taille de l'entrée est mise à jour en premier. Le cluster qui
+    /// contient le dernier octet conservé devient la fin de la chaîne et les
+    /// clusters suivants sont libérés ; une taille nulle libère toute la
+    /// chaîne et remet le premier cluster à 0. Le secteur FSInfo est mis à
+    /// jour comme avec [`Fat32Fs::free_chain`]. Les données conservées ne
+    /// sont pas modifiées, et une taille égale à la taille actuelle ne change
+    /// rien.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::NotFound`] si `name` n'existe pas
+    /// - [`Fat32Error::IsDirectory`] si `name` est un répertoire
+    /// - [`Fat32Error::Unsupported`] si `new_len` dépasse la taille actuelle
+    ///   (agrandir un fichier n'est pas pris en charge)
+    /// - [`Fat32Error::CorruptedChain`] si la chaîne est plus courte que la
+    ///   taille du fichier
+    pub fn truncate(&self, dir_cluster: u32, name: &str, new_len: u32) -> Result<()> {
+        self.check_writable()?;
+        let (entry, span) = self.find_entry(dir_cluster, name)?.ok_or(Fat32Error::NotFound)?;
+        if entry.is_dir() {
+            return Err(Fat32Error::IsDirectory);
+        }
+        let size = entry.file_size();
+        if new_len > size {
+            return Err(Fat32Error::Unsupported);
+        }
+        if new_len == size {
+            return Ok(());
+        }
+
+        let Some(first) = entry.first_cluster_opt() else {
+            return self.update_entry_metadata(span.short, 0, new_len);
+        };
+        if new_len == 0 {
+            // L'entrée d'abord, comme pour une suppression
+            self.update_entry_metadata(span.short, 0, 0)?;
+            return self.free_chain(first).map(|_| ());
+        }
+
+        let kept = new_len.div_ceil(self.geom.cluster_size_bytes());
+        let mut last = first;
+        for _ in 1..kept {
+            last = self
+                .read_fat_entry(last)?
+                .next_cluster()
+                .ok_or(Fat32Error::CorruptedChain)?;
+        }
+        self.update_entry_metadata(span.short, first, new_len)?;
+        if let Some(rest) = self.read_fat_entry(last)?.next_cluster() {
+            self.write_fat_entry(last, FatEntry::end_of_chain())?;
+            self.free_chain(rest)?;
+        }
+        Ok(())
+    }
+
+    /// Compacte le répertoire `dir_cluster` (0 pour la racine) en retirant
+    /// les entrées supprimées (`0xE5`), et retourne le nombre de clusters
+    /// libérés.
+    ///
+    /// Les entrées restantes (dont `.`, `..` et le label de volume) sont
+    /// réécrites dans le même ordre, sans trou depuis le début du
+    /// répertoire : les fragments LFN restent donc juste avant leur entrée
+    /// 8.3. La fin du dernier cluster utilisé est remplie de zéros, et les
+    /// clusters suivants, devenus vides, sont retirés de la chaîne du
+    /// répertoire et libérés comme avec [`Fat32Fs::free_chain`]. Le premier
+    /// cluster est toujours conservé.
+    ///
+    /// Le répertoire est réécrit en place, secteur par secteur : une
+    /// interruption peut le laisser incohérent.
+    ///
+    /// Les entrées changent d'emplacement, ce que le système de fichiers ne
+    /// peut pas signaler aux [`File`] ouverts : un fichier de ce répertoire
+    /// ouvert avant le compactage garde l'ancien emplacement de son entrée,
+    /// et [`File::write`] mettrait ensuite à jour une autre entrée. Aucun
+    /// fichier du répertoire ne doit donc être ouvert pendant l'appel ; il
+    /// faut le rouvrir avec [`Fat32Fs::open`] après. Il en va de même pour
+    /// les [`EntryLocation`] obtenues auparavant.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - les erreurs de [`Fat32Fs::cluster_chain`] pour la chaîne du
+    ///   répertoire, et les erreurs de lecture ou d'écriture
+    pub fn compact_dir(&self, dir_cluster: u32) -> Result<u32> {
+        self.check_writable()?;
+        let start = if dir_cluster == 0 { self.geom.root_cluster } else { dir_cluster };
+        let slots_per_sector = self.geom.bytes_per_sector / 32;
+        let slots_per_cluster = slots_per_sector * self.geom.sectors_per_cluster;
+
+        let mut read_buf = [0u8; MAX_SECTOR_SIZE];
+        let read = self.sector_buf(&mut read_buf)?;
+        let mut write_buf = [0u8; MAX_SECTOR_SIZE];
+        let write = self.sector_buf(&mut write_buf)?;
+
+        // Curseur d'écriture : jamais en avance sur la lecture, un secteur
+        // n'est donc écrit qu'une fois entièrement lu
+        let mut write_cluster = Some(start);
+        let mut write_sector = 0;
+        let mut write_slot = 0;
+        let mut kept = 0;
+
+        'chain: for cluster in self.cluster_chain(start) {
+            let lba = self.geom.cluster_to_lba(cluster?)?;
+            for sector_index in 0..self.geom.sectors_per_cluster {
+                self.read_sectors(lba + sector_index, 1, read)?;
+                for slot in read.chunks_exact(32) {
+                    match slot[0] {
+                        0x00 => break 'chain,
+                        DELETED_ENTRY => continue,
+                        _ => {}
+                    }
+                    let offset = (write_slot * 32) as usize;
+                    write[offset..offset + 32].copy_from_slice(slot);
+                    write_slot += 1;
+                    kept += 1;
+                    if write_slot < slots_per_sector {
+                        continue;
+                    }
+
+                    let target = write_cluster.ok_or(Fat32Error::CorruptedDirectory)?;
+                    self.write_sectors(self.geom.cluster_to_lba(target)? + write_sector, 1, write)?;
+                    write.fill(0);
+                    write_slot = 0;
+                    write_sector += 1;
+                    if write_sector == self.geom.sectors_per_cluster {
+                        write_sector = 0;
+                        write_cluster = self.read_fat_entry(target)?.next_cluster();
+                    }
+                }
+            }
+        }
+
+        // Fin du dernier cluster conservé : secteur en cours, puis zéros
+        if kept == 0 || kept % slots_per_cluster != 0 {
+            let target = write_cluster.ok_or(Fat32Error::CorruptedDirectory)?;
+            let lba = self.geom.cluster_to_lba(target)?;
+            self.write_sectors(lba + write_sector, 1, write)?;
+            write.fill(0);
+            for sector_index in write_sector + 1..self.geom.sectors_per_cluster {
+                self.write_sectors(lba + sector_index, 1, write)?;
+            }
+        }
+
+        let mut last = start;
+        for _ in 1..kept.div_ceil(slots_per_cluster) {
+            last = self
+                .read_fat_entry(last)?
+                .next_cluster()
+                .ok_or(Fat32Error::CorruptedDirectory)?;
+        }
+        match self.read_fat_entry(last)?.next_cluster() {
+            Some(rest) => {
+                self.write_fat_entry(last, FatEntry::end_of_chain())?;
+                self.free_chain(rest)
+            }
+            None => Ok(0),
+        }
+    }
+
+    // True si le répertoire `dir_cluster` est `ancestor` ou l'un de ses
+    // descendants, en remontant les entrées `..` jusqu'à la racine.
+    fn is_same_or_descendant(&self, dir_cluster: u32, ancestor: u32) -> Result<bool> {
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+        let mut current = dir_cluster;
+
+        // Une boucle de `..` ne peut pas dépasser le nombre de clusters
+        for _ in 0..=self.geom.total_clusters() {
+            if current == ancestor {
+                return Ok(true);
+            }
+            if current == 0 || current == self.geom.root_cluster {
+                return Ok(false);
+            }
+            self.read_sectors(self.geom.cluster_to_lba(current)?, 1, sector)?;
+            let mut slot = [0u8; 32];
+            slot.copy_from_slice(&sector[32..64]);
+            let dotdot = DirectoryEntryRaw::parse(&slot);
+            if dotdot.name != DOTDOT_NAME {
+                return Err(Fat32Error::CorruptedDirectory);
+            }
+            current = dotdot.first_cluster();
+        }
+        Err(Fat32Error::CorruptedDirectory)
+    }
+
+    /// Remplace les attributs modifiables (lecture seule, caché, système,
+    /// archive) de l'entrée `name` du répertoire `dir_cluster` par ceux de
+    /// `attrs`.
+    ///
+    /// Les bits [`attr::DIRECTORY`] et [`attr::VOLUME_ID`] (et les bits
+    /// réservés) de l'entrée sont toujours conservés, et ignorés dans
+    /// `attrs` : un fichier ne peut pas devenir un répertoire, ni l'inverse.
+    /// Pour ne changer qu'un attribut, utiliser [`Fat32Fs::set_read_only`] et
+    /// les méthodes voisines.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::NotFound`] si aucune entrée ne correspond à `name`
+    pub fn set_attributes(&self, dir_cluster: u32, name: &str, attrs: Attributes) -> Result<()> {
+        self.update_attributes(dir_cluster, name, |old| {
+            (old & !MODIFIABLE_ATTRIBUTES) | (attrs.bits() & MODIFIABLE_ATTRIBUTES)
+        })
+    }
+
+    /// Pose ou retire l'attribut lecture seule de l'entrée `name`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::set_attributes`].
+    pub fn set_read_only(&self, dir_cluster: u32, name: &str, read_only: bool) -> Result<()> {
+        self.update_attributes(dir_cluster, name, |old| with_bit(old, attr::READ_ONLY, read_only))
+    }
+
+    /// Pose ou retire l'attribut caché de l'entrée `name`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::set_attributes`].
+    pub fn set_hidden(&self, dir_cluster: u32, name: &str, hidden: bool) -> Result<()> {
+        self.update_attributes(dir_cluster, name, |old| with_bit(old, attr::HIDDEN, hidden))
+    }
+
+    /// Pose ou retire l'attribut système de l'entrée `name`.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::set_attributes`].
+    pub fn set_system(&self, dir_cluster: u32, name: &str, system: bool) -> Result<()> {
+        self.update_attributes(dir_cluster, name, |old| with_bit(old, attr::SYSTEM, system))
+    }
+
+    /// Pose ou retire l'attribut archive de l'entrée `name` (à retirer après
+    /// une sauvegarde, par exemple).
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::set_attributes`].
+    pub fn set_archive(&self, dir_cluster: u32, name: &str, archive: bool) -> Result<()> {
+        self.update_attributes(dir_cluster, name, |old| with_bit(old, attr::ARCHIVE, archive))
+    }
+
+    // Réécrit l'octet d'attributs de l'entrée 8.3 de `name` avec
+    // `update(ancienne valeur)`.
+    fn update_attributes<F>(&self, dir_cluster: u32, name: &str, update: F) -> Result<()>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        self.check_writable()?;
+        let (entry, span) = self
+            .find_entry(dir_cluster, name)?
+            .ok_or(Fat32Error::NotFound)?;
+
+        let value = update(entry.attributes().bits());
+        self.update_slots(span.short, 1, |_, slot| slot[11] = value)
+    }
+
+    /// Supprime le sous-répertoire vide `name` du répertoire `parent_cluster`
+    /// (0 pour la racine).
+    ///
+    /// Le répertoire ne doit contenir que `.` et `..` (les entrées supprimées
+    /// sont ignorées). Son entrée et ses fragments LFN sont marqués comme
+    /// supprimés dans le parent, puis sa chaîne de clusters est libérée.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::NotFound`] si aucune entrée ne correspond à `name`
+    /// - [`Fat32Error::IsNotDirectory`] si l'entrée est un fichier
+    /// - [`Fat32Error::DirectoryNotEmpty`] si le répertoire contient d'autres entrées
+    pub fn remove_dir(&self, parent_cluster: u32, name: &str) -> Result<()> {
+        self.check_writable()?;
+        let (entry, span) = self
+            .find_entry(parent_cluster, name)?
+            .ok_or(Fat32Error::NotFound)?;
+        if !entry.is_dir() {
+            return Err(Fat32Error::IsNotDirectory);
+        }
+        if !self.is_dir_empty(entry.first_cluster())? {
+            return Err(Fat32Error::DirectoryNotEmpty);
+        }
+
+        // L'entrée d'abord : en cas d'échec, des clusters perdus plutôt
+        // qu'une entrée qui pointe vers des clusters libres
+        self.mark_slots_deleted(span)?;
+        if let Some(cluster) = entry.first_cluster_opt() {
+            self.free_chain(cluster)?;
+        }
+        Ok(())
+    }
+
+    // True si le répertoire `cluster` ne contient que `.` et `..` (un
+    // cluster 0 désigne un répertoire sans données, considéré vide).
+    fn is_dir_empty(&self, cluster: u32) -> Result<bool> {
+        if cluster == 0 {
+            return Ok(true);
+        }
+        Ok(self.read_dir(cluster)?.next_file()?.is_none())
+    }
+
+    /// Crée un fichier vide `name` dans le répertoire `dir_cluster` (0 pour la
+    /// racine) et retourne son entrée.
+    ///
+    /// L'entrée est écrite dans le premier emplacement libre du répertoire
+    /// (entrée supprimée ou fin du répertoire), qui est agrandi d'un cluster
+    /// s'il est plein. Le fichier n'a aucun cluster alloué (premier cluster
+    /// 0, taille 0) et porte l'attribut archive ; ses dates de création, de
+    /// modification et d'accès sont celles de [`Fat32Fs::now`].
+    ///
+    /// Un nom qui tient en 8.3 (voir [`encode_short_name`]) est stocké dans
+    /// la seule entrée 8.3 : un nom en minuscules garde sa casse grâce aux
+    /// drapeaux de l'octet réservé. Les autres reçoivent un nom court généré
+    /// (voir [`generate_short_name`]) précédé de fragments LFN.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::ReadOnly`] si le volume est monté en lecture seule
+    /// - [`Fat32Error::InvalidPath`] si `name` est vide, vaut `.` ou `..`, ou
+    ///   contient un séparateur ou un caractère interdit
+    /// - [`Fat32Error::AlreadyExists`] si une entrée porte déjà ce nom
+    /// - [`Fat32Error::NameTooLong`] si le nom dépasse 255 caractères UTF-16
+    /// - [`Fat32Error::DiskFull`] s'il faut agrandir le répertoire et que le
+    ///   volume est plein
+    pub fn create_file(&self, dir_cluster: u32, name: &str) -> Result<DirEntry> {
+        self.create_entry(dir_cluster, name, attr::ARCHIVE, 0)
+            .map(|(entry, _)| entry)
+    }
+
+    // Écrit une nouvelle entrée `name` (fragments LFN compris) dans le
+    // répertoire `dir_cluster`, et retourne l'entrée et son emplacement.
+    fn create_entry(
+        &self,
+        dir_cluster: u32,
+        name: &str,
+        attributes: u8,
+        first_cluster: u32,
+    ) -> Result<(DirEntry, EntrySpan)> {
+        let template = self.new_short_entry(&[b' '; 11], attributes, first_cluster);
+        self.insert_entry(dir_cluster, name, template, None)
+    }
+
+    // Écrit dans le répertoire `dir_cluster` une entrée nommée `name` (et ses
+    // fragments LFN si nécessaire), copie de `template` hormis le nom court
+    // et les drapeaux de casse. `replacing` est l'entrée 8.3 que la nouvelle
+    // remplacera (renommage) : elle ne compte pas comme un doublon.
+    fn insert_entry(
+        &self,
+        dir_cluster: u32,
+        name: &str,
+        template: [u8; 32],
+        replacing: Option<EntryPosition>,
+    ) -> Result<(DirEntry, EntrySpan)> {
+        self.check_writable()?;
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(Fat32Error::InvalidPath);
+        }
+        if let Some((_, existing)) = self.find_entry(dir_cluster, name)? {
+            if Some(existing.short) != replacing {
+                return Err(Fat32Error::AlreadyExists);
+            }
+        }
+
+        let mut lfn = [[0u8; 32]; LFN_MAX_ENTRIES];
+        let mut slot = template;
+        let lfn_count = match encode_short_name(name) {
+            Some((short_name, case_flags)) => {
+                slot[..11].copy_from_slice(&short_name);
+                slot[12] = case_flags;
+                0
+            }
+            None => {
+                let short_name = generate_short_name(name, &mut self.read_dir(dir_cluster)?)?;
+                slot[..11].copy_from_slice(&short_name);
+                slot[12] = 0;
+                build_lfn_entries(name, &short_name, &mut lfn)?
+            }
+        };
+
+        let slots = lfn_count as u32 + 1;
+        let first = self.find_free_slots(dir_cluster, slots)?;
+        self.update_slots(first, slots, |index, dst| {
+            let index = index as usize;
+            dst.copy_from_slice(if index < lfn_count { &lfn[index] } else { &slot });
+        })?;
+        // L'entrée 8.3 est la dernière de la série
+        let short = self.slot_position(first, slots - 1)?;
+
+        let raw = DirectoryEntryRaw::parse(&slot);
+        let entry = if lfn_count != 0 {
+            let mut units = [0u16; LFN_MAX_ENTRIES * LFN_CHARS_PER_ENTRY];
+            for (dst, unit) in units.iter_mut().zip(name.encode_utf16()) {
+                *dst = unit;
+            }
+            DirEntry::new(raw, Some(&units))
+        } else {
+            DirEntry::new(raw, None)
+        };
+        Ok((entry, EntrySpan { first, slots, short }))
+    }
+
+    // Entrée 8.3 brute d'un fichier ou dossier qui vient d'être créé, datée
+    // de `now()` (création, modification et accès), de taille nulle.
+    fn new_short_entry(&self, short_name: &[u8; 11], attributes: u8, first_cluster: u32) -> [u8; 32] {
+        let (date, time, tenths) = self.now().to_dos();
+        let mut slot = [0u8; 32];
+        slot[..11].copy_from_slice(short_name);
+        slot[11] = attributes;
+        slot[13] = tenths;
+        slot[14..16].copy_from_slice(&time.to_le_bytes());
+        slot[16..18].copy_from_slice(&date.to_le_bytes());
+        slot[18..20].copy_from_slice(&date.to_le_bytes());
+        slot[20..22].copy_from_slice(&((first_cluster >> 16) as u16).to_le_bytes());
+        slot[22..24].copy_from_slice(&time.to_le_bytes());
+        slot[24..26].copy_from_slice(&date.to_le_bytes());
+        slot[26..28].copy_from_slice(&(first_cluster as u16).to_le_bytes());
+        slot
+    }
+
+    /// Crée le sous-répertoire vide `name` dans le répertoire `parent_cluster`
+    /// (0 pour la racine) et retourne son premier cluster.
+    ///
+    /// Un cluster est alloué et rempli de zéros, puis reçoit les entrées `.`
+    /// (le répertoire lui-même) et `..` (le parent, 0 quand c'est la racine).
+    /// L'entrée du répertoire est ensuite créée dans le parent avec
+    /// l'attribut dossier, comme avec [`Fat32Fs::create_file`]. Si cette
+    /// dernière étape échoue, le cluster est libéré.
+    ///
+    /// # Errors
+    ///
+    /// Les mêmes que [`Fat32Fs::create_file`], notamment
+    /// [`Fat32Error::AlreadyExists`] si une entrée porte déjà ce nom.
+    pub fn create_dir(&self, parent_cluster: u32, name: &str) -> Result<u32> {
+        self.check_writable()?;
+        if self.find_entry(parent_cluster, name)?.is_some() {
+            return Err(Fat32Error::AlreadyExists);
+        }
+
+        let cluster = self.allocate_chain(1)?;
+        let result = self.init_dir_cluster(cluster, parent_cluster).and_then(|()| {
+            self.create_entry(parent_cluster, name, attr::DIRECTORY, cluster)
+        });
+        match result {
+            Ok(_) => Ok(cluster),
+            Err(err) => {
+                // Erreur d'origine plus utile que celle de la libération
+                let _ = self.free_chain(cluster);
+                Err(err)
+            }
+        }
+    }
+
+    /// Crée le répertoire `path` (ex: `"/a/b/c"`) et tous ses parents
+    /// manquants, comme `mkdir -p`, et retourne le premier cluster du dernier.
+    ///
+    /// Les composants sont résolus depuis la racine comme avec
+    /// [`Fat32Fs::open`] ; les répertoires qui existent déjà sont réutilisés.
+    /// `"/"` désigne la racine. Un volume en lecture seule n'est refusé que
+    /// s'il faut effectivement créer un répertoire.
+    ///
+    /// # Errors
+    ///
+    /// - [`Fat32Error::InvalidPath`] si un composant est vide, `.` ou `..`
+    /// - [`Fat32Error::IsNotDirectory`] si un composant existe et est un fichier
+    /// - les erreurs de [`Fat32Fs::create_dir`]
+    pub fn create_dir_all(&self, path: &str) -> Result<u32> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let mut dir_cluster = self.geom.root_cluster;
+        if path.is_empty() {
+            return Ok(dir_cluster);
+        }
+
+        for component in path.split('/') {
+            if component.is_empty() || component == "." || component == ".." {
+                return Err(Fat32Error::InvalidPath);
+            }
+
+            dir_cluster = match self.find_in_dir(dir_cluster, component)? {
+                Some(entry) if entry.is_dir() => entry.first_cluster(),
+                Some(_) => return Err(Fat32Error::IsNotDirectory),
+                None => self.create_dir(dir_cluster, component)?,
+            };
+        }
+        Ok(dir_cluster)
+    }
+
+    // Vide le cluster `cluster` d'un nouveau répertoire et y écrit les
+    // entrées `.` et `..`.
+    fn init_dir_cluster(&self, cluster: u32, parent_cluster: u32) -> Result<()> {
+        self.zero_cluster(cluster)?;
+        // `..` vaut 0 quand le parent est la racine
+        let parent = if parent_cluster == self.geom.root_cluster { 0 } else { parent_cluster };
+        let dot = self.new_short_entry(&DOT_NAME, attr::DIRECTORY, cluster);
+        let dotdot = self.new_short_entry(&DOTDOT_NAME, attr::DIRECTORY, parent);
+        let first = EntryPosition { cluster, offset: 0 };
+        self.update_slots(first, 2, |index, dst| {
+            dst.copy_from_slice(if index == 0 { &dot } else { &dotdot });
+        })
+    }
+
+    // Emplacement de l'entrée située `index` entrées après `first`, en
+    // suivant la chaîne du répertoire.
+    fn slot_position(&self, first: EntryPosition, index: u32) -> Result<EntryPosition> {
+        let cluster_size = self.geom.cluster_size_bytes();
+        let mut cluster = first.cluster;
+        let mut offset = first.offset + index * 32;
+        while offset >= cluster_size {
+            cluster = self
+                .read_fat_entry(cluster)?
+                .next_cluster()
+                .ok_or(Fat32Error::CorruptedDirectory)?;
+            offset -= cluster_size;
+        }
+        Ok(EntryPosition { cluster, offset })
+    }
+
+    // Cherche `count` entrées libres consécutives dans le répertoire
+    // `dir_cluster` et retourne l'emplacement de la première. Le répertoire
+    // est agrandi de clusters vides si la place manque.
+    fn find_free_slots(&self, dir_cluster: u32, count: u32) -> Result<EntryPosition> {
+        let start = if dir_cluster == 0 { self.geom.root_cluster } else { dir_cluster };
+        let bytes_per_sector = self.geom.bytes_per_sector;
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+
+        let mut run_start = EntryPosition::default();
+        let mut run_len = 0;
+        let mut last = start;
+        // Après une entrée 0x00, toutes les suivantes sont libres
+        let mut end_seen = false;
+
+        for cluster in self.cluster_chain(start) {
+            last = cluster?;
+            for sector_index in 0..self.geom.sectors_per_cluster {
+                self.read_sectors(self.geom.cluster_to_lba(last)? + sector_index, 1, sector)?;
+                for (i, slot) in sector.chunks_exact(32).enumerate() {
+                    end_seen |= slot[0] == 0x00;
+                    if !end_seen && slot[0] != DELETED_ENTRY {
+                        run_len = 0;
+                        continue;
+                    }
+                    if run_len == 0 {
+                        run_start = EntryPosition {
+                            cluster: last,
+                            offset: sector_index * bytes_per_sector + i as u32 * 32,
+                        };
+                    }
+                    run_len += 1;
+                    if run_len == count {
+                        return Ok(run_start);
+                    }
+                }
+            }
+        }
+
+        // Répertoire plein : la série en cours se prolonge dans les clusters ajoutés
+        while run_len < count {
+            last = self.append_dir_cluster(last)?;
+            if run_len == 0 {
+                run_start = EntryPosition {
+                    cluster: last,
+                    offset: 0,
+                };
+            }
+            run_len += self.geom.cluster_size_bytes() / 32;
+        }
+        Ok(run_start)
+    }
+
+    // Ajoute un cluster vide après `dir_last_cluster`, dernier cluster de la
+    // chaîne d'un répertoire, et le retourne. Le cluster est rempli de zéros
+    // avant d'être relié : le répertoire ne voit jamais de données
+    // résiduelles, même si l'écriture est interrompue. Il est cherché à
+    // partir de `dir_last_cluster + 1` et rendu en cas d'échec.
+    fn append_dir_cluster(&self, dir_last_cluster: u32) -> Result<u32> {
+        if !self.read_fat_entry(dir_last_cluster)?.is_end() {
+            return Err(Fat32Error::InvalidCluster(dir_last_cluster));
+        }
+
+        let (cluster, _) = self.claim_clusters(dir_last_cluster + 1, 1)?;
+        let linked = self
+            .zero_cluster(cluster)
+            .and_then(|()| self.write_fat_entry(dir_last_cluster, FatEntry::set_next(cluster)));
+        if let Err(err) = linked {
+            let _ = self.release_chain(cluster);
+            return Err(err);
+        }
+        if let Err(err) = self.update_fsinfo(-1, Some(self.next_free_after(cluster))) {
+            let _ = self.write_fat_entry(dir_last_cluster, FatEntry::end_of_chain());
+            let _ = self.release_chain(cluster);
+            return Err(err);
+        }
+        Ok(cluster)
+    }
+
+    // Remplit de zéros tous les secteurs de `cluster`.
+    fn zero_cluster(&self, cluster: u32) -> Result<()> {
+        let buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = &buf[..self.geom.bytes_per_sector as usize];
+        let lba = self.geom.cluster_to_lba(cluster)?;
+        for sector_index in 0..self.geom.sectors_per_cluster {
+            self.write_sectors(lba + sector_index, 1, sector)?;
+        }
+        Ok(())
+    }
+
+    // Met à jour le premier cluster, la taille et les dates de modification
+    // et d'accès de l'entrée 8.3 située à `position`, sans toucher aux autres
+    // champs.
+    pub(crate) fn update_entry_metadata(
+        &self,
+        position: EntryPosition,
+        first_cluster: u32,
+        size: u32,
+    ) -> Result<()> {
+        let bytes_per_sector = self.geom.bytes_per_sector;
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+
+        let lba = self.geom.cluster_to_lba(position.cluster)? + position.offset / bytes_per_sector;
+        self.read_sectors(lba, 1, sector)?;
+        let entry = &mut sector[(position.offset % bytes_per_sector) as usize..][..32];
+        entry[20..22].copy_from_slice(&((first_cluster >> 16) as u16).to_le_bytes());
+        entry[26..28].copy_from_slice(&(first_cluster as u16).to_le_bytes());
+        entry[28..32].copy_from_slice(&size.to_le_bytes());
+        let (date, time, _) = self.now().to_dos();
+        entry[18..20].copy_from_slice(&date.to_le_bytes());
+        entry[22..24].copy_from_slice(&time.to_le_bytes());
+        entry[24..26].copy_from_slice(&date.to_le_bytes());
+        self.write_sectors(lba, 1, sector)
+    }
+
+    // Écrit `0xE5` dans le premier octet des `span.slots` entrées de 32 octets
+    // qui commencent à `span`.
+    fn mark_slots_deleted(&self, span: EntrySpan) -> Result<()> {
+        self.update_slots(span.first, span.slots, |_, slot| slot[0] = DELETED_ENTRY)
+    }
+
+    // Applique `update(index, entrée)` aux `count` entrées de 32 octets qui
+    // commencent à `first`, en suivant la chaîne du répertoire si elles
+    // débordent sur le cluster suivant. Chaque secteur concerné est lu puis
+    // réécrit une seule fois.
+    fn update_slots<F>(&self, first: EntryPosition, count: u32, mut update: F) -> Result<()>
+    where
+        F: FnMut(u32, &mut [u8]),
+    {
+        let bytes_per_sector = self.geom.bytes_per_sector;
+        let cluster_size = self.geom.cluster_size_bytes();
+        let mut buf = [0u8; MAX_SECTOR_SIZE];
+        let sector = self.sector_buf(&mut buf)?;
+
+        let mut cluster = first.cluster;
+        let mut offset = first.offset;
+        let mut index = 0;
+        while index < count {
+            if offset >= cluster_size {
+                cluster = self
+                    .read_fat_entry(cluster)?
+                    .next_cluster()
+                    .ok_or(Fat32Error::CorruptedDirectory)?;
+                offset = 0;
+            }
+
+            // Toutes les entrées concernées de ce secteur en une écriture
+            let lba = self.geom.cluster_to_lba(cluster)? + offset / bytes_per_sector;
+            self.read_sectors(lba, 1, sector)?;
+            let mut in_sector = (offset % bytes_per_sector) as usize;
+            while index < count && in_sector < sector.len() {
+                update(index, &mut sector[in_sector..in_sector + 32]);
+                in_sector += 32;
+                offset += 32;
+                index += 1;
+            }
+            self.write_sectors(lba, 1, sector)?;
+        }
+        Ok(())
+    }
+}
+
+/// Plus grand numéro de queue `~N` essayé par [`generate_short_name`].
+pub const MAX_SHORT_NAME_TAIL: u32 = 1024;
+
+/// Génère un nom court 8.3 pour le nom long `long`, sans collision avec les
+/// entrées restantes de `existing` (qui est parcouru jusqu'au bout).
+///
+/// Le nom court reprend la base calculée par [`ShortNameBasis`]. Si la
+/// conversion est exacte (seule la casse change) et que ce nom est libre, il
+/// est utilisé tel quel ; sinon la queue `~N` libre la plus petite est
+/// ajoutée (`LONGFI~1.HTM`, `LONGFI~2.HTM`..., `LONGF~10.HTM`).
+///
+/// # Errors
+///
+/// - [`Fat32Error::NameTooLong`] ou [`Fat32Error::InvalidPath`] si `long`
+///   ne peut pas être converti (voir [`ShortNameBasis::new`])
+/// - [`Fat32Error::AlreadyExists`] si les queues `~1` à
+///   [`MAX_SHORT_NAME_TAIL`] sont toutes prises
+/// - les erreurs de lecture du répertoire
+pub fn generate_short_name<D: BlockDevice>(
+    long: &str,
+    existing: &mut DirectoryIterator<'_, '_, D>,
+) -> Result<[u8; 11]> {
+    let basis = ShortNameBasis::new(long)?;
+    let mut exact_taken = false;
+    // Bit `n - 1` posé si la queue `~n` est déjà utilisée
+    let mut taken = [0u64; MAX_SHORT_NAME_TAIL as usize / 64];
+
+    while let Some(entry) = existing.next_entry()? {
+        if entry.is_lfn() {
+            continue;
+        }
+        let name = entry.name;
+        exact_taken |= name == basis.name();
+        if let Some(n) = basis.tail_of(&name).filter(|&n| n <= MAX_SHORT_NAME_TAIL) {
+            taken[(n - 1) as usize / 64] |= 1 << ((n - 1) % 64);
+        }
+    }
+
+    if basis.is_exact() && !exact_taken {
+        return Ok(basis.name());
+    }
+    (1..=MAX_SHORT_NAME_TAIL)
+        .find(|&n| taken[(n - 1) as usize / 64] & (1 << ((n - 1) % 64)) == 0)
+        .map(|n| basis.with_tail(n))
+        .ok_or(Fat32Error::AlreadyExists)
+}
+
+// Valide le secteur de boot d'un volume FAT32 lu sur un device à secteurs de
+// `sector_size` octets et retourne sa géométrie (partagé avec le montage
+// asynchrone). En mode strict, les champs sans effet sur la lecture doivent
+// aussi être conformes.
+pub(crate) fn volume_geometry(boot_sector: &[u8], sector_size: usize, strict: bool) -> Result<Fat32Geometry> {
+    // Vérifie la signature du boot sector (octets 510-511 = 0x55AA)
+    if boot_sector.len() < 512 {
+        return Err(Fat32Error::InvalidBootSector);
+    }
+    
+    if boot_sector[510] != 0x55 || boot_sector[511] != 0xAA {
+        return Err(Fat32Error::InvalidBootSector);
+    }
+
+    // On a vérifié que boot_sector fait au moins 512 octets
+    let boot = BootSector::parse(boot_sector).ok_or(Fat32Error::InvalidBootSector)?;
+    let bpb = boot.bpb;
+    
+    // Vérifie que c'est bien FAT32 (fat_size_16 doit être 0)
+    if bpb.fat_size_16 != 0 || bpb.fat_size_32 == 0 {
+        return Err(Fat32Error::NotFat32);
+    }
+
+    bpb.validate()?;
+    if strict {
+        boot.validate_strict()?;
+    }
+
+    // Le type de FAT se déduit du nombre de clusters, pas des champs de taille
+    if detect_fat_type(&bpb) != FatType::Fat32 {
+        return Err(Fat32Error::NotFat32);
+    }
+
+    // Le device doit lire des secteurs de la taille annoncée par la BPB
+    if bpb.bytes_per_sector as usize != sector_size {
+        return Err(Fat32Error::UnsupportedSectorSize);
+    }
+
+    Ok(Fat32Geometry::from_bpb(&bpb))
+}
+
+// Vérifie que le volume tient sur le device à partir du secteur
+// `partition_start`, d'après le résultat de `sector_count`.
+pub(crate) fn check_device_size(
+    geom: &Fat32Geometry,
+    sector_count: core::result::Result<u32, BlockDeviceError>,
+    partition_start: u32,
+) -> Result<()> {
+    // Devices qui ne connaissent pas leur taille : pas de vérification
+    match sector_count {
+        Ok(count) if geom.total_sectors > count.saturating_sub(partition_start) => {
+            Err(Fat32Error::DeviceTooSmall)
+        }
+        Ok(_) | Err(BlockDeviceError::Unsupported) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Remplace les espaces de fin d'un label par des octets nuls.
+fn trim_label(mut label: [u8; 11]) -> [u8; 11] {
+    for b in label.iter_mut().rev() {
+        if *b != b' ' {
+            break;
+        }
+        *b = 0;
+    }
+    label
+}
+
+/// Profondeur maximale de [`Fat32Fs::walk`].
+pub const MAX_WALK_DEPTH: usize = 16;
+
+/// Taille en octets du buffer de [`WalkPath`], qui contient les noms UTF-8
+/// de tous les répertoires parents.
+pub const MAX_WALK_PATH: usize = 1024;
+
+/// Répertoires parents d'une entrée visitée par [`Fat32Fs::walk`], du plus
+/// haut (sous le répertoire de départ) au plus proche.
+///
+/// Les noms sont stockés à la suite dans un seul buffer, empilés et
+/// dépilés au fil de la descente.
+pub struct WalkPath {
+    names: [u8; MAX_WALK_PATH],
+    // Fin du nom de chaque niveau dans `names`
+    ends: [u16; MAX_WALK_DEPTH],
+    clusters: [u32; MAX_WALK_DEPTH],
+    depth: usize,
+}
+
+impl WalkPath {
+    fn new() -> Self {
+        Self {
+            names: [0u8; MAX_WALK_PATH],
+            ends: [0u16; MAX_WALK_DEPTH],
+            clusters: [0u32; MAX_WALK_DEPTH],
+            depth: 0,
+        }
+    }
+
+    // Ajoute un niveau à la fin du chemin.
+    fn push(&mut self, name: &str, cluster: u32) -> Result<()> {
+        let start = self.names_end();
+        let end = start + name.len();
+        if self.depth == MAX_WALK_DEPTH || end > MAX_WALK_PATH {
+            return Err(Fat32Error::NameTooLong);
+        }
+        self.names[start..end].copy_from_slice(name.as_bytes());
+        self.ends[self.depth] = end as u16;
+        self.clusters[self.depth] = cluster;
+        self.depth += 1;
+        Ok(())
+    }
+
+    // Retire le dernier niveau ; `false` si le chemin était vide.
+    fn pop(&mut self) -> bool {
+        if self.depth == 0 {
+            return false;
+        }
+        self.depth -= 1;
+        true
+    }
+
+    fn names_end(&self) -> usize {
+        match self.depth {
+            0 => 0,
+            depth => self.ends[depth - 1] as usize,
+        }
+    }
+
+    /// Nombre de répertoires parents (0 pour les entrées directes du
+    /// répertoire de départ).
+    pub fn len(&self) -> usize {
+        self.depth
+    }
+
+    /// `true` pour les entrées directes du répertoire de départ.
+    pub fn is_empty(&self) -> bool {
+        self.depth == 0
+    }
+
+    /// Nom du répertoire parent de niveau `level` (nom long si présent).
+    pub fn name(&self, level: usize) -> Option<&str> {
+        if level >= self.depth {
+            return None;
+        }
+        let start = match level {
+            0 => 0,
+            level => self.ends[level - 1] as usize,
+        };
+        // Les noms viennent de `&str` : le découpage reste de l'UTF-8 valide
+        core::str::from_utf8(&self.names[start..self.ends[level] as usize]).ok()
+    }
+
+    /// Premier cluster du répertoire parent de niveau `level`.
+    pub fn cluster(&self, level: usize) -> Option<u32> {
+        self.clusters[..self.depth].get(level).copied()
+    }
+
+    /// Noms des répertoires parents, du plus haut au plus proche.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.depth).filter_map(|level| self.name(level))
+    }
+}
+
+/// Suite à donner au parcours, retournée par le callback de [`Fat32Fs::walk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkAction {
+    /// Continue, en descendant dans l'entrée si c'est un répertoire.
+    Continue,
+    /// Continue sans descendre dans cette entrée.
+    SkipDir,
+    /// Arrête le parcours.
+    Stop,
+}
+
+// Position d'un `ClusterChain` dans la chaîne.
+#[derive(Clone, Copy)]
+enum ChainState {
+    // Le premier cluster n'a pas encore été produit.
+    Start(u32),
+    // Dernier cluster produit : le suivant est lu dans la FAT au prochain appel.
+    After(u32),
+    Done,
+}
+
+/// Itérateur sur les numéros de clusters d'une chaîne, créé par
+/// [`Fat32Fs::cluster_chain`].
+///
+/// # Exemple
+///
+/// ```no_run
+/// use fat32_parser::{BlockDevice, Fat32Fs};
+///
+/// // Nombre de fragments (suites de clusters contigus) d'une chaîne.
+/// fn fragments<D: BlockDevice>(fs: &Fat32Fs<'_, D>, start: u32) -> fat32_parser::Result<u32> {
+///     let mut fragments = 0;
+///     let mut previous = None;
+///     for cluster in fs.cluster_chain(start) {
+///         let cluster = cluster?;
+///         if previous.map_or(true, |p: u32| p + 1 != cluster) {
+///             fragments += 1;
+///         }
+///         previous = Some(cluster);
+///     }
+///     Ok(fragments)
+/// }
+/// ```
+pub struct ClusterChain<'fs, 'a, D: BlockDevice> {
+    fs: &'fs Fat32Fs<'a, D>,
+    state: ChainState,
+    // Détection de cycle (Brent) : `saved` est un cluster déjà produit,
+    // remplacé par le cluster courant après `power` pas, `power` doublant à
+    // chaque fois. Si la chaîne boucle, un cluster suivant finit par être
+    // égal à `saved`, au plus deux tours de boucle après son début. Seules
+    // les entrées lues pour avancer sont comparées : aucune lecture de FAT
+    // supplémentaire.
+    saved: u32,
+    power: u32,
+    steps: u32,
+    count: u32,
+    // Protection contre les chaînes anormalement longues
+    limit: u32,
+}
+
+impl<D: BlockDevice> ClusterChain<'_, '_, D> {
+    // Lit dans la FAT le cluster qui suit `cluster` (`None` en fin de chaîne).
+    fn advance(&mut self, cluster: u32) -> Result<Option<u32>> {
+        let fat_entry = self.fs.read_fat_entry(cluster)?;
+        if fat_entry.is_end() {
+            return Ok(None);
+        }
+        // Un cluster de la chaîne ne peut pas être marqué libre
+        if fat_entry.is_free() {
+            return Err(Fat32Error::CorruptedFat);
+        }
+        let next = match fat_entry.next_cluster() {
+            Some(next) => next,
+            None => return Ok(None),
+        };
+
+        if next == self.saved {
+            return Err(Fat32Error::CorruptedChain);
+        }
+        self.steps += 1;
+        if self.steps == self.power {
+            self.saved = next;
+            self.power = self.power.saturating_mul(2);
+            self.steps = 0;
+        }
+        Ok(Some(next))
+    }
+}
+
+impl<D: BlockDevice> Iterator for ClusterChain<'_, '_, D> {
+    type Item = Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cluster = match self.state {
+            ChainState::Done => return None,
+            ChainState::Start(cluster) => cluster,
+            ChainState::After(previous) => match self.advance(previous) {
+                Ok(Some(cluster)) => cluster,
+                Ok(None) => {
+                    self.state = ChainState::Done;
+                    return None;
+                }
+                Err(e) => {
+                    self.state = ChainState::Done;
+                    return Some(Err(e));
+                }
+            },
+        };
+
+        self.state = ChainState::Done;
+        if self.count >= self.limit {
+            return Some(Err(Fat32Error::InvalidCluster(cluster)));
+        }
+        if let Err(e) = self.fs.check_cluster(cluster) {
+            return Some(Err(e));
+        }
+
+        self.count += 1;
+        self.state = ChainState::After(cluster);
+        Some(Ok(cluster))
+    }
+}
+
+/// Itérateur sur les clusters défectueux du volume, créé par
+/// [`Fat32Fs::scan_bad_clusters`].
+pub struct BadClusterIter<'fs, 'a, D: BlockDevice> {
+    fs: &'fs Fat32Fs<'a, D>,
+    // Prochain cluster à examiner, et premier numéro hors du volume
+    next: u32,
+    end: u32,
+}
+
+impl<D: BlockDevice> Iterator for BadClusterIter<'_, '_, D> {
+    type Item = Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.end {
+            let cluster = self.next;
+            self.next += 1;
+            match self.fs.read_fat_entry(cluster) {
+                Ok(entry) if entry.is_bad() => return Some(Ok(cluster)),
+                Ok(_) => {}
+                Err(e) => {
+                    self.next = self.end;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Itérateur sur les entrées d'un répertoire FAT32.
+///
+/// Implémente [`Iterator`] en produisant des [`DirEntry`] décodées (avec nom
+/// long) ; après une erreur, l'itération s'arrête.
+///
+/// # Exemples
+///
+/// ```no_run
+/// use fat32_parser::{Fat32Fs, BlockDevice};
+///
+/// fn list<D: BlockDevice>(fs: &Fat32Fs<'_, D>) -> fat32_parser::Result<u32> {
+///     let mut total = 0;
+///     for entry in fs.read_root_dir()? {
+///         total += entry?.file_size();
+///     }
+///     Ok(total)
+/// }
+/// ```
+pub struct DirectoryIterator<'fs, 'a, D: BlockDevice> {
+    fs: &'fs Fat32Fs<'a, D>,
+    cluster: u32,
+    // Position de la prochaine entrée dans le buffer
+    offset: usize,
+    // Le buffer contient les octets `window_start..window_start + window_len`
+    // du cluster courant : un grand cluster est parcouru par fenêtres.
+    window_start: usize,
+    window_len: usize,
+    cluster_size: usize,
+    buffer: [u8; CHUNK_SIZE],
+    // Dernière entrée retournée par `next_entry`
+    entry: DirectoryEntryRaw,
+    done: bool,
+    // Retourner aussi les entrées `.` et `..`
+    include_dots: bool,
+    // Emplacement de la dernière entrée retournée par `next_file`
+    last_span: EntrySpan,
+    // Continuer après l'entrée `0x00` jusqu'à la fin de la chaîne
+    past_end: bool,
+}
+
+impl<'fs, 'a, D: BlockDevice> DirectoryIterator<'fs, 'a, D> {
+    fn new(fs: &'fs Fat32Fs<'a, D>, start_cluster: u32) -> Result<Self> {
+        Self::resume(fs, EntryPosition { cluster: start_cluster, offset: 0 })
+    }
+
+    // Commence la lecture à l'entrée `position` (offset multiple de 32, au
+    // plus la taille d'un cluster), sans relire ce qui la précède.
+    fn resume(fs: &'fs Fat32Fs<'a, D>, position: EntryPosition) -> Result<Self> {
+        let bytes_per_sector = fs.geom.bytes_per_sector as usize;
+        if bytes_per_sector == 0 || bytes_per_sector > CHUNK_SIZE {
+            return Err(Fat32Error::BufferTooSmall);
+        }
+
+        let mut iter = Self {
+            fs,
+            cluster: position.cluster,
+            offset: 0,
+            window_start: 0,
+            window_len: 0,
+            cluster_size: fs.geom.cluster_size_bytes() as usize,
+            buffer: [0u8; CHUNK_SIZE],
+            entry: DirectoryEntryRaw::parse(&[0u8; 32]),
+            done: false,
+            include_dots: false,
+            last_span: EntrySpan::default(),
+            past_end: false,
+        };
+
+        let offset = position.offset as usize;
+        if offset >= iter.cluster_size {
+            // Fin du cluster : le suivant sera lu dans la FAT
+            iter.window_start = iter.cluster_size;
+        } else {
+            // Charge la fenêtre qui commence au secteur de l'entrée
+            let start = offset - offset % bytes_per_sector;
+            iter.load_window(position.cluster, start)?;
+            iter.offset = offset - start;
+        }
+
+        Ok(iter)
+    }
+
+    // Charge dans le buffer la fenêtre du cluster `cluster` qui commence à
+    // l'octet `start` (multiple de la taille d'un secteur).
+    fn load_window(&mut self, cluster: u32, start: usize) -> Result<()> {
+        let bytes_per_sector = self.fs.geom.bytes_per_sector as usize;
+        let len = (self.cluster_size - start).min(CHUNK_SIZE / bytes_per_sector * bytes_per_sector);
+        self.fs.read_cluster_sectors(
+            cluster,
+            (start / bytes_per_sector) as u32,
+            &mut self.buffer[..len],
+        )?;
+        self.cluster = cluster;
+        self.window_start = start;
+        self.window_len = len;
+        self.offset = 0;
+        Ok(())
+    }
+
+    /// Inclut (ou non) les entrées `.` et `..` des sous-répertoires, ignorées
+    /// par défaut.
+    pub fn include_dot_entries(mut self, include: bool) -> Self {
+        self.include_dots = include;
+        self
+    }
+
+    /// Avance d'une entrée de 32 octets et retourne son offset dans le buffer,
+    /// en chargeant le cluster suivant si nécessaire.
+    ///
+    /// Retourne `None` à la fin du répertoire (entrée `0x00` ou fin de chaîne).
+    fn next_slot(&mut self) -> Result<Option<usize>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        // Vérifie si on est à la fin de la fenêtre chargée
+        if self.offset >= self.window_len {
+            let next_start = self.window_start + self.window_len;
+            if next_start < self.cluster_size {
+                // Fenêtre suivante du même cluster
+                self.load_window(self.cluster, next_start)?;
+            } else {
+                // Charge le cluster suivant
+                let fat_entry = self.fs.read_fat_entry(self.cluster)?;
+
+                if fat_entry.is_end() {
+                    self.done = true;
+                    return Ok(None);
+                }
+
+                match fat_entry.next_cluster() {
+                    Some(next) => self.load_window(next, 0)?,
+                    None => {
+                        self.done = true;
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+
+        let offset = self.offset;
+        self.offset += 32; // Taille d'une entrée de répertoire
+
+        // Fin du répertoire
+        if self.buffer[offset] == 0x00 && !self.past_end {
+            self.done = true;
+            return Ok(None);
+        }
+
+        Ok(Some(offset))
+    }
+
+    // Copie l'entrée de 32 octets située à `offset` dans le buffer.
+    fn slot(&self, offset: usize) -> [u8; 32] {
+        let mut slot = [0u8; 32];
+        slot.copy_from_slice(&self.buffer[offset..offset + 32]);
+        slot
+    }
+
+    /// Retourne la prochaine entrée de répertoire valide.
+    pub fn next_entry(&mut self) -> Result<Option<&DirectoryEntryRaw>> {
+        loop {
+            let offset = match self.next_slot()? {
+                Some(offset) => offset,
+                None => return Ok(None),
+            };
+
+            // Entrée supprimée ou volume label, on skip
+            let first_byte = self.buffer[offset];
+            let attributes = Attributes(self.buffer[offset + 11]);
+            if first_byte == 0xE5 || (attributes.is_volume_id() && !attributes.is_lfn()) {
+                continue;
+            }
+
+            let entry = DirectoryEntryRaw::parse(&self.slot(offset));
+            if entry.is_dot_entry() && !self.include_dots {
+                continue;
+            }
+            self.entry = entry;
+            return Ok(Some(&self.entry));
+        }
+    }
+
+    /// Retourne le prochain fichier ou dossier avec son nom long reconstruit.
+    ///
+    /// Les fragments LFN (attribut `0x0F`) qui précèdent une entrée 8.3 sont
+    /// accumulés puis décodés depuis l'UTF-16LE. Les entrées supprimées, le
+    /// volume label et, sauf avec [`include_dot_entries`](Self::include_dot_entries),
+    /// les entrées `.` et `..` sont ignorés.
+    ///
+    /// Si la somme de contrôle des fragments ne correspond pas au nom court
+    /// (fragments orphelins laissés par un outil sans support LFN), le nom
+    /// long est ignoré et l'entrée est retournée avec son nom 8.3. Il en va
+    /// de même pour une chaîne LFN incomplète : les fragments interrompus par
+    /// une entrée supprimée, le volume label ou le début d'une autre chaîne
+    /// (drapeau `0x40`), ou dont la séquence est incohérente, sont ignorés.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de lecture du répertoire et de sa chaîne de
+    /// clusters.
+    pub fn next_file(&mut self) -> Result<Option<DirEntry>> {
+        let mut units = [0u16; LFN_MAX_ENTRIES * LFN_CHARS_PER_ENTRY];
+        // Nombre total de fragments de la chaîne en cours (0 = aucune chaîne)
+        let mut lfn_count = 0usize;
+        // Numéro de séquence attendu pour le prochain fragment
+        let mut expected = 0u8;
+        // Somme de contrôle annoncée par la chaîne, et cohérence entre fragments
+        let mut checksum = 0u8;
+        let mut checksum_consistent = true;
+        // Emplacement du premier fragment de la chaîne en cours
+        let mut chain_start = EntryPosition::default();
+
+        loop {
+            let Some(offset) = self.next_slot()? else {
+                return Ok(None);
+            };
+            let position = EntryPosition {
+                cluster: self.cluster,
+                offset: (self.window_start + offset) as u32,
+            };
+
+            let slot = self.slot(offset);
+            let attributes = Attributes(slot[11]);
+
+            // Entrée supprimée : abandonne la chaîne LFN en cours
+            if slot[0] == 0xE5 {
+                lfn_count = 0;
+                continue;
+            }
+
+            if attributes.is_lfn() {
+                let sequence = slot[0] & !LFN_LAST_ENTRY;
+                let valid = sequence != 0 && sequence as usize <= LFN_MAX_ENTRIES;
+
+                if slot[0] & LFN_LAST_ENTRY != 0 && valid {
+                    // Premier fragment sur le disque = dernier morceau du nom ;
+                    // une chaîne déjà commencée est abandonnée
+                    lfn_count = sequence as usize;
+                    checksum = slot[13];
+                    checksum_consistent = true;
+                    chain_start = position;
+                } else if lfn_count == 0 || !valid || sequence != expected {
+                    // Fragment hors séquence : la chaîne et lui sont ignorés
+                    lfn_count = 0;
+                    continue;
+                } else if slot[13] != checksum {
+                    checksum_consistent = false;
+                }
+
+                let start = (sequence as usize - 1) * LFN_CHARS_PER_ENTRY;
+                units[start..start + LFN_CHARS_PER_ENTRY].copy_from_slice(&lfn_units(&slot));
+                expected = sequence - 1;
+                continue;
+            }
+
+            // Volume label : ignoré, et il interrompt une chaîne LFN
+            if attributes.is_volume_id() {
+                lfn_count = 0;
+                continue;
+            }
+
+            // Entrée 8.3 : une chaîne LFN incomplète ne lui appartient pas
+            if lfn_count != 0 && expected != 0 {
+                lfn_count = 0;
+            }
+
+            let raw = DirectoryEntryRaw::parse(&slot);
+            if raw.is_dot_entry() && !self.include_dots {
+                lfn_count = 0;
+                continue;
+            }
+            let long_name = if lfn_count != 0
+                && checksum_consistent
+                && lfn_checksum(&raw.name) == checksum
+            {
+                Some(&units[..lfn_count * LFN_CHARS_PER_ENTRY])
+            } else {
+                None
+            };
+
+            self.last_span = if lfn_count != 0 {
+                EntrySpan {
+                    first: chain_start,
+                    slots: lfn_count as u32 + 1,
+                    short: position,
+                }
+            } else {
+                EntrySpan {
+                    first: position,
+                    slots: 1,
+                    short: position,
+                }
+            };
+            return Ok(Some(DirEntry::new(raw, long_name)));
+        }
+    }
+
+    /// Emplacement sur le disque de la dernière entrée retournée par
+    /// [`next_file`](Self::next_file) (ou par l'itérateur).
+    ///
+    /// Avant la première entrée, tous les champs valent 0.
+    pub fn location(&self) -> EntryLocation {
+        EntryLocation::from(self.last_span)
+    }
+}
+
+/// Entrée de 32 octets retournée par [`RawDirIter`], avec son emplacement.
+#[derive(Debug, Clone, Copy)]
+pub struct RawSlot {
+    /// Contenu de l'entrée, copié sans interprétation.
+    pub entry: DirectoryEntryRaw,
+    /// Cluster du répertoire qui contient l'entrée.
+    pub cluster: u32,
+    /// Offset de l'entrée dans ce cluster, en octets.
+    pub offset: u32,
+}
+
+/// Itérateur sur les entrées brutes d'un répertoire, créé par
+/// [`Fat32Fs::raw_entries`].
+pub struct RawDirIter<'fs, 'a, D: BlockDevice> {
+    inner: DirectoryIterator<'fs, 'a, D>,
+}
+
+impl<D: BlockDevice> RawDirIter<'_, '_, D> {
+    /// Continue (ou non) après l'entrée `0x00` qui marque la fin du
+    /// répertoire, jusqu'à la fin de sa chaîne de clusters. Les entrées
+    /// libres sont alors retournées aussi.
+    pub fn include_unused(mut self, include: bool) -> Self {
+        self.inner.past_end = include;
+        self
+    }
+}
+
+impl<D: BlockDevice> Iterator for RawDirIter<'_, '_, D> {
+    type Item = Result<RawSlot>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next_slot() {
+            Ok(Some(offset)) => Some(Ok(RawSlot {
+                entry: DirectoryEntryRaw::parse(&self.inner.slot(offset)),
+                cluster: self.inner.cluster,
+                offset: (self.inner.window_start + offset) as u32,
+            })),
+            Ok(None) => None,
+            Err(err) => {
+                self.inner.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<D: BlockDevice> Iterator for DirectoryIterator<'_, '_, D> {
+    type Item = Result<DirEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_file() {
+            Ok(entry) => entry.map(Ok),
+            Err(err) => {
+                // Pas de reprise possible après une erreur
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_device::BlockDeviceError;
+    use crate::devices::SliceDevice;
+    use crate::testing::*;
+    use crate::timestamp::FixedClock;
+
+    // Valeur brute d'une fin de chaîne, pour préparer la FAT des devices de test.
+    const END_OF_CHAIN: u32 = FatEntry::end_of_chain().value;
+
+    struct DummyDevice;
+
+    impl BlockDevice for DummyDevice {
+        fn read_sectors(
+            &self,
+            _lba: u32,
+            _count: u32,
+            _buf: &mut [u8],
+        ) -> core::result::Result<(), BlockDeviceError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_invalid_boot_sector() {
+        let dev = DummyDevice;
+        let invalid_boot = [0u8; 512]; // Signature invalide
+        assert!(matches!(
+            Fat32Fs::mount(&dev, &invalid_boot),
+            Err(Fat32Error::InvalidBootSector)
+        ));
+    }
+
+    #[test]
+    fn test_boot_sector_too_small() {
+        let dev = DummyDevice;
+        let small_boot = [0u8; 100]; // Trop petit
+        assert!(matches!(
+            Fat32Fs::mount(&dev, &small_boot),
+            Err(Fat32Error::InvalidBootSector)
+        ));
+    }
+        #[test]
+    fn test_valid_signature_but_not_fat32() {
+        let dev = DummyDevice;
+        let mut boot = [0u8; 512];
+        boot[510] = 0x55;
+        boot[511] = 0xAA;
+        // Signature valide mais BPB invalide (fat_size_32 = 0)
+        let result = Fat32Fs::mount(&dev, &boot);
+        assert!(matches!(result, Err(Fat32Error::NotFat32)));
+    }
+
+    #[test]
+    fn test_fat_entry_reading() {
+        // Test avec un device qui retourne des données connues
+    }
+
+    #[test]
+    fn test_next_file_long_name() {
+        let short = *b"ALONGF~1TXT";
+        let checksum = lfn_checksum(&short);
+        let dev = device_with_root(&[
+            lfn_entry(0x42, "ame.txt", checksum),
+            lfn_entry(0x01, "A long file n", checksum),
+            short_entry(&short, 0x20),
+            short_entry(b"README  TXT", 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let mut dir = fs.read_root_dir().unwrap();
+
+        let entry = dir.next_file().unwrap().unwrap();
+        assert_eq!(entry.name(), "A long file name.txt");
+        assert!(entry.has_long_name());
+        assert_eq!(entry.short_name(), short);
+
+        let entry = dir.next_file().unwrap().unwrap();
+        assert_eq!(entry.name(), "README.TXT");
+        assert!(!entry.has_long_name());
+
+        assert!(dir.next_file().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_next_file_checksum() {
+        let good = *b"GOOD~1  TXT";
+        let stale = *b"STALE   TXT";
+        let dev = device_with_root(&[
+            lfn_entry(0x41, "good name.txt", lfn_checksum(&good)),
+            short_entry(&good, 0x20),
+            // Fragment orphelin : sa somme de contrôle vise un autre nom court
+            lfn_entry(0x41, "old name.txt", lfn_checksum(&good)),
+            short_entry(&stale, 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let mut dir = fs.read_root_dir().unwrap();
+
+        let entry = dir.next_file().unwrap().unwrap();
+        assert_eq!(entry.name(), "good name.txt");
+
+        let entry = dir.next_file().unwrap().unwrap();
+        assert_eq!(entry.name(), "STALE.TXT");
+        assert!(!entry.has_long_name());
+    }
+
+    // Racine : SUB/ (cluster 3) et "Long Name.txt" ; SUB contient HELLO.TXT.
+    fn device_with_tree() -> MemDevice {
+        let mut dev = MemDevice::new(4);
+        for cluster in 2..=5 {
+            dev.set_fat(cluster, 0x0FFFFFFF);
+        }
+        let long_short = *b"LONGNA~1TXT";
+        dev.write_entries(2, &[
+            file_entry(b"SUB        ", 0x10, 3, 0),
+            lfn_entry(0x41, "Long Name.txt", lfn_checksum(&long_short)),
+            file_entry(&long_short, 0x20, 5, 42),
+        ]);
+        dev.write_entries(3, &[
+            file_entry(b".          ", 0x10, 3, 0),
+            file_entry(b"..         ", 0x10, 0, 0),
+            file_entry(b"HELLO   TXT", 0x20, 4, 5),
+        ]);
+        dev
+    }
+
+    fn root_names<D: BlockDevice>(fs: &Fat32Fs<'_, D>) -> Vec<String> {
+        fs.read_root_dir()
+            .unwrap()
+            .map(|entry| String::from(entry.unwrap().name()))
+            .collect()
+    }
+
+    #[test]
+    fn test_delete_entry() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        fs.delete_entry(0, "long name.txt").unwrap();
+        assert_eq!(root_names(&fs), ["SUB"]);
+        // Fragment LFN et entrée 8.3 marqués, sans déplacer les autres
+        let data = dev.data.borrow();
+        assert_eq!(data[2 * 512], b'S');
+        assert_eq!(data[2 * 512 + 32], 0xE5);
+        assert_eq!(data[2 * 512 + 64], 0xE5);
+        assert_eq!(&data[2 * 512 + 65..2 * 512 + 75], b"ONGNA~1TXT");
+        drop(data);
+
+        assert_eq!(fs.delete_entry(0, "long name.txt"), Err(Fat32Error::NotFound));
+        assert_eq!(fs.delete_entry(0, "SUB"), Err(Fat32Error::DirectoryNotEmpty));
+        fs.delete_entry(3, "hello.txt").unwrap();
+        fs.delete_entry(0, "SUB").unwrap();
+        assert!(root_names(&fs).is_empty());
+
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.delete_entry(0, "SUB"), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_set_attributes() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        let attributes = |path: &str| fs.metadata(path).unwrap().attributes;
+
+        // Le bit répertoire est conservé, même absent de `attrs`
+        fs.set_attributes(2, "SUB", Attributes(attr::HIDDEN | attr::SYSTEM)).unwrap();
+        assert_eq!(attributes("/SUB"), Attributes(attr::DIRECTORY | attr::HIDDEN | attr::SYSTEM));
+        // ... et ne peut pas être posé sur un fichier (nom long)
+        fs.set_attributes(2, "Long Name.txt", Attributes(attr::DIRECTORY | attr::READ_ONLY)).unwrap();
+        assert_eq!(attributes("/Long Name.txt"), Attributes(attr::READ_ONLY));
+        assert!(fs.open("/Long Name.txt").is_ok());
+
+        fs.set_archive(2, "long name.txt", true).unwrap();
+        fs.set_read_only(2, "LONGNA~1.TXT", false).unwrap();
+        assert_eq!(attributes("/Long Name.txt"), Attributes(attr::ARCHIVE));
+        fs.set_hidden(2, "SUB", false).unwrap();
+        fs.set_system(2, "SUB", false).unwrap();
+        assert_eq!(attributes("/SUB"), Attributes(attr::DIRECTORY));
+
+        assert_eq!(fs.set_read_only(2, "MISSING", true), Err(Fat32Error::NotFound));
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.set_hidden(2, "SUB", true), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_rename() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        let before = fs.metadata("/SUB/HELLO.TXT").unwrap();
+
+        // Nom 8.3 vers nom long : même chaîne, même taille, mêmes dates
+        fs.rename(3, "hello.txt", "Hello World Document.txt").unwrap();
+        assert!(!fs.exists("/SUB/HELLO.TXT").unwrap());
+        let after = fs.metadata("/SUB/Hello World Document.txt").unwrap();
+        assert_eq!(after, before);
+        let entry = fs.find_in_dir(3, "HELLOW~1.TXT").unwrap().unwrap();
+        assert!(entry.has_long_name());
+
+        // ... puis retour vers un nom 8.3, sans fragment LFN
+        fs.rename(3, "hello world document.txt", "HELLO.TXT").unwrap();
+        assert_eq!(fs.metadata("/SUB/HELLO.TXT").unwrap(), before);
+        assert!(!fs.find_in_dir(3, "HELLO.TXT").unwrap().unwrap().has_long_name());
+        assert!(!fs.exists("/SUB/Hello World Document.txt").unwrap());
+
+        // Changement de casse seul
+        fs.rename(2, "Long Name.txt", "LONG NAME.TXT").unwrap();
+        assert_eq!(fs.find_in_dir(2, "long name.txt").unwrap().unwrap().name(), "LONG NAME.TXT");
+
+        assert_eq!(fs.rename(2, "SUB", "long name.txt"), Err(Fat32Error::AlreadyExists));
+        assert_eq!(fs.rename(2, "MISSING", "OTHER"), Err(Fat32Error::NotFound));
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.rename(2, "SUB", "DIR"), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_move_entry() {
+        let dev = device_with_tree();
+        // Place pour le répertoire créé (cluster 6)
+        dev.data.borrow_mut().resize(512 * 8, 0);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        let dotdot = |dir: u32| {
+            let mut buf = [0u8; 512];
+            fs.read_cluster(dir, &mut buf).unwrap();
+            assert_eq!(&buf[32..43], b"..         ");
+            u32::from(u16::from_le_bytes([buf[58], buf[59]]))
+        };
+
+        // Fichier de la racine vers SUB : même chaîne, même taille
+        let before = fs.metadata("/Long Name.txt").unwrap();
+        fs.move_entry(0, "long name.txt", 3, "Moved File.txt").unwrap();
+        assert!(!fs.exists("/Long Name.txt").unwrap());
+        assert_eq!(fs.metadata("/SUB/Moved File.txt").unwrap(), before);
+
+        // Sous-répertoire déplacé : `..` pointe vers le nouveau parent
+        let new_dir = fs.create_dir(0, "NEW").unwrap();
+        fs.move_entry(0, "SUB", new_dir, "Sub Dir").unwrap();
+        assert_eq!(dotdot(3), new_dir);
+        assert_eq!(fs.open("/NEW/Sub Dir/HELLO.TXT").unwrap().size(), 5);
+        // ... puis revient à la racine (`..` à 0)
+        fs.move_entry(new_dir, "sub dir", 0, "SUB").unwrap();
+        assert_eq!(dotdot(3), 0);
+        assert!(fs.exists("/SUB/Moved File.txt").unwrap());
+
+        // Pas de déplacement d'un répertoire dans lui-même ou un descendant
+        fs.move_entry(0, "NEW", 3, "NEW").unwrap();
+        assert_eq!(fs.move_entry(0, "SUB", new_dir, "SUB"), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.move_entry(0, "SUB", 3, "SUB"), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.move_entry(3, "moved file.txt", 0, "sub"), Err(Fat32Error::AlreadyExists));
+        assert_eq!(fs.move_entry(3, "NEW", 0, "SUB"), Err(Fat32Error::AlreadyExists));
+        assert_eq!(fs.move_entry(0, "MISSING", 3, "X"), Err(Fat32Error::NotFound));
+        assert!(fs.exists("/SUB/NEW").unwrap());
+    }
+
+    #[test]
+    fn test_move_entry_dotdot_failure() {
+        let inner = device_with_tree();
+        inner.data.borrow_mut().resize(512 * 8, 0);
+        // Le cluster de SUB (secteur 3) ne peut pas être écrit
+        let dev = FailingWrites { inner, fail_lba: 3 };
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        let new_dir = fs.create_dir(0, "NEW").unwrap();
+
+        assert_eq!(fs.move_entry(0, "SUB", new_dir, "SUB"), Err(Fat32Error::IoError));
+        assert!(fs.exists("/SUB/HELLO.TXT").unwrap());
+        assert!(!fs.exists("/NEW/SUB").unwrap());
+    }
+
+    #[test]
+    fn test_move_entry_without_dotdot() {
+        let dev = device_with_tree();
+        dev.data.borrow_mut().resize(512 * 8, 0);
+        // La deuxième entrée de SUB (cluster 3) n'est pas `..`
+        dev.data.borrow_mut()[3 * 512 + 32..3 * 512 + 64]
+            .copy_from_slice(&short_entry(b"OTHER   TXT", 0x20));
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        let new_dir = fs.create_dir(0, "NEW").unwrap();
+
+        assert_eq!(
+            fs.move_entry(0, "SUB", new_dir, "SUB"),
+            Err(Fat32Error::CorruptedDirectory)
+        );
+        assert!(fs.exists("/SUB").unwrap());
+        assert!(!fs.exists("/NEW/SUB").unwrap());
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, END_OF_CHAIN);
+        for (cluster, next) in [(3, 4), (4, 5), (5, END_OF_CHAIN), (6, END_OF_CHAIN)] {
+            dev.set_fat(cluster, next);
+        }
+        // FSInfo au secteur 9
+        dev.write_data(9, &fsinfo_sector(100, 7));
+        dev.write_entries(2, &[
+            file_entry(b"DATA    BIN", 0x20, 3, 1300),
+            file_entry(b"SMALL   BIN", 0x20, 6, 10),
+        ]);
+        let geom = Fat32Geometry {
+            fs_info_sector: 9,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+        let size = |name: &str| fs.find_in_dir(2, name).unwrap().unwrap().file_size();
+
+        // 3 clusters ramenés à 1 : le cluster 3 devient la fin de la chaîne
+        fs.truncate(2, "DATA.BIN", 512).unwrap();
+        assert_eq!(size("DATA.BIN"), 512);
+        assert!(fs.read_fat_entry(3).unwrap().is_end());
+        assert!(fs.read_fat_entry(4).unwrap().is_free());
+        assert!(fs.read_fat_entry(5).unwrap().is_free());
+        assert_eq!(fs.read_fsinfo().unwrap().free_clusters(), Some(102));
+
+        // Même nombre de clusters : seule la taille change
+        fs.truncate(2, "DATA.BIN", 100).unwrap();
+        assert_eq!(size("DATA.BIN"), 100);
+        assert!(fs.read_fat_entry(3).unwrap().is_end());
+
+        // Taille nulle : chaîne libérée, premier cluster à 0
+        fs.truncate(2, "SMALL.BIN", 0).unwrap();
+        let entry = fs.find_in_dir(2, "SMALL.BIN").unwrap().unwrap();
+        assert_eq!((entry.file_size(), entry.first_cluster()), (0, 0));
+        assert!(fs.read_fat_entry(6).unwrap().is_free());
+        assert_eq!(fs.read_fsinfo().unwrap().free_clusters(), Some(103));
+
+        assert_eq!(fs.truncate(2, "DATA.BIN", 200), Err(Fat32Error::Unsupported));
+        assert_eq!(fs.truncate(2, "MISSING", 0), Err(Fat32Error::NotFound));
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.truncate(2, "DATA.BIN", 0), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_zero_on_free() {
+        let cluster_data = |dev: &MemDevice, cluster: usize| dev.data.borrow()[cluster * 512..][..512].to_vec();
+
+        // Par défaut, les données restent en place
+        let mut dev = device_with_tree();
+        dev.write_data(5, &[0xAA; 512]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        fs.truncate(0, "Long Name.txt", 0).unwrap();
+        fs.delete_entry(0, "Long Name.txt").unwrap();
+        assert_eq!(cluster_data(&dev, 5), [0xAA; 512]);
+
+        let mut dev = device_with_tree();
+        dev.write_data(4, b"Hello");
+        dev.write_data(5, &[0xAA; 512]);
+        let mut fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        fs.set_zero_on_free(true);
+        fs.truncate(0, "Long Name.txt", 0).unwrap();
+        fs.delete_entry(0, "Long Name.txt").unwrap();
+        assert!(fs.read_fat_entry(5).unwrap().is_free());
+        let mut buf = [0xFFu8; 512];
+        fs.read_cluster(5, &mut buf).unwrap();
+        assert_eq!(buf, [0u8; 512]);
+        // Les autres clusters ne sont pas touchés
+        assert_eq!(cluster_data(&dev, 4)[..5], *b"Hello");
+    }
+
+    #[test]
+    fn test_locate() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        // Nom long : un fragment LFN puis l'entrée 8.3
+        let location = fs.locate(0, "long name.txt").unwrap().unwrap();
+        assert_eq!(location, EntryLocation {
+            cluster: 2,
+            offset: 64,
+            first_slot_cluster: 2,
+            first_slot_offset: 32,
+            slots: 2,
+        });
+        assert_eq!(fs.locate(2, "LONGNA~1.TXT").unwrap(), Some(location));
+
+        let hello = fs.locate(3, "hello.txt").unwrap().unwrap();
+        assert_eq!((hello.cluster, hello.offset, hello.slots), (3, 64, 1));
+        assert_eq!((hello.first_slot_cluster, hello.first_slot_offset), (3, 64));
+        assert_eq!(fs.locate(0, "missing"), Ok(None));
+
+        // Même emplacement depuis l'itérateur
+        let mut dir = fs.read_root_dir().unwrap();
+        dir.next_file().unwrap();
+        assert_eq!(dir.location().offset, 0);
+        dir.next_file().unwrap();
+        assert_eq!(dir.location(), location);
+    }
+
+    #[test]
+    fn test_compact_dir() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 5);
+        dev.set_fat(4, END_OF_CHAIN);
+        dev.set_fat(5, END_OF_CHAIN);
+        let deleted = |mut entry: [u8; 32]| {
+            entry[0] = DELETED_ENTRY;
+            entry
+        };
+        let long_short = *b"LONGNA~1TXT";
+        let gone_short = *b"GONENA~1TXT";
+
+        // Racine sur 3 clusters : entrées vivantes et supprimées mêlées
+        let mut root = [deleted(short_entry(b"OLD     TXT", 0x20)); 16];
+        root[0] = file_entry(b"FIRST   TXT", 0x20, 0, 0);
+        root[3] = lfn_entry(0x41, "Long Name.txt", lfn_checksum(&long_short));
+        root[4] = file_entry(&long_short, 0x20, 0, 0);
+        root[5] = deleted(lfn_entry(0x41, "Gone Name.txt", lfn_checksum(&gone_short)));
+        root[6] = deleted(file_entry(&gone_short, 0x20, 0, 0));
+        root[15] = file_entry(b"SUB        ", 0x10, 4, 0);
+        dev.write_entries(2, &root);
+        dev.write_entries(3, &[deleted(short_entry(b"OLD2    TXT", 0x20)), short_entry(b"LAST    TXT", 0x20)]);
+        dev.write_entries(5, &[deleted(short_entry(b"OLD3    TXT", 0x20))]);
+        dev.write_entries(4, &[
+            file_entry(b".          ", 0x10, 4, 0),
+            file_entry(b"..         ", 0x10, 0, 0),
+            deleted(short_entry(b"OLD4    TXT", 0x20)),
+            short_entry(b"KEPT    TXT", 0x20),
+        ]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        assert_eq!(fs.compact_dir(0).unwrap(), 2);
+        assert_eq!(root_names(&fs), ["FIRST.TXT", "Long Name.txt", "SUB", "LAST.TXT"]);
+        assert!(fs.read_fat_entry(2).unwrap().is_end());
+        assert!(fs.read_fat_entry(3).unwrap().is_free());
+        assert!(fs.read_fat_entry(5).unwrap().is_free());
+        let data = dev.data.borrow();
+        // Fragment LFN toujours juste avant son entrée 8.3, fin remplie de zéros
+        assert_eq!(data[2 * 512 + 32], 0x41);
+        assert_eq!(&data[2 * 512 + 64..2 * 512 + 75], &long_short);
+        assert!(data[2 * 512 + 5 * 32..3 * 512].iter().all(|&b| b == 0));
+        drop(data);
+
+        // `.` et `..` restent en tête d'un sous-répertoire
+        assert_eq!(fs.compact_dir(4).unwrap(), 0);
+        let mut dir = fs.read_dir(4).unwrap().include_dot_entries(true);
+        let names: Vec<_> = core::iter::from_fn(|| dir.next_file().unwrap())
+            .map(|entry| String::from(entry.name()))
+            .collect();
+        assert_eq!(names, [".", "..", "KEPT.TXT"]);
+
+        // Rien à retirer : le répertoire est inchangé
+        assert_eq!(fs.compact_dir(0).unwrap(), 0);
+        assert_eq!(root_names(&fs), ["FIRST.TXT", "Long Name.txt", "SUB", "LAST.TXT"]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.compact_dir(0), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_raw_entries() {
+        let mut dev = device_with_tree();
+        dev.set_fat(2, 6);
+        dev.set_fat(6, END_OF_CHAIN);
+        dev.data.borrow_mut().resize(512 * 8, 0);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        fs.delete_entry(3, "HELLO.TXT").unwrap();
+        fs.delete_entry(0, "SUB").unwrap();
+
+        // Entrée supprimée et fragment LFN compris, avec leur emplacement
+        let slots: Vec<_> = fs.raw_entries(0).unwrap().map(Result::unwrap).collect();
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0].entry.name[0], 0xE5);
+        assert!(slots[1].entry.is_lfn());
+        assert_eq!(slots[2].entry.name, *b"LONGNA~1TXT");
+        assert_eq!((slots[2].cluster, slots[2].offset), (2, 64));
+
+        // Jusqu'à la fin de la chaîne : 2 clusters de 16 entrées
+        let all: Vec<_> = fs.raw_entries(0).unwrap().include_unused(true).map(Result::unwrap).collect();
+        assert_eq!(all.len(), 32);
+        assert!(all[3..].iter().all(|slot| slot.entry.name[0] == 0x00));
+        assert_eq!((all[31].cluster, all[31].offset), (6, 480));
+
+        // `.` et `..` ne sont pas filtrés
+        let sub: Vec<_> = fs.raw_entries(3).unwrap().map(|slot| slot.unwrap().entry.name).collect();
+        assert_eq!(sub, [*b".          ", *b"..         ", *b"\xE5ELLO   TXT"]);
+    }
+
+    #[test]
+    fn test_undelete_candidates() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        fs.delete_entry(3, "hello.txt").unwrap();
+        fs.delete_entry(0, "SUB").unwrap();
+        fs.delete_entry(0, "Long Name.txt").unwrap();
+        let mut out = [DeletedEntry::default(); 4];
+
+        assert_eq!(fs.undelete_candidates(0, &mut out).unwrap(), 2);
+        // Sans fragment LFN, le premier caractère est perdu
+        assert_eq!(out[0].entry.name(), "_UB");
+        assert!(out[0].entry.is_dir() && !out[0].first_char_recovered);
+        assert_eq!(out[0].entry.first_cluster(), 3);
+        // Nom long complet, et premier caractère du nom 8.3 retrouvé
+        let long = &out[1];
+        assert_eq!(long.entry.name(), "Long Name.txt");
+        assert!(long.first_char_recovered);
+        assert_eq!(long.entry.raw().name, *b"LONGNA~1TXT");
+        assert_eq!((long.entry.first_cluster(), long.entry.file_size()), (5, 42));
+        assert_eq!((long.location.first_slot_offset, long.location.offset, long.location.slots), (32, 64, 2));
+
+        assert_eq!(fs.undelete_candidates(3, &mut out).unwrap(), 1);
+        assert_eq!(out[0].entry.name(), "_ELLO.TXT");
+        assert_eq!(out[0].entry.file_size(), 5);
+
+        // `out` trop petit : le total est quand même retourné
+        assert_eq!(fs.undelete_candidates(0, &mut out[..1]).unwrap(), 2);
+        assert_eq!(fs.undelete_candidates(0, &mut []).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_delete_entry_across_clusters() {
+        // 15 fichiers au cluster 2, puis un nom long dont le fragment occupe
+        // la dernière entrée du cluster 2 et l'entrée 8.3 la première du 3
+        let mut dev = MemDevice::new(2);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 0x0FFFFFFF);
+        let short = *b"SPLIT~1 TXT";
+        let mut entries = [[0u8; 32]; 16];
+        for (i, entry) in entries.iter_mut().take(15).enumerate() {
+            *entry = short_entry(&numbered_name(i), 0x20);
+        }
+        entries[15] = lfn_entry(0x41, "split.txt", lfn_checksum(&short));
+        dev.write_entries(2, &entries);
+        dev.write_entries(3, &[file_entry(&short, 0x20, 0, 0), short_entry(b"LAST    TXT", 0x20)]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        fs.delete_entry(0, "split.txt").unwrap();
+        let names = root_names(&fs);
+        assert_eq!(names.len(), 16);
+        assert_eq!(names[15], "LAST.TXT");
+        assert_eq!(dev.data.borrow()[2 * 512 + 15 * 32], 0xE5);
+        assert_eq!(dev.data.borrow()[3 * 512], 0xE5);
+    }
+
+    #[test]
+    fn test_create_file() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.write_entries(2, &[short_entry(b"OLD     TXT", 0x20), short_entry(b"KEEP    TXT", 0x20)]);
+        dev.data.get_mut()[2 * 512] = 0xE5;
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        // L'entrée supprimée est réutilisée
+        let entry = fs.create_file(0, "notes.txt").unwrap();
+        assert_eq!(entry.name(), "notes.txt");
+        assert_eq!(entry.short_name(), *b"NOTES   TXT");
+        assert!(entry.attributes().is_archive());
+        assert_eq!((entry.first_cluster(), entry.file_size()), (0, 0));
+        assert_eq!(&dev.data.borrow()[2 * 512..2 * 512 + 11], b"NOTES   TXT");
+
+        fs.create_file(0, "DATA").unwrap();
+        assert_eq!(root_names(&fs), ["notes.txt", "KEEP.TXT", "DATA"]);
+        assert_eq!(fs.open("/NOTES.TXT").unwrap().size(), 0);
+
+        assert_eq!(fs.create_file(0, "Notes.TXT").map(|_| ()), Err(Fat32Error::AlreadyExists));
+        assert_eq!(fs.create_file(0, "a/b").map(|_| ()), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.create_file(0, "").map(|_| ()), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.create_file(0, "a?b").map(|_| ()), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.create_file(0, &"x".repeat(256)).map(|_| ()), Err(Fat32Error::NameTooLong));
+
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.create_file(0, "NEW").map(|_| ()), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_create_file_timestamps() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 0x0FFFFFFF);
+        let clock = FixedClock(Timestamp {
+            year: 2024,
+            month: 5,
+            day: 17,
+            hour: 9,
+            minute: 30,
+            second: 15,
+            centiseconds: 40,
+        });
+        let mut fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        // Sans horloge : début de l'ère DOS
+        let entry = fs.create_file(0, "EPOCH").unwrap();
+        assert_eq!(entry.raw().created(), DOS_EPOCH);
+
+        fs.set_clock(&clock);
+        let entry = fs.create_file(0, "NOW").unwrap();
+        assert_eq!(entry.raw().created(), clock.0);
+        assert_eq!(entry.raw().modified(), Timestamp { second: 14, centiseconds: 0, ..clock.0 });
+        assert_eq!(entry.raw().accessed(), (2024, 5, 17));
+        let stored = fs.find_in_dir(0, "NOW").unwrap().unwrap();
+        assert_eq!(stored.raw().created(), clock.0);
+    }
+
+    #[test]
+    fn test_create_file_long_name() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.write_entries(2, &[short_entry(b"LONGFI~1TXT", 0x20)]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let entry = fs.create_file(0, "Long File Name.txt").unwrap();
+        assert_eq!(entry.name(), "Long File Name.txt");
+        assert!(entry.has_long_name());
+        assert_eq!(entry.short_name(), *b"LONGFI~2TXT");
+
+        // Casse mixte : nom court exact, nom long conservé
+        let entry = fs.create_file(0, "ReadMe.md").unwrap();
+        assert_eq!(entry.short_name(), *b"README  MD ");
+        assert_eq!(entry.name(), "ReadMe.md");
+
+        assert_eq!(root_names(&fs), ["LONGFI~1.TXT", "Long File Name.txt", "ReadMe.md"]);
+        let data = dev.data.borrow();
+        assert_eq!(data[2 * 512 + 32], LFN_LAST_ENTRY | 2);
+        assert_eq!(data[2 * 512 + 64], 1);
+        assert_eq!(&data[2 * 512 + 96..2 * 512 + 107], b"LONGFI~2TXT");
+        drop(data);
+
+        assert!(fs.find_in_dir(0, "LONGFI~2.TXT").unwrap().is_some());
+        assert_eq!(fs.create_file(0, "long file NAME.txt").map(|_| ()), Err(Fat32Error::AlreadyExists));
+        fs.delete_entry(0, "Long File Name.txt").unwrap();
+        assert_eq!(root_names(&fs), ["LONGFI~1.TXT", "ReadMe.md"]);
+    }
+
+    #[test]
+    fn test_create_file_lfn_spans_clusters() {
+        // 15 entrées occupées : les fragments LFN débordent sur un nouveau cluster
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 0x0FFFFFFF);
+        fill_dir(&mut dev, 2, 15);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        fs.create_file(0, "a rather long file name.txt").unwrap();
+        assert_eq!(fs.chain_length(2).unwrap(), 2);
+        let names = root_names(&fs);
+        assert_eq!(names.len(), 16);
+        assert_eq!(names[15], "a rather long file name.txt");
+    }
+
+    #[test]
+    fn test_create_dir() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        // Données résiduelles dans les clusters qui seront alloués
+        dev.write_data(3, &[0xAA; 512]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let cluster = fs.create_dir(0, "Photos").unwrap();
+        assert_eq!(cluster, 3);
+        let entry = fs.find_in_dir(0, "photos").unwrap().unwrap();
+        assert!(entry.is_dir());
+        assert_eq!(entry.first_cluster(), cluster);
+
+        let dots: Vec<(String, u32)> = fs
+            .read_dir(cluster)
+            .unwrap()
+            .include_dot_entries(true)
+            .map(|entry| entry.map(|e| (String::from(e.name()), e.first_cluster())))
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(dots, [(String::from("."), cluster), (String::from(".."), 0)]);
+        assert_eq!(fs.read_dir(cluster).unwrap().count(), 0);
+
+        // `..` d'un sous-répertoire de sous-répertoire : son parent
+        let nested = fs.create_dir(cluster, "2024").unwrap();
+        assert_eq!(fs.parent_cluster(nested).unwrap(), Some(cluster));
+        assert_eq!(fs.open("/Photos/2024").map(|_| ()), Err(Fat32Error::IsDirectory));
+    }
+
+    #[test]
+    fn test_create_dir_all() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        // Chemin entièrement absent
+        let deep = fs.create_dir_all("/music/rock/1975").unwrap();
+        let rock = fs.find_in_dir(0, "music").unwrap().unwrap().first_cluster();
+        let rock = fs.find_in_dir(rock, "rock").unwrap().unwrap().first_cluster();
+        assert_eq!(fs.find_in_dir(rock, "1975").unwrap().unwrap().first_cluster(), deep);
+        assert_eq!(fs.parent_cluster(deep).unwrap(), Some(rock));
+
+        // Chemin existant en partie : seul le dernier niveau est créé
+        let free = fs.free_clusters().unwrap();
+        let jazz = fs.create_dir_all("music/Jazz").unwrap();
+        assert_eq!(fs.free_clusters().unwrap(), free - 1);
+        assert_eq!(fs.parent_cluster(jazz).unwrap(), fs.find_in_dir(0, "MUSIC").unwrap().map(|e| e.first_cluster()));
+
+        // Chemin existant : rien n'est créé, même en lecture seule
+        assert_eq!(fs.create_dir_all("/music/rock/1975").unwrap(), deep);
+        assert_eq!(fs.create_dir_all("/").unwrap(), 2);
+        let read_only = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(read_only.create_dir_all("/music/rock").unwrap(), rock);
+        assert_eq!(read_only.create_dir_all("/music/pop"), Err(Fat32Error::ReadOnly));
+
+        fs.create_file(rock, "notes.txt").unwrap();
+        assert_eq!(fs.create_dir_all("/music/rock/notes.txt/x"), Err(Fat32Error::IsNotDirectory));
+        assert_eq!(fs.create_dir_all("/music//x"), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.create_dir_all("/music/../x"), Err(Fat32Error::InvalidPath));
+    }
+
+    #[test]
+    fn test_remove_dir() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        let free = fs.free_clusters().unwrap();
+
+        let dir = fs.create_dir(0, "Empty Folder").unwrap();
+        // Une entrée supprimée ne rend pas le répertoire non vide
+        fs.create_file(dir, "GONE.TXT").unwrap();
+        fs.delete_entry(dir, "GONE.TXT").unwrap();
+
+        fs.remove_dir(0, "empty folder").unwrap();
+        assert!(root_names(&fs).is_empty());
+        assert_eq!(fs.free_clusters().unwrap(), free);
+        assert!(fs.read_fat_entry(dir).unwrap().is_free());
+        assert_eq!(fs.remove_dir(0, "Empty Folder"), Err(Fat32Error::NotFound));
+    }
+
+    #[test]
+    fn test_remove_dir_not_empty() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let dir = fs.create_dir(0, "FULL").unwrap();
+        fs.create_dir(dir, "INNER").unwrap();
+        fs.create_file(0, "FILE.TXT").unwrap();
+        assert_eq!(fs.remove_dir(0, "FULL"), Err(Fat32Error::DirectoryNotEmpty));
+        assert_eq!(fs.remove_dir(0, "FILE.TXT"), Err(Fat32Error::IsNotDirectory));
+        assert_eq!(root_names(&fs), ["FULL", "FILE.TXT"]);
+
+        fs.remove_dir(dir, "INNER").unwrap();
+        fs.remove_dir(0, "FULL").unwrap();
+        assert_eq!(root_names(&fs), ["FILE.TXT"]);
+
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.remove_dir(0, "FULL"), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_create_dir_collision_keeps_clusters() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.write_entries(2, &[short_entry(b"TAKEN      ", 0x20)]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        let free = fs.free_clusters().unwrap();
+        assert_eq!(fs.create_dir(0, "taken"), Err(Fat32Error::AlreadyExists));
+        assert_eq!(fs.create_dir(0, "bad:name"), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.free_clusters().unwrap(), free);
+
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.create_dir(0, "NEW"), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_create_file_grows_full_directory() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 0x0FFFFFFF);
+        fill_dir(&mut dev, 2, 16);
+        // Données résiduelles dans le cluster qui sera ajouté
+        dev.write_data(3, &[0xAA; 512]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        fs.create_file(0, "EXTRA.BIN").unwrap();
+        assert_eq!(fs.chain_length(2).unwrap(), 2);
+        let names = root_names(&fs);
+        assert_eq!(names.len(), 17);
+        assert_eq!(names[16], "EXTRA.BIN");
+        // Le nouveau cluster est vidé : la fin du répertoire suit la nouvelle entrée
+        assert!(dev.data.borrow()[3 * 512 + 32..4 * 512].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_append_dir_cluster() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, END_OF_CHAIN);
+        dev.set_fat(3, END_OF_CHAIN);
+        dev.write_data(4, &[0xAA; 512]);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        // Premier cluster libre après le dernier du répertoire, vidé puis relié
+        assert_eq!(fs.append_dir_cluster(2).unwrap(), 4);
+        assert_eq!(fs.read_fat_entry(2).unwrap().next_cluster(), Some(4));
+        assert!(fs.read_fat_entry(4).unwrap().is_end());
+        assert!(dev.data.borrow()[4 * 512..5 * 512].iter().all(|&b| b == 0));
+
+        // Seul le dernier cluster d'une chaîne peut être prolongé
+        assert_eq!(fs.append_dir_cluster(2), Err(Fat32Error::InvalidCluster(2)));
+    }
+
+    #[test]
+    fn test_generate_short_name() {
+        let dev = device_with_root(&[
+            short_entry(b"LONGFI~1HTM", 0x20),
+            short_entry(b"LONGFI~3HTM", 0x20),
+            short_entry(b"README  TXT", 0x20),
+            lfn_entry(0x41, "LONGFI~2.HTM", 0),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let generate = |long: &str| generate_short_name(long, &mut fs.read_root_dir().unwrap());
+
+        // Plus petite queue libre ; un fragment LFN n'occupe aucun nom court
+        assert_eq!(&generate("Long File Name.html").unwrap(), b"LONGFI~2HTM");
+        assert_eq!(&generate("Long Filename.htm").unwrap(), b"LONGFI~2HTM");
+        // Nom exact libre, ou déjà pris
+        assert_eq!(&generate("Makefile").unwrap(), b"MAKEFILE   ");
+        assert_eq!(&generate("ReadMe.txt").unwrap(), b"README~1TXT");
+        assert_eq!(generate("bad|name"), Err(Fat32Error::InvalidPath));
+    }
+
+    #[test]
+    fn test_generate_short_name_wider_tail() {
+        let mut entries = [[0u8; 32]; 9];
+        for (i, entry) in entries.iter_mut().enumerate() {
+            let mut name = *b"DOCUME~1TXT";
+            name[7] += i as u8;
+            *entry = short_entry(&name, 0x20);
+        }
+        let dev = device_with_root(&entries);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let short = generate_short_name("documentation.txt", &mut fs.read_root_dir().unwrap()).unwrap();
+        assert_eq!(&short, b"DOCUM~10TXT");
+    }
+
+    #[test]
+    fn test_open_nested_file() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let file = fs.open("/SUB/HELLO.TXT").unwrap();
+        assert_eq!(file.first_cluster(), 4);
+        assert_eq!(file.size(), 5);
+
+        // Casse ignorée, "/" initial facultatif, nom long accepté
+        assert_eq!(fs.open("sub/hello.txt").unwrap(), file);
+        assert_eq!(fs.open("long name.TXT").unwrap().size(), 42);
+        assert_eq!(fs.open("LONGNA~1.TXT").unwrap().first_cluster(), 5);
+    }
+
+    #[test]
+    fn test_find_in_dir() {
+        let short = *b"README~1TXT";
+        let dev = device_with_root(&[
+            short_entry(b"OTHER   TXT", 0x20),
+            lfn_entry(0x41, "ReadMe.txt", lfn_checksum(&short)),
+            file_entry(&short, 0x20, 7, 10),
+            file_entry(b"README  TXT", 0x20, 8, 20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        // Le nom long correspond avant l'entrée 8.3 homonyme
+        let entry = fs.find_in_dir(0, "README.TXT").unwrap().unwrap();
+        assert_eq!(entry.name(), "ReadMe.txt");
+        assert_eq!(entry.first_cluster(), 7);
+        assert_eq!(fs.find_in_dir(2, "readme~1.txt").unwrap().unwrap().file_size(), 10);
+        assert!(fs.find_in_dir(0, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_find_in_dir_latin1_case() {
+        let short = *b"CAFE~1  TXT";
+        let dev = device_with_root(&[
+            lfn_entry(0x41, "Café.txt", lfn_checksum(&short)),
+            file_entry(&short, 0x20, 7, 10),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        assert_eq!(fs.find_in_dir(0, "CAFÉ.TXT").unwrap().unwrap().first_cluster(), 7);
+        assert_eq!(fs.find_in_dir(0, "café.txt").unwrap().unwrap().first_cluster(), 7);
+        assert!(fs.find_in_dir(0, "cafe.txt").unwrap().is_none());
+        assert_eq!(fs.open("/CAFÉ.txt").unwrap().size(), 10);
+    }
+
+    #[test]
+    fn test_subdirectory_hides_dot_entries() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let names = |include| -> Vec<String> {
+            fs.read_dir(3)
+                .unwrap()
+                .include_dot_entries(include)
+                .map(|entry| String::from(entry.unwrap().name()))
+                .collect()
+        };
+        assert_eq!(names(false), ["HELLO.TXT"]);
+        assert_eq!(names(true), [".", "..", "HELLO.TXT"]);
+
+        let mut dir = fs.read_dir(3).unwrap();
+        assert_eq!(dir.next_entry().unwrap().unwrap().name, *b"HELLO   TXT");
+
+        assert_eq!(fs.parent_cluster(3), Ok(Some(2)));
+        assert_eq!(fs.parent_cluster(2), Ok(None));
+        assert_eq!(fs.parent_cluster(5), Err(Fat32Error::CorruptedDirectory));
+    }
+
+    #[test]
+    fn test_walk() {
+        let mut dev = device_with_tree();
+        // SUB/DEEP (cluster 6) contient NOTE.TXT
+        dev.data.get_mut().resize(512 * 7, 0);
+        dev.set_fat(6, 0x0FFFFFFF);
+        dev.write_entries(3, &[
+            file_entry(b".          ", 0x10, 3, 0),
+            file_entry(b"..         ", 0x10, 0, 0),
+            file_entry(b"HELLO   TXT", 0x20, 4, 5),
+            file_entry(b"DEEP       ", 0x10, 6, 0),
+        ]);
+        dev.write_entries(6, &[
+            file_entry(b".          ", 0x10, 6, 0),
+            file_entry(b"..         ", 0x10, 3, 0),
+            file_entry(b"NOTE    TXT", 0x20, 0, 0),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let walk = |depth_limit, prune: &str| {
+            let mut seen = Vec::new();
+            fs.walk(0, depth_limit, |path, entry| {
+                let mut full = String::new();
+                for name in path.names() {
+                    full.push_str(name);
+                    full.push('/');
+                }
+                full.push_str(entry.name());
+                seen.push(full);
+                if entry.name() == prune {
+                    Ok(WalkAction::SkipDir)
+                } else {
+                    Ok(WalkAction::Continue)
+                }
+            })
+            .unwrap();
+            seen
+        };
+
+        assert_eq!(
+            walk(8, ""),
+            ["SUB", "SUB/HELLO.TXT", "SUB/DEEP", "SUB/DEEP/NOTE.TXT", "Long Name.txt"]
+        );
+        assert_eq!(walk(1, ""), ["SUB", "SUB/HELLO.TXT", "SUB/DEEP", "Long Name.txt"]);
+        assert_eq!(walk(0, ""), ["SUB", "Long Name.txt"]);
+        assert_eq!(walk(8, "DEEP"), ["SUB", "SUB/HELLO.TXT", "SUB/DEEP", "Long Name.txt"]);
+
+        // Arrêt anticipé
+        let mut count = 0;
+        fs.walk(0, 8, |_, _| {
+            count += 1;
+            Ok(if count == 2 { WalkAction::Stop } else { WalkAction::Continue })
+        })
+        .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let mut dev = device_with_tree();
+        // SUB/DEEP (cluster 6) contient un fichier de 1000 octets
+        dev.data.get_mut().resize(512 * 8, 0);
+        dev.set_fat(6, 0x0FFFFFFF);
+        dev.write_entries(3, &[
+            file_entry(b".          ", 0x10, 3, 0),
+            file_entry(b"..         ", 0x10, 0, 0),
+            file_entry(b"HELLO   TXT", 0x20, 4, 5),
+            file_entry(b"DEEP       ", 0x10, 6, 0),
+        ]);
+        dev.write_entries(6, &[
+            file_entry(b".          ", 0x10, 6, 0),
+            file_entry(b"..         ", 0x10, 3, 0),
+            file_entry(b"BIG     BIN", 0x20, 0, 1000),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        assert_eq!(fs.dir_size(0, 8).unwrap(), 42 + 5 + 1000);
+        assert_eq!(fs.dir_size(3, 8).unwrap(), 5 + 1000);
+        // Limite de profondeur : les sous-répertoires plus profonds sont ignorés
+        assert_eq!(fs.dir_size(0, 0).unwrap(), 42);
+        assert_eq!(fs.dir_size(0, 1).unwrap(), 42 + 5);
+    }
+
+    #[test]
+    fn test_walk_self_referencing_directory() {
+        // LOOP pointe vers la racine elle-même (cluster 2) : la limite de
+        // profondeur arrête la descente
+        let dev = device_with_root(&[file_entry(b"LOOP       ", 0x10, 2, 0)]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut visits = 0;
+        fs.walk(0, 1000, |path, _| {
+            assert!(path.len() <= MAX_WALK_DEPTH);
+            visits += 1;
+            Ok(WalkAction::Continue)
+        })
+        .unwrap();
+        assert_eq!(visits, MAX_WALK_DEPTH + 1);
+    }
+
+    #[test]
+    fn test_walk_resumes_parent_in_next_cluster() {
+        // SUB est la dernière entrée du cluster 2 : le parcours de la racine
+        // reprend au début du cluster 3 après celui de SUB (cluster 4)
+        let mut dev = MemDevice::new(3);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 0x0FFFFFFF);
+        dev.set_fat(4, 0x0FFFFFFF);
+        fill_dir(&mut dev, 2, 15);
+        dev.data.get_mut()[2 * 512 + 15 * 32..3 * 512]
+            .copy_from_slice(&file_entry(b"SUB        ", 0x10, 4, 0));
+        dev.write_entries(3, &[short_entry(b"LAST    TXT", 0x20)]);
+        dev.write_entries(4, &[
+            file_entry(b".          ", 0x10, 4, 0),
+            file_entry(b"..         ", 0x10, 0, 0),
+            short_entry(b"INNER   TXT", 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut seen = Vec::new();
+        fs.walk(0, 8, |path, entry| {
+            if entry.name() == "INNER.TXT" {
+                assert_eq!((path.name(0), path.cluster(0)), (Some("SUB"), Some(4)));
+                assert_eq!(path.name(1), None);
+            }
+            seen.push(String::from(entry.name()));
+            Ok(WalkAction::Continue)
+        })
+        .unwrap();
+        assert_eq!(seen.len(), 18);
+        assert_eq!(seen[14..], ["F14.TXT", "SUB", "INNER.TXT", "LAST.TXT"]);
+    }
+
+    #[test]
+    fn test_walk_path_buffer() {
+        let mut path = WalkPath::new();
+        assert!(path.is_empty());
+        path.push("docs", 3).unwrap();
+        path.push("été", 7).unwrap();
+        assert_eq!(path.names().collect::<Vec<_>>(), ["docs", "été"]);
+        assert_eq!(path.cluster(1), Some(7));
+        assert!(path.pop());
+        assert_eq!(path.len(), 1);
+        assert_eq!(path.cluster(1), None);
+
+        // Le buffer des noms est partagé par tous les niveaux
+        let long = "x".repeat(MAX_WALK_PATH - 4);
+        assert_eq!(path.push(&long, 5), Ok(()));
+        assert_eq!(path.push("y", 6), Err(Fat32Error::NameTooLong));
+        assert!(path.pop() && path.pop() && !path.pop());
+    }
+
+    #[test]
+    fn test_open_errors() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        assert_eq!(fs.open("/SUB/MISSING.TXT"), Err(Fat32Error::NotFound));
+        assert_eq!(fs.open("/SUB"), Err(Fat32Error::IsDirectory));
+        assert_eq!(fs.open("/LONGNA~1.TXT/X"), Err(Fat32Error::IsNotDirectory));
+        assert_eq!(fs.open("SUB//HELLO.TXT"), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.open("./SUB/HELLO.TXT"), Err(Fat32Error::InvalidPath));
+        assert_eq!(fs.open(""), Err(Fat32Error::InvalidPath));
+    }
+
+    #[test]
+    fn test_find_matching() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let mut out = [DirEntry::default(); 4];
+
+        assert_eq!(fs.find_matching(2, "*.TXT", &mut out).unwrap(), 1);
+        assert_eq!(out[0].name(), "Long Name.txt");
+        assert_eq!(fs.find_matching(2, "*", &mut out).unwrap(), 2);
+        assert_eq!(out[0].name(), "SUB");
+        assert_eq!(fs.find_matching(3, "hell?.t?t", &mut out).unwrap(), 1);
+        assert_eq!(out[0].first_cluster(), 4);
+        assert_eq!(fs.find_matching(3, "*.bin", &mut out).unwrap(), 0);
+
+        // Buffer trop petit : le total est retourné, seul le début est copié
+        let mut one = [DirEntry::default(); 1];
+        assert_eq!(fs.find_matching(2, "*", &mut one).unwrap(), 2);
+        assert_eq!(one[0].name(), "SUB");
+    }
+
+    #[test]
+    fn test_exists_and_metadata() {
+        let mut dev = device_with_tree();
+        // HELLO.TXT : créé le 2024-02-29 13:37:42, modifié le 2024-03-01 08:00:00
+        let mut hello = file_entry(b"HELLO   TXT", 0x21, 4, 5);
+        hello[14..16].copy_from_slice(&((13 << 11) | (37 << 5) | 21u16).to_le_bytes());
+        hello[16..18].copy_from_slice(&((44 << 9) | (2 << 5) | 29u16).to_le_bytes());
+        hello[18..20].copy_from_slice(&((44 << 9) | (3 << 5) | 2u16).to_le_bytes());
+        hello[22..24].copy_from_slice(&(8u16 << 11).to_le_bytes());
+        hello[24..26].copy_from_slice(&((44 << 9) | (3 << 5) | 1u16).to_le_bytes());
+        dev.write_entries(3, &[
+            file_entry(b".          ", 0x10, 3, 0),
+            file_entry(b"..         ", 0x10, 0, 0),
+            hello,
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        assert!(fs.exists("/").unwrap());
+        assert!(fs.exists("/SUB").unwrap());
+        assert!(fs.exists("sub/hello.txt").unwrap());
+        assert!(fs.exists("/Long Name.txt").unwrap());
+        assert!(!fs.exists("/SUB/MISSING.TXT").unwrap());
+        assert!(!fs.exists("/MISSING/HELLO.TXT").unwrap());
+        // Les erreurs autres que NotFound sont propagées
+        assert_eq!(fs.exists("/LONGNA~1.TXT/X"), Err(Fat32Error::IsNotDirectory));
+        assert_eq!(fs.exists("SUB//HELLO.TXT"), Err(Fat32Error::InvalidPath));
+
+        let meta = fs.metadata("/SUB/HELLO.TXT").unwrap();
+        assert!(!meta.is_dir);
+        assert_eq!((meta.size, meta.first_cluster), (5, 4));
+        assert!(meta.attributes.is_read_only() && meta.attributes.is_archive());
+        let created = meta.created;
+        assert_eq!((created.year, created.month, created.day), (2024, 2, 29));
+        assert_eq!((created.hour, created.minute, created.second), (13, 37, 42));
+        assert_eq!((meta.modified.month, meta.modified.day, meta.modified.hour), (3, 1, 8));
+        assert_eq!((meta.accessed.month, meta.accessed.day, meta.accessed.hour), (3, 2, 0));
+
+        let sub = fs.metadata("/SUB").unwrap();
+        assert!(sub.is_dir && sub.attributes.is_directory());
+        assert_eq!(sub.first_cluster, 3);
+
+        let root = fs.metadata("/").unwrap();
+        assert!(root.is_dir);
+        assert_eq!(root.first_cluster, 2);
+        assert_eq!(root.modified, DOS_EPOCH);
+    }
+
+    // Chaîne 2 -> 3 -> 4 (+ cluster 5 qui ne doit jamais être lu)
+    fn device_with_chain() -> MemDevice {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 4);
+        dev.set_fat(4, 5);
+        dev.set_fat(5, 0x0FFFFFFF);
+        for cluster in 2..=5 {
+            dev.write_data(cluster, &[cluster as u8; 512]);
+        }
+        dev
+    }
+
+    #[test]
+    fn test_read_range() {
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        // À cheval sur les clusters 2 et 3
+        let mut buf = [0u8; 8];
+        assert_eq!(fs.read_range(2, 1100, 508, &mut buf).unwrap(), 8);
+        assert_eq!(buf, [2, 2, 2, 2, 3, 3, 3, 3]);
+        // Directement dans le troisième cluster
+        assert_eq!(fs.read_range(2, 1100, 1030, &mut buf).unwrap(), 8);
+        assert_eq!(buf, [4; 8]);
+
+        // Lecture partielle en fin de fichier, puis au-delà
+        assert_eq!(fs.read_range(2, 1100, 1096, &mut buf).unwrap(), 4);
+        assert_eq!(fs.read_range(2, 1100, 1100, &mut buf).unwrap(), 0);
+        assert_eq!(fs.read_range(2, 1100, u64::MAX, &mut buf).unwrap(), 0);
+        assert_eq!(fs.read_range(0, 0, 0, &mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_allocated_size() {
+        // Fichier d'un octet sur une chaîne de trois clusters (3 -> 4 -> 5)
+        let mut dev = device_with_root(&[file_entry(b"A       TXT", 0x20, 3, 1)]);
+        dev.set_fat(3, 4);
+        dev.set_fat(4, 5);
+        dev.set_fat(5, 0x0FFFFFFF);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let file = fs.open("/a.txt").unwrap();
+        assert_eq!(file.size(), 1);
+        assert_eq!(fs.allocated_size(file.first_cluster()).unwrap(), 3 * 512);
+        assert_eq!(fs.allocated_size(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fragmentation() {
+        let mut dev = device_with_chain();
+        // Chaîne contiguë 2 -> 3 -> 4 -> 5
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert!(fs.is_contiguous(2).unwrap());
+        assert_eq!(fs.fragment_count(2).unwrap(), 1);
+
+        // Chaîne fragmentée 6 -> 7 -> 10 -> 8 -> 9
+        for (cluster, next) in [(6, 7), (7, 10), (10, 8), (8, 9), (9, 0x0FFFFFFF)] {
+            dev.set_fat(cluster, next);
+        }
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert!(!fs.is_contiguous(6).unwrap());
+        // Fragments : [6, 7], [10], [8, 9]
+        assert_eq!(fs.fragment_count(6).unwrap(), 3);
+
+        assert!(fs.is_contiguous(0).unwrap());
+        assert_eq!(fs.fragment_count(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_free_chain() {
+        // Chaîne 3 -> 4 -> 6, cluster 5 utilisé par un autre fichier,
+        // FSInfo au secteur 9
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(3, 4);
+        dev.set_fat(4, 6);
+        dev.set_fat(5, 0x0FFFFFFF);
+        dev.set_fat(6, 0x0FFFFFFF);
+        dev.write_data(9, &fsinfo_sector(100, 2));
+        let geom = Fat32Geometry {
+            fs_info_sector: 9,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        assert_eq!(fs.free_chain(3).unwrap(), 3);
+        for cluster in [3, 4, 6] {
+            assert!(fs.read_fat_entry(cluster).unwrap().is_free());
+        }
+        assert!(fs.read_fat_entry(5).unwrap().is_end());
+        assert_eq!(fs.read_fsinfo().unwrap().free_clusters(), Some(103));
+        assert_eq!(fs.free_chain(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_free_chain_rejects_cycle() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 4);
+        dev.set_fat(4, 3);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        assert_eq!(fs.free_chain(2), Err(Fat32Error::CorruptedChain));
+        // Rien n'a été libéré
+        for cluster in 2..=4 {
+            assert!(!fs.read_fat_entry(cluster).unwrap().is_free());
+        }
+
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.free_chain(2), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_allocate_chain() {
+        // Clusters 3 et 6 occupés, FSInfo au secteur 20 (hors des clusters utilisés)
+        let mut dev = MemDevice::new(20);
+        dev.set_fat(2, END_OF_CHAIN);
+        dev.set_fat(3, END_OF_CHAIN);
+        dev.set_fat(6, END_OF_CHAIN);
+        dev.write_data(20, &fsinfo_sector(50, 3));
+        let geom = Fat32Geometry {
+            fs_info_sector: 20,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        let first = fs.allocate_chain(4).unwrap();
+        let chain: Vec<u32> = fs.cluster_chain(first).map(|c| c.unwrap()).collect();
+        assert_eq!(chain, [4, 5, 7, 8]);
+        assert!(fs.read_fat_entry(6).unwrap().is_end());
+
+        let info = fs.read_fsinfo().unwrap();
+        assert_eq!(info.free_clusters(), Some(46));
+        assert_eq!(info.next_free_hint(), Some(9));
+        assert_eq!(fs.allocate_chain(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_allocate_chain_fsinfo_failure() {
+        // Le secteur FSInfo (20) se lit mais ne peut pas être écrit
+        let mut inner = MemDevice::new(20);
+        inner.set_fat(2, END_OF_CHAIN);
+        inner.write_data(20, &fsinfo_sector(50, 3));
+        let dev = FailingWrites { inner, fail_lba: 20 };
+        let geom = Fat32Geometry {
+            fs_info_sector: 20,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        assert_eq!(fs.allocate_chain(2), Err(Fat32Error::IoError));
+        assert!(fs.read_fat_entry(3).unwrap().is_free());
+        assert!(fs.read_fat_entry(4).unwrap().is_free());
+
+        // Même chose pour une plage contiguë
+        assert_eq!(fs.allocate_contiguous(3), Err(Fat32Error::IoError));
+        assert_eq!(fs.free_clusters().unwrap(), 125);
+
+        // Chaîne allongée : le cluster 2 reste la fin de chaîne
+        assert_eq!(fs.extend_chain(2, 2), Err(Fat32Error::IoError));
+        assert!(fs.read_fat_entry(2).unwrap().is_end());
+        assert_eq!(fs.free_clusters().unwrap(), 125);
+    }
+
+    #[test]
+    fn test_allocate_contiguous() {
+        // Clusters 3 et 6 occupés : le trou 4-5 est trop petit pour 4 clusters
+        let mut dev = MemDevice::new(20);
+        dev.set_fat(2, END_OF_CHAIN);
+        dev.set_fat(3, END_OF_CHAIN);
+        dev.set_fat(6, END_OF_CHAIN);
+        dev.write_data(20, &fsinfo_sector(50, 3));
+        let geom = Fat32Geometry {
+            fs_info_sector: 20,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new(&dev, geom);
+        assert_eq!(fs.allocate_contiguous(4), Err(Fat32Error::ReadOnly));
+
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+        let first = fs.allocate_contiguous(4).unwrap().unwrap();
+        let chain: Vec<u32> = fs.cluster_chain(first).map(|c| c.unwrap()).collect();
+        assert_eq!(chain, [7, 8, 9, 10]);
+        assert!(fs.read_fat_entry(4).unwrap().is_free());
+
+        let info = fs.read_fsinfo().unwrap();
+        assert_eq!(info.free_clusters(), Some(46));
+        assert_eq!(info.next_free_hint(), Some(11));
+
+        // Le trou 4-5 sert pour une plage assez petite
+        assert_eq!(fs.allocate_contiguous(2).unwrap(), Some(4));
+        assert_eq!(fs.allocate_contiguous(0).unwrap(), Some(0));
+
+        // Pas de plage assez grande : rien n'est modifié
+        let free = fs.free_clusters().unwrap();
+        assert_eq!(fs.allocate_contiguous(free + 1).unwrap(), None);
+        assert_eq!(fs.free_clusters().unwrap(), free);
+        assert_eq!(fs.read_fsinfo().unwrap().free_clusters(), Some(44));
+    }
+
+    #[test]
+    fn test_write_fsinfo() {
+        let mut dev = MemDevice::new(20);
+        dev.set_fat(2, END_OF_CHAIN);
+        let mut sector = fsinfo_sector(50, 3);
+        sector[100] = 0xAB;
+        dev.write_data(20, &sector);
+        let geom = || Fat32Geometry {
+            fs_info_sector: 20,
+            ..test_geometry()
+        };
+
+        let fs = Fat32Fs::new(&dev, geom());
+        let info = FsInfo { free_count: 10, next_free: 4 };
+        assert_eq!(fs.write_fsinfo(&info), Err(Fat32Error::ReadOnly));
+
+        let fs = Fat32Fs::new_with_mode(&dev, geom(), Mode::ReadWrite);
+        fs.write_fsinfo(&info).unwrap();
+        assert_eq!(fs.read_fsinfo().unwrap(), info);
+        // Le reste du secteur est conservé
+        let data = dev.data.borrow();
+        assert_eq!(data[20 * 512 + 100], 0xAB);
+        assert_eq!(data[20 * 512 + 510..20 * 512 + 512], [0x55, 0xAA]);
+        drop(data);
+
+        // Nombre de clusters libres inconnu : il le reste après une allocation
+        let unknown = FsInfo { free_count: crate::fsinfo::UNKNOWN, next_free: crate::fsinfo::UNKNOWN };
+        fs.write_fsinfo(&unknown).unwrap();
+        assert_eq!(fs.read_fsinfo().unwrap().free_clusters(), None);
+        let first = fs.allocate_chain(1).unwrap();
+        let info = fs.read_fsinfo().unwrap();
+        assert_eq!(info.free_clusters(), None);
+        assert_eq!(info.next_free_hint(), Some(first + 1));
+
+        // Puis décrémenté une fois connu
+        fs.write_fsinfo(&FsInfo { free_count: 40, next_free: 4 }).unwrap();
+        fs.allocate_chain(1).unwrap();
+        assert_eq!(fs.read_fsinfo().unwrap().free_clusters(), Some(39));
+
+        // Pas de secteur FSInfo
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        assert_eq!(fs.write_fsinfo(&info), Err(Fat32Error::InvalidFsInfo));
+    }
+
+    #[test]
+    fn test_allocate_chain_disk_full_rolls_back() {
+        let (dev, geom) = device_with_free(&[4, 9]);
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        assert_eq!(fs.allocate_chain(3), Err(Fat32Error::DiskFull));
+        // Les deux clusters réservés ont été rendus
+        assert_eq!(fs.free_clusters().unwrap(), 2);
+        assert!(fs.read_fat_entry(4).unwrap().is_free());
+        assert!(fs.read_fat_entry(9).unwrap().is_free());
+
+        let first = fs.allocate_chain(2).unwrap();
+        assert_eq!(fs.chain_length(first).unwrap(), 2);
+        assert_eq!(fs.free_clusters().unwrap(), 0);
+
+        let fs = Fat32Fs::new(&dev, fs.geom);
+        assert_eq!(fs.allocate_chain(1), Err(Fat32Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_extend_chain() {
+        // Chaîne 2 -> 3, cluster 5 occupé
+        let mut dev = MemDevice::new(10);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, END_OF_CHAIN);
+        dev.set_fat(5, END_OF_CHAIN);
+        let geom = Fat32Geometry {
+            num_fats: 2,
+            fat_size: 1,
+            first_data_sector: 3,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        assert_eq!(fs.extend_chain(3, 2).unwrap(), 6);
+        let chain: Vec<u32> = fs.cluster_chain(2).map(|c| c.unwrap()).collect();
+        assert_eq!(chain, [2, 3, 4, 6]);
+        // La seconde FAT (secteur 2) est identique à la première
+        {
+            let data = dev.data.borrow();
+            assert_eq!(data[512..1024], data[1024..1536]);
+        }
+
+        assert_eq!(fs.extend_chain(6, 0).unwrap(), 6);
+        // Le cluster 2 n'est pas une fin de chaîne
+        assert_eq!(fs.extend_chain(2, 1), Err(Fat32Error::InvalidCluster(2)));
+        assert_eq!(fs.chain_length(2).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_extend_chain_disk_full() {
+        let (dev, geom) = device_with_free(&[4, 9]);
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        assert_eq!(fs.extend_chain(3, 3), Err(Fat32Error::DiskFull));
+        assert!(fs.read_fat_entry(3).unwrap().is_end());
+        assert_eq!(fs.free_clusters().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_read_file_chain_partial_last_cluster() {
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut seen = Vec::new();
+        fs.read_file_chain(2, 1100, |cluster, data| {
+            assert!(data.iter().all(|&b| b == cluster as u8));
+            seen.push((cluster, data.len()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(seen, vec![(2, 512), (3, 512), (4, 76)]);
+    }
+
+    #[test]
+    fn test_read_file_chain_exact_multiple() {
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut seen = Vec::new();
+        fs.read_file_chain(2, 1024, |cluster, data| {
+            seen.push((cluster, data.len()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(seen, vec![(2, 512), (3, 512)]);
+    }
+
+    #[test]
+    fn test_read_file_chain_truncated() {
+        // 4 clusters dans la chaîne, mais l'entrée annonce 2100 octets
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut seen = Vec::new();
+        let result = fs.read_file_chain(2, 2100, |cluster, data| {
+            seen.push((cluster, data.len()));
+            Ok(())
+        });
+        assert_eq!(result, Err(Fat32Error::CorruptedChain));
+        assert_eq!(seen, vec![(2, 512), (3, 512), (4, 512), (5, 512)]);
+        assert!(fs.read_file_chain(2, 2048, |_, _| Ok(())).is_ok());
+
+        // Taille non nulle sans premier cluster
+        let result = fs.read_file_chain(0, 10, |_, _| panic!("aucun cluster à lire"));
+        assert_eq!(result, Err(Fat32Error::CorruptedChain));
+    }
+
+    #[test]
+    fn test_read_file_chain_empty() {
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        // Aucun cluster lu, même avec un numéro de cluster invalide
+        let mut calls = 0;
+        fs.read_file_chain(0, 0, |_, _| {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_open_empty_file() {
+        let dev = device_with_root(&[file_entry(b"EMPTY   TXT", 0x20, 0, 0)]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let entry = fs.read_root_dir().unwrap().next_file().unwrap().unwrap();
+        assert_eq!(entry.first_cluster_opt(), None);
+
+        let mut file = fs.open("EMPTY.TXT").unwrap();
+        let mut buf = [0u8; 16];
+        assert_eq!(file.read(&fs, &mut buf).unwrap(), 0);
+        fs.read_file_chain(file.first_cluster(), file.size(), |_, _| {
+            panic!("aucun cluster ne doit être lu")
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_directory_iterator_trait() {
+        let dev = device_with_tree();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut names = Vec::new();
+        for entry in fs.read_root_dir().unwrap() {
+            names.push(String::from(entry.unwrap().name()));
+        }
+        assert_eq!(names, ["SUB", "Long Name.txt"]);
+
+        let files = fs
+            .read_dir(3)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| !entry.is_dir())
+            .count();
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn test_directory_iterator_stops_after_error() {
+        // Le cluster suivant de la racine est hors du device
+        let mut dev = MemDevice::new(1);
+        fill_dir(&mut dev, 2, 16);
+        dev.set_fat(2, 100);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let mut dir = fs.read_root_dir().unwrap();
+
+        assert_eq!(dir.by_ref().take(16).filter(|entry| entry.is_ok()).count(), 16);
+        assert!(matches!(dir.next(), Some(Err(Fat32Error::OutOfBounds))));
+        assert!(dir.next().is_none());
+    }
+
+    #[test]
+    fn test_directory_spans_small_clusters() {
+        // Clusters d'un secteur : 16 entrées dans le cluster 2, la suite dans le 3
+        let mut dev = MemDevice::new(2);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 0x0FFFFFFF);
+        fill_dir(&mut dev, 2, 16);
+        dev.write_entries(3, &[short_entry(b"LAST    TXT", 0x20)]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let names: Vec<String> = fs
+            .read_root_dir()
+            .unwrap()
+            .map(|entry| String::from(entry.unwrap().name()))
+            .collect();
+        assert_eq!(names.len(), 17);
+        assert_eq!(names[15], "F15.TXT");
+        assert_eq!(names[16], "LAST.TXT");
+    }
+
+    // Clusters de 128 secteurs (64 Ko) : cluster 2 aux secteurs 2 à 129,
+    // cluster 3 aux secteurs 130 à 257.
+    fn large_cluster_geometry() -> Fat32Geometry {
+        Fat32Geometry {
+            sectors_per_cluster: 128,
+            total_sectors: 2 + 2 * 128,
+            ..test_geometry()
+        }
+    }
+
+    #[test]
+    fn test_read_chain_large_clusters() {
+        let mut dev = MemDevice::new(256);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 0x0FFFFFFF);
+        // Chaque secteur porte son numéro
+        for lba in 2..258u32 {
+            let start = lba as usize * 512;
+            dev.data.get_mut()[start..start + 4].copy_from_slice(&lba.to_le_bytes());
+        }
+        let fs = Fat32Fs::new(&dev, large_cluster_geometry());
+
+        let mut sectors = Vec::new();
+        fs.read_cluster_chain(2, |cluster, data| {
+            assert_eq!(data.len(), 4096);
+            for sector in data.chunks(512) {
+                sectors.push((cluster, u32::from_le_bytes(sector[..4].try_into().unwrap())));
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sectors.len(), 256);
+        assert_eq!(sectors[0], (2, 2));
+        assert_eq!(sectors[127], (2, 129));
+        assert_eq!(sectors[128], (3, 130));
+
+        // Taille de fichier au milieu d'un morceau du second cluster
+        let size = 64 * 1024 + 5000;
+        let mut total = 0;
+        fs.read_file_chain(2, size, |_, data| {
+            total += data.len() as u32;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(total, size);
+    }
+
+    #[test]
+    fn test_directory_large_cluster() {
+        // 200 entrées dans un cluster de 64 Ko : le buffer est rechargé
+        let mut dev = MemDevice::new(128);
+        dev.set_fat(2, 0x0FFFFFFF);
+        let mut entries = [[0u8; 32]; 200];
+        for (i, entry) in entries.iter_mut().enumerate() {
+            let mut name = *b"F000    TXT";
+            name[1] += (i / 100) as u8;
+            name[2] += (i / 10 % 10) as u8;
+            name[3] += (i % 10) as u8;
+            *entry = short_entry(&name, 0x20);
+        }
+        dev.write_entries(2, &entries);
+        let geom = Fat32Geometry {
+            total_sectors: 2 + 128,
+            ..large_cluster_geometry()
+        };
+        let fs = Fat32Fs::new(&dev, geom);
+
+        let names: Vec<String> = fs
+            .read_root_dir()
+            .unwrap()
+            .map(|entry| String::from(entry.unwrap().name()))
+            .collect();
+        assert_eq!(names.len(), 200);
+        assert_eq!(names[128], "F128.TXT");
+        assert_eq!(names[199], "F199.TXT");
+    }
+
+    #[test]
+    fn test_write_fat_entry_mirrors() {
+        // Deux FAT d'un secteur : FAT0 au secteur 1, FAT1 au secteur 2
+        let dev = MemDevice::new(2);
+        let geom = Fat32Geometry {
+            num_fats: 2,
+            first_data_sector: 3,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+
+        // Les bits réservés de la valeur existante sont conservés
+        dev.data.borrow_mut()[512 + 20..512 + 24].copy_from_slice(&0xA000_0000u32.to_le_bytes());
+        fs.write_fat_entry(5, FatEntry::new(0x0FFFFFFF)).unwrap();
+        fs.write_fat_entry(6, FatEntry::new(9)).unwrap();
+
+        let data = dev.data.borrow();
+        for fat_start in [512, 1024] {
+            let slot = |cluster: usize| {
+                let start = fat_start + cluster * 4;
+                u32::from_le_bytes(data[start..start + 4].try_into().unwrap())
+            };
+            assert_eq!(slot(5), 0xAFFF_FFFF);
+            assert_eq!(slot(6), 9);
+            assert_eq!(slot(7), 0);
+        }
+        drop(data);
+
+        assert_eq!(fs.read_fat_entry(6).unwrap().next_cluster(), Some(9));
+    }
 
-            // SAFETY: buffer contient des données valides alignées sur 32 octets
-            let entry = unsafe {
-                &*(self.buffer.as_ptr().add(self.offset) as *const DirectoryEntryRaw)
-            };
+    #[test]
+    fn test_verify_fats() {
+        // Deux FAT d'un secteur : FAT0 au secteur 1, FAT1 au secteur 2
+        let dev = MemDevice::new(2);
+        let geom = Fat32Geometry {
+            num_fats: 2,
+            first_data_sector: 3,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+        fs.write_fat_entry(5, FatEntry::new(0x0FFFFFFF)).unwrap();
+        assert_eq!(fs.verify_fats().unwrap(), None);
 
-            self.offset += 32; // Taille d'une entrée de répertoire
+        // Divergence sur les entrées 7 et 9 de FAT1 : la première est signalée
+        dev.data.borrow_mut()[1024 + 9 * 4] = 1;
+        dev.data.borrow_mut()[1024 + 7 * 4 + 3] = 0x10;
+        assert_eq!(fs.verify_fats().unwrap(), Some(7));
 
-            // Fin du répertoire
-            if entry.name[0] == 0x00 {
-                self.done = true;
-                return Ok(None);
-            }
+        // Une seule FAT : rien à comparer
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.verify_fats().unwrap(), None);
+    }
 
-            // Entrée supprimée ou volume label, on skip
-            if entry.is_unused() || entry.attributes == 0x08 {
-                continue;
-            }
+    #[test]
+    fn test_repair_fats() {
+        // Trois FAT d'un secteur (secteurs 1 à 3), dont deux copies divergentes
+        let dev = MemDevice::new(3);
+        let geom = || Fat32Geometry {
+            num_fats: 3,
+            first_data_sector: 4,
+            ..test_geometry()
+        };
+        dev.data.borrow_mut()[512 + 8..512 + 12].copy_from_slice(&0x0FFFFFFFu32.to_le_bytes());
+        dev.data.borrow_mut()[1024 + 20] = 7;
+        dev.data.borrow_mut()[1536 + 40] = 9;
+
+        let fs = Fat32Fs::new(&dev, geom());
+        assert_eq!(fs.repair_fats(), Err(Fat32Error::ReadOnly));
+        assert_eq!(fs.verify_fats().unwrap(), Some(2));
+
+        let fs = Fat32Fs::new_with_mode(&dev, geom(), Mode::ReadWrite);
+        assert_eq!(fs.repair_fats().unwrap(), 2);
+        assert_eq!(fs.verify_fats().unwrap(), None);
+        let data = dev.data.borrow();
+        assert_eq!(data[512..1024], data[1024..1536]);
+        assert_eq!(data[512..1024], data[1536..2048]);
+        drop(data);
+
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        assert_eq!(fs.repair_fats().unwrap(), 0);
+    }
 
-            return Ok(Some(entry));
+    #[test]
+    fn test_scan_bad_clusters() {
+        let mut dev = MemDevice::new(8);
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.set_fat(4, 0x0FFFFFF7);
+        dev.set_fat(5, 6);
+        dev.set_fat(6, 0x0FFFFFF8);
+        // Les 4 bits réservés sont ignorés
+        dev.set_fat(9, 0xFFFFFFF7);
+        dev.set_fat(127, 0x0FFFFFF7);
+
+        let counting = CountingDevice::new(dev);
+        let fs = Fat32Fs::new(&counting, test_geometry());
+        let bad: Vec<u32> = fs.scan_bad_clusters().unwrap().map(|c| c.unwrap()).collect();
+        assert_eq!(bad, [4, 9, 127]);
+        assert_eq!(counting.reads(), 1);
+
+        let fs = Fat32Fs::new(&DummyDevice, Fat32Geometry { bytes_per_sector: 100, ..test_geometry() });
+        assert!(matches!(fs.scan_bad_clusters(), Err(Fat32Error::UnsupportedSectorSize)));
+    }
+
+    #[test]
+    fn test_load_fat() {
+        // 3000 clusters : FAT de 24 secteurs
+        let mut dev = MemDevice::new(0);
+        dev.data.get_mut().resize(512 * 3100, 0);
+        let geom = Fat32Geometry {
+            first_data_sector: 30,
+            fat_size: 24,
+            total_sectors: 3030,
+            ..test_geometry()
+        };
+        dev.set_fat(2, 0x0FFFFFFF);
+        dev.set_fat(10, 2000);
+        dev.set_fat(2000, 0xF000_0BB8); // 3000 avec bits réservés
+        dev.set_fat(3000, 0x0FFFFFFF);
+        for cluster in 3..10 {
+            dev.set_fat(cluster, 0x0FFFFFF7);
         }
+
+        let counting = CountingDevice::new(dev);
+        let fs = Fat32Fs::new(&counting, geom);
+        let table = fs.load_fat().unwrap();
+        assert_eq!(table.len(), 3002);
+        assert_eq!(counting.reads(), 3);
+
+        let chain: Vec<u32> = table.chain(10).map(|c| c.unwrap()).collect();
+        assert_eq!(chain, [10, 2000, 3000]);
+        assert_eq!(table.find_free(2), Some(11));
+        assert_eq!(table.free_count(), fs.free_clusters().unwrap());
+        // Aucune lecture supplémentaire pour parcourir la table
+        assert_eq!(counting.reads(), 3 + 3);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::block_device::BlockDeviceError;
+    #[test]
+    fn test_write_fat_entry_read_only_device() {
+        let dev = DummyDevice;
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+        assert_eq!(fs.write_fat_entry(5, FatEntry::new(0)), Err(Fat32Error::Unsupported));
+    }
 
-    struct DummyDevice;
+    #[test]
+    fn test_read_only_mode_rejects_writes() {
+        let dev = MemDevice::new(2);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.mode(), Mode::ReadOnly);
+        assert_eq!(fs.write_fat_entry(5, FatEntry::new(9)), Err(Fat32Error::ReadOnly));
+        assert_eq!(fs.write_sectors(2, 1, &[0xFF; 512]), Err(Fat32Error::ReadOnly));
+        assert!(dev.data.borrow().iter().all(|&b| b == 0));
 
-    impl BlockDevice for DummyDevice {
+        // `mount` reste en lecture seule, même sur un device inscriptible
+        let image = fat32_image();
+        let ram = UnsizedDevice(crate::devices::RamDevice::from_vec(image.clone()));
+        let fs = Fat32Fs::mount(&ram, &image[..512]).unwrap();
+        assert_eq!(fs.write_fat_entry(5, FatEntry::new(9)), Err(Fat32Error::ReadOnly));
+
+        let fs = Fat32Fs::mount_with_mode(&ram, &image[..512], Mode::ReadWrite).unwrap();
+        fs.write_fat_entry(5, FatEntry::new(9)).unwrap();
+        assert_eq!(fs.read_fat_entry(5).unwrap().next_cluster(), Some(9));
+    }
+
+    #[test]
+    fn test_chain_self_reference() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 3);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut seen = Vec::new();
+        let result = fs.read_cluster_chain(2, |cluster, _| {
+            seen.push(cluster);
+            Ok(())
+        });
+        assert_eq!(result, Err(Fat32Error::CorruptedChain));
+        assert_eq!(seen, [2, 3]);
+    }
+
+    #[test]
+    fn test_chain_cycle_detected_within_two_laps() {
+        // 2 -> 3 -> 4 -> 5 -> 6 -> 4
+        let mut dev = MemDevice::new(6);
+        for cluster in 2..6 {
+            dev.set_fat(cluster, cluster + 1);
+        }
+        dev.set_fat(6, 4);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut seen = Vec::new();
+        let result = fs.read_cluster_chain(2, |cluster, _| {
+            seen.push(cluster);
+            Ok(())
+        });
+        assert_eq!(result, Err(Fat32Error::CorruptedChain));
+        // Détectée au plus deux tours après l'entrée dans la boucle
+        assert_eq!(seen, [2, 3, 4, 5, 6, 4]);
+    }
+
+    // Volume de `clusters` clusters dont la FAT est calculée à la volée :
+    // une seule chaîne 2 -> 3 -> ... -> dernier cluster, données nulles.
+    struct LongChainDevice {
+        clusters: u32,
+    }
+
+    impl LongChainDevice {
+        fn geometry(&self) -> Fat32Geometry {
+            let fat_size = (self.clusters + 2).div_ceil(128);
+            Fat32Geometry {
+                first_data_sector: 1 + fat_size,
+                fat_size,
+                total_sectors: 1 + fat_size + self.clusters,
+                ..test_geometry()
+            }
+        }
+    }
+
+    impl BlockDevice for LongChainDevice {
         fn read_sectors(
             &self,
-            _lba: u32,
-            _count: u32,
-            _buf: &mut [u8],
+            lba: u32,
+            count: u32,
+            buf: &mut [u8],
         ) -> core::result::Result<(), BlockDeviceError> {
+            let fat_size = self.geometry().fat_size;
+            for (i, sector) in buf.chunks_mut(512).take(count as usize).enumerate() {
+                let lba = lba + i as u32;
+                if lba == 0 || lba > fat_size {
+                    sector.fill(0);
+                    continue;
+                }
+                for (j, slot) in sector.chunks_mut(4).enumerate() {
+                    let cluster = (lba - 1) * 128 + j as u32;
+                    let last = self.clusters + 1;
+                    let value = match cluster {
+                        0 | 1 => 0x0FFFFFFF,
+                        c if c < last => c + 1,
+                        c if c == last => 0x0FFFFFFF,
+                        _ => 0,
+                    };
+                    slot.copy_from_slice(&value.to_le_bytes());
+                }
+            }
             Ok(())
         }
     }
 
     #[test]
-    fn test_invalid_boot_sector() {
-        let dev = DummyDevice;
-        let invalid_boot = [0u8; 512]; // Signature invalide
+    fn test_long_chain_within_volume() {
+        // Plus long que l'ancienne limite fixe de 100000 clusters
+        let dev = LongChainDevice { clusters: 100_010 };
+        let mut fs = Fat32Fs::new(&dev, dev.geometry());
+        assert_eq!(fs.max_chain_length(), 100_010);
+
+        let mut count = 0;
+        fs.read_cluster_chain(2, |_, _| {
+            count += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, 100_010);
+
+        fs.set_max_chain_length(Some(1000));
+        let result = fs.read_cluster_chain(2, |_, _| Ok(()));
+        assert_eq!(result, Err(Fat32Error::InvalidCluster(1002)));
+    }
+
+    #[test]
+    fn test_cluster_chain_iterator() {
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let clusters: Vec<u32> = fs.cluster_chain(2).map(|c| c.unwrap()).collect();
+        assert_eq!(clusters, [2, 3, 4, 5]);
+        assert_eq!(fs.chain_length(3), Ok(3));
+        assert_eq!(fs.chain_length(1), Err(Fat32Error::InvalidCluster(1)));
+    }
+
+    #[test]
+    fn test_nth_cluster() {
+        let dev = device_with_chain();
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        assert_eq!(fs.nth_cluster(2, 0), Ok(Some(2)));
+        assert_eq!(fs.nth_cluster(2, 3), Ok(Some(5)));
+        assert_eq!(fs.nth_cluster(3, 1), Ok(Some(4)));
+        assert_eq!(fs.nth_cluster(2, 4), Ok(None));
+        assert_eq!(fs.nth_cluster(2, 1000), Ok(None));
+        assert_eq!(fs.nth_cluster(0, 0), Err(Fat32Error::InvalidCluster(0)));
+    }
+
+    #[test]
+    fn test_cluster_chain_stops_after_cycle() {
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 3);
+        dev.set_fat(3, 2);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut chain = fs.cluster_chain(2);
+        assert_eq!(chain.next(), Some(Ok(2)));
+        assert_eq!(chain.next(), Some(Ok(3)));
+        assert_eq!(chain.next(), Some(Ok(2)));
+        assert_eq!(chain.next(), Some(Err(Fat32Error::CorruptedChain)));
+        assert_eq!(chain.next(), None);
+        assert_eq!(fs.chain_length(2), Err(Fat32Error::CorruptedChain));
+    }
+
+    #[test]
+    fn test_cluster_chain_reaches_free_entry() {
+        // 2 -> 3, mais l'entrée du cluster 3 est libre
+        let mut dev = MemDevice::new(4);
+        dev.set_fat(2, 3);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut chain = fs.cluster_chain(2);
+        assert_eq!(chain.next(), Some(Ok(2)));
+        assert_eq!(chain.next(), Some(Ok(3)));
+        assert_eq!(chain.next(), Some(Err(Fat32Error::CorruptedFat)));
+        assert_eq!(chain.next(), None);
+        assert_eq!(fs.chain_length(2), Err(Fat32Error::CorruptedFat));
+    }
+
+    #[test]
+    fn test_fat_cache_single_sector_chain() {
+        // Chaîne 2 -> 3 -> ... -> 101 : toutes les entrées dans le même secteur
+        let mut mem = MemDevice::new(100);
+        for cluster in 2..101 {
+            mem.set_fat(cluster, cluster + 1);
+        }
+        mem.set_fat(101, 0x0FFFFFFF);
+        let dev = CountingDevice::new(mem);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut cluster = 2;
+        while let Some(next) = fs.read_fat_entry(cluster).unwrap().next_cluster() {
+            cluster = next;
+        }
+        assert_eq!(cluster, 101);
+        assert_eq!(dev.reads(), 1);
+    }
+
+    #[test]
+    fn test_fat_cache_invalidated_by_write() {
+        let mut mem = MemDevice::new(4);
+        mem.set_fat(2, 3);
+        let dev = CountingDevice::new(mem);
+        let fs = Fat32Fs::new_with_mode(&dev, test_geometry(), Mode::ReadWrite);
+
+        assert_eq!(fs.read_fat_entry(2).unwrap().next_cluster(), Some(3));
+        fs.write_fat_entry(2, FatEntry::new(4)).unwrap();
+        assert_eq!(fs.read_fat_entry(2).unwrap().next_cluster(), Some(4));
+    }
+
+    // Volume de 10 clusters (2 à 11), tous occupés sauf ceux de `free`.
+    fn device_with_free(free: &[u32]) -> (MemDevice, Fat32Geometry) {
+        let mut dev = MemDevice::new(10);
+        for cluster in 2..12 {
+            if !free.contains(&cluster) {
+                dev.set_fat(cluster, 0x0FFFFFFF);
+            }
+        }
+        let geom = Fat32Geometry {
+            total_sectors: 12,
+            ..test_geometry()
+        };
+        (dev, geom)
+    }
+
+    #[test]
+    fn test_find_free_cluster() {
+        let (dev, geom) = device_with_free(&[4, 9]);
+        let fs = Fat32Fs::new(&dev, geom);
+        assert_eq!(fs.geom.total_clusters(), 10);
+
+        assert_eq!(fs.find_free_cluster(2).unwrap(), Some(4));
+        assert_eq!(fs.find_free_cluster(5).unwrap(), Some(9));
+        // Reprend au cluster 2 après la fin du volume
+        assert_eq!(fs.find_free_cluster(10).unwrap(), Some(4));
+        // Indice invalide : départ au cluster 2
+        assert_eq!(fs.find_free_cluster(500).unwrap(), Some(4));
+    }
+
+    #[test]
+    fn test_statfs() {
+        let (dev, geom) = device_with_free(&[4, 9]);
+        let fs = Fat32Fs::new(&dev, geom);
+        assert_eq!(fs.free_clusters().unwrap(), 2);
+        assert_eq!(fs.used_clusters().unwrap(), 8);
+        let stat = fs.statfs().unwrap();
+        assert_eq!(
+            stat,
+            StatFs { total: 10, free: 2, used: 8, cluster_size: 512 }
+        );
+        assert_eq!(stat.free_bytes(), 1024);
+        assert_eq!(stat.total_bytes(), 5120);
+    }
+
+    #[test]
+    fn test_fat_scan_capped_at_fat_size() {
+        // La BPB annonce 298 clusters, mais la FAT d'un secteur n'en décrit
+        // que 126 : le secteur suivant (cluster 2) contient des données
+        let mut dev = MemDevice::new(10);
+        dev.set_fat(2, END_OF_CHAIN);
+        dev.write_data(2, &[0xFF; 512]);
+        let geom = Fat32Geometry {
+            total_sectors: 300,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new(&dev, geom);
+
+        assert_eq!(fs.free_clusters().unwrap(), 125);
+        assert_eq!(fs.statfs().unwrap().used, 298 - 125);
+        assert_eq!(fs.find_free_cluster(127).unwrap(), Some(127));
+        assert_eq!(fs.find_free_cluster(200).unwrap(), Some(3));
+        assert_eq!(fs.load_fat().unwrap().len(), 128);
+
+        // Aucune plage ne déborde de la FAT
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+        assert_eq!(fs.allocate_contiguous(126).unwrap(), None);
+        assert_eq!(fs.allocate_contiguous(125).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_free_clusters_reads_fat_in_chunks() {
+        // 3000 clusters : FAT de 24 secteurs, lue en 3 blocs de 8 secteurs
+        let mut mem = MemDevice::new(30);
+        mem.set_fat(2, 0x0FFFFFFF);
+        mem.set_fat(1000, 0x0FFFFFF7); // défectueux : compté comme utilisé
+        mem.set_fat(3001, 5);
+        // Les entrées nulles après le cluster 3001 (fin du dernier secteur
+        // de FAT) ne sont pas comptées
+        let dev = CountingDevice::new(mem);
+        let geom = Fat32Geometry {
+            fat_size: 24,
+            first_data_sector: 25,
+            total_sectors: 25 + 3000,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new(&dev, geom);
+
+        assert_eq!(fs.free_clusters().unwrap(), 2997);
+        assert_eq!(dev.reads(), 3);
+    }
+
+    // Device qui journalise les secteurs lus, dans l'ordre.
+    struct RecordingDevice {
+        inner: MemDevice,
+        log: RefCell<Vec<u32>>,
+    }
+
+    impl BlockDevice for RecordingDevice {
+        fn read_sectors(&self, lba: u32, count: u32, buf: &mut [u8]) -> core::result::Result<(), BlockDeviceError> {
+            self.log.borrow_mut().push(lba);
+            self.inner.read_sectors(lba, count, buf)
+        }
+    }
+
+    #[test]
+    fn test_read_cluster_chain_looks_up_next_before_callback() {
+        // Chaîne 2 -> 3 -> 4 : FAT au secteur 1, cluster N au secteur N
+        let mut inner = MemDevice::new(3);
+        inner.set_fat(2, 3);
+        inner.set_fat(3, 4);
+        inner.set_fat(4, 0x0FFFFFFF);
+        let dev = RecordingDevice {
+            inner,
+            log: RefCell::new(Vec::new()),
+        };
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        // Le callback est noté `u32::MAX` dans le journal
+        const CALLBACK: u32 = u32::MAX;
+        fs.read_cluster_chain(2, |_, _| {
+            dev.log.borrow_mut().push(CALLBACK);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(*dev.log.borrow(), [2, 1, CALLBACK, 3, CALLBACK, 4, CALLBACK]);
+
+        // Erreur de FAT après la fin du fichier : jamais remontée
+        let mut inner = MemDevice::new(3);
+        inner.set_fat(2, 1);
+        let fs = Fat32Fs::new(&inner, test_geometry());
+        let mut calls = 0;
+        fs.read_file_chain(2, 100, |_, _| {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(fs.read_cluster_chain(2, |_, _| Ok(())), Err(Fat32Error::InvalidCluster(1)));
+    }
+
+    #[test]
+    fn test_read_sector() {
+        let image = fat32_image();
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        let fs = Fat32Fs::mount(&dev, &image[..512]).unwrap();
+
+        let mut buf = [0xAAu8; 600];
+        fs.read_sector(0, &mut buf).unwrap();
+        assert_eq!(&buf[..512], &image[..512]);
+        assert!(buf[512..].iter().all(|&b| b == 0xAA));
+        assert_eq!(fs.read_sector(0, &mut buf[..511]), Err(Fat32Error::BufferTooSmall));
+
+        let boot: [u8; 512] = fs.read_sector_into(0).unwrap();
+        assert_eq!(&boot[..], &image[..512]);
+        let padded: [u8; 1024] = fs.read_sector_into(0).unwrap();
+        assert_eq!(&padded[..512], &image[..512]);
+        assert!(padded[512..].iter().all(|&b| b == 0));
+        assert_eq!(fs.read_sector_into::<256>(0), Err(Fat32Error::BufferTooSmall));
+    }
+
+    #[test]
+    fn test_backup_boot_sector() {
+        use crate::devices::RamDevice;
+        use crate::format::{format, NO_NAME};
+
+        let device = RamDevice::new(70_000);
+        format(&device, 70_000, 1, &NO_NAME).unwrap();
+        let mut boot = [0u8; 512];
+        device.read_sectors(0, 1, &mut boot).unwrap();
+
+        let fs = Fat32Fs::mount(&device, &boot).unwrap();
+        assert_eq!(fs.backup_boot_sector(), Some(6));
+        assert_eq!(fs.verify_backup_boot(), Ok(true));
+
+        // Copie divergente
+        let mut backup = boot;
+        backup[3..11].copy_from_slice(b"OTHEROEM");
+        device.write_sectors(6, 1, &backup).unwrap();
+        assert_eq!(fs.verify_backup_boot(), Ok(false));
+        device.write_sectors(6, 1, &boot).unwrap();
+
+        // Secteur de boot principal endommagé : on monte la copie
+        let mut damaged = boot;
+        damaged[11..13].copy_from_slice(&0u16.to_le_bytes());
+        assert_eq!(Fat32Fs::mount(&device, &damaged).err(), Some(Fat32Error::UnsupportedSectorSize));
+        let fs = Fat32Fs::mount_from_backup(&device, &damaged).unwrap();
+        assert_eq!(fs.geom.fat_start_lba, 32);
+        // Champ `backup_boot_sector` illisible : secteur 6 par défaut
+        damaged[50..52].copy_from_slice(&0xFFFFu16.to_le_bytes());
+        assert!(Fat32Fs::mount_from_backup(&device, &damaged).is_ok());
+        damaged[510] = 0;
+        assert!(Fat32Fs::mount_from_backup(&device, &damaged).is_ok());
+
+        // Copie endommagée elle aussi : l'erreur de la copie est retournée
+        device.write_sectors(6, 1, &[0u8; 512]).unwrap();
+        assert_eq!(
+            Fat32Fs::mount_from_backup(&device, &damaged).err(),
+            Some(Fat32Error::InvalidBootSector)
+        );
+        // Un secteur valide est monté directement
+        assert!(Fat32Fs::mount_from_backup(&device, &boot).is_ok());
+
+        // Sans copie annoncée par la BPB
+        let image = fat32_image();
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        let fs = Fat32Fs::mount(&dev, &image[..512]).unwrap();
+        assert_eq!(fs.backup_boot_sector(), None);
+        assert_eq!(fs.verify_backup_boot(), Ok(false));
+    }
+
+    #[test]
+    fn test_read_contiguous() {
+        // Deux fragments : 2 -> 3 -> 4, puis 7 -> 8
+        let mut mem = MemDevice::new(8);
+        mem.set_fat(2, 3);
+        mem.set_fat(3, 4);
+        mem.set_fat(4, 7);
+        mem.set_fat(7, 8);
+        mem.set_fat(8, 0x0FFFFFFF);
+        for cluster in 2..10 {
+            mem.write_data(cluster, &[cluster as u8; 512]);
+        }
+        let dev = CountingDevice::new(mem);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut buf = [0u8; 8 * 512];
+        assert_eq!(fs.read_contiguous(2, 8, &mut buf).unwrap(), 3);
+        assert!(buf[..512].iter().all(|&b| b == 2));
+        assert!(buf[1024..1536].iter().all(|&b| b == 4));
+        // Un secteur de FAT et un seul appel pour les trois clusters
+        assert_eq!(dev.reads(), 2);
+
+        // Limites : nombre de clusters demandé et taille du buffer
+        assert_eq!(fs.read_contiguous(2, 2, &mut buf).unwrap(), 2);
+        assert_eq!(fs.read_contiguous(2, 8, &mut buf[..1024]).unwrap(), 2);
+        assert_eq!(fs.read_contiguous(7, 8, &mut buf).unwrap(), 2);
+        assert_eq!(fs.read_contiguous(2, 0, &mut buf).unwrap(), 0);
+        assert_eq!(fs.read_contiguous(2, 8, &mut buf[..100]), Err(Fat32Error::BufferTooSmall));
+        assert_eq!(fs.read_contiguous(1, 8, &mut buf), Err(Fat32Error::InvalidCluster(1)));
+    }
+
+    #[test]
+    fn test_read_contiguous_loop_device_calls() {
+        // Fichier contigu de 40 clusters, lu par blocs de 16 clusters
+        let mut mem = MemDevice::new(40);
+        for cluster in 2..41 {
+            mem.set_fat(cluster, cluster + 1);
+        }
+        mem.set_fat(41, 0x0FFFFFFF);
+        let dev = CountingDevice::new(mem);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+
+        let mut buf = [0u8; 16 * 512];
+        let mut total = 0;
+        let mut next = Some(2);
+        while let Some(cluster) = next {
+            let count = fs.read_contiguous(cluster, 16, &mut buf).unwrap();
+            total += count;
+            next = fs.nth_cluster(cluster, count).unwrap();
+        }
+        assert_eq!(total, 40);
+        // 3 lectures de données (16 + 16 + 8) et 1 secteur de FAT, contre
+        // 40 lectures de données cluster par cluster
+        assert_eq!(dev.reads(), 4);
+    }
+
+    #[test]
+    fn test_cluster_bounds() {
+        let (dev, geom) = device_with_free(&[]);
+        let fs = Fat32Fs::new_with_mode(&dev, geom, Mode::ReadWrite);
+        let mut buf = [0u8; 512];
+
+        // Dernier cluster valide
+        assert_eq!(fs.geom.max_valid_cluster(), 11);
+        assert!(fs.read_fat_entry(11).is_ok());
+        assert!(fs.read_cluster(11, &mut buf).is_ok());
+
+        // Premier cluster hors du volume
+        assert_eq!(fs.read_fat_entry(12), Err(Fat32Error::InvalidCluster(12)));
+        assert_eq!(fs.read_cluster(12, &mut buf), Err(Fat32Error::InvalidCluster(12)));
+        assert_eq!(
+            fs.write_fat_entry(12, FatEntry::new(0)),
+            Err(Fat32Error::InvalidCluster(12))
+        );
+        assert_eq!(fs.read_cluster(1, &mut buf), Err(Fat32Error::InvalidCluster(1)));
+    }
+
+    #[test]
+    fn test_find_free_cluster_full_volume() {
+        let (dev, geom) = device_with_free(&[]);
+        let fs = Fat32Fs::new(&dev, geom);
+        assert_eq!(fs.find_free_cluster(2).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_fsinfo() {
+        let mut dev = MemDevice::new(4);
+        dev.write_data(5, &fsinfo_sector(42, 7));
+
+        // Pas de secteur FSInfo
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.read_fsinfo(), Err(Fat32Error::InvalidFsInfo));
+
+        let geom = Fat32Geometry {
+            fs_info_sector: 5,
+            ..test_geometry()
+        };
+        let fs = Fat32Fs::new(&dev, geom);
+        let info = fs.read_fsinfo().unwrap();
+        assert_eq!(info.free_clusters(), Some(42));
+        assert_eq!(info.next_free_hint(), Some(7));
+
+        // Signature de structure corrompue
+        dev.data.borrow_mut()[5 * 512 + 484] = 0;
+        assert_eq!(fs.read_fsinfo(), Err(Fat32Error::InvalidFsInfo));
+    }
+
+    #[test]
+    fn test_volume_label_from_root() {
+        let dev = device_with_root(&[
+            short_entry(b"HELLO   TXT", 0x20),
+            short_entry(b"MY DISK    ", 0x08),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.volume_label().unwrap(), Some(*b"MY DISK\0\0\0\0"));
+    }
+
+    #[test]
+    fn test_volume_label_from_boot_sector() {
+        let dev = device_with_root(&[short_entry(b"HELLO   TXT", 0x20)]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert_eq!(fs.volume_label().unwrap(), None);
+
+        dev.data.borrow_mut()[66] = 0x29;
+        dev.data.borrow_mut()[71..82].copy_from_slice(b"NO NAME    ");
+        assert_eq!(fs.volume_label().unwrap(), None);
+
+        dev.data.borrow_mut()[71..82].copy_from_slice(b"BOOTLABEL  ");
+        assert_eq!(fs.volume_label().unwrap(), Some(*b"BOOTLABEL\0\0"));
+    }
+
+    #[test]
+    fn test_mount_crafted_image() {
+        let image = fat32_image();
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        let fs = Fat32Fs::mount(&dev, &image[..512]).unwrap();
+        assert_eq!(fs.geom.first_data_sector, 2);
+        assert_eq!(fs.geom.root_cluster, 2);
+
+        let mut file = fs.open("/hello.txt").unwrap();
+        let mut buf = [0u8; 64];
+        let len = file.read(&fs, &mut buf).unwrap();
+        assert_eq!(&buf[..len], b"Hello, world!");
+    }
+
+    #[test]
+    fn test_mount_rejects_truncated_image() {
+        let image = fat32_image();
+        let dev = SliceDevice::new(&image);
+        assert_eq!(
+            Fat32Fs::mount(&dev, &image[..512]).map(|_| ()),
+            Err(Fat32Error::DeviceTooSmall)
+        );
+
+        // Même volume derrière une partition qui commence au secteur 4
+        let mut image = vec![0u8; 512 * 4];
+        image.extend_from_slice(&fat32_image());
+        image.resize(512 * (4 + 2 + FAT32_MIN_CLUSTERS as usize) - 1, 0);
+        let dev = SliceDevice::new(&image);
+        assert_eq!(
+            Fat32FsBuilder::new().build_at(&dev, &image[2048..2560], 4).map(|_| ()),
+            Err(Fat32Error::DeviceTooSmall)
+        );
+        assert!(Fat32FsBuilder::new().build_at(&dev, &image[2048..2560], 3).is_ok());
+    }
+
+    #[test]
+    fn test_builder() {
+        let image = fat32_image();
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        let clock = FixedClock(Timestamp { year: 2024, ..DOS_EPOCH });
+
+        let fs = Fat32FsBuilder::new()
+            .read_write()
+            .with_clock(&clock)
+            .max_chain(3)
+            .zero_on_free(true)
+            .build(&dev, &image[..512])
+            .unwrap();
+        assert_eq!(fs.mode(), Mode::ReadWrite);
+        assert!(fs.zero_on_free());
+        assert_eq!(fs.now().year, 2024);
+        assert_eq!(fs.max_chain_length(), 3);
+        assert_eq!(fs.geom.root_cluster, 2);
+
+        // Options par défaut : celles de `mount`
+        let fs = Fat32FsBuilder::new().build(&dev, &image[..512]).unwrap();
+        assert_eq!(fs.mode(), Mode::ReadOnly);
+        assert_eq!(fs.now(), DOS_EPOCH);
+        assert_eq!(fs.max_chain_length(), FAT32_MIN_CLUSTERS);
+        assert!(!fs.zero_on_free());
+
         assert!(matches!(
-            Fat32Fs::mount(&dev, &invalid_boot),
+            Fat32FsBuilder::new().read_write().build(&dev, &[0u8; 512]),
             Err(Fat32Error::InvalidBootSector)
         ));
     }
 
     #[test]
-    fn test_boot_sector_too_small() {
-        let dev = DummyDevice;
-        let small_boot = [0u8; 100]; // Trop petit
+    fn test_mount_4k_sectors() {
+        let image = fat32_image_with_sector_size(4096);
+        let dev = SizedDevice::<4096> { data: image };
+        let fs = Fat32Fs::mount(&dev, &dev.data[..4096]).unwrap();
+        assert_eq!(fs.geom.bytes_per_sector, 4096);
+        assert!(fs.read_fat_entry(3).unwrap().is_end());
+
+        let mut file = fs.open("/hello.txt").unwrap();
+        let mut buf = [0u8; 64];
+        let len = file.read(&fs, &mut buf).unwrap();
+        assert_eq!(&buf[..len], b"Hello, world!");
+        assert_eq!(fs.read_file_to_vec("hello.txt").unwrap(), b"Hello, world!");
+    }
+
+    #[test]
+    fn test_mount_rejects_sector_size() {
+        // BPB à 2048 octets mais device à secteurs de 512
+        let image = fat32_image_with_sector_size(2048);
+        let dev = SliceDevice::new(&image);
         assert!(matches!(
-            Fat32Fs::mount(&dev, &small_boot),
-            Err(Fat32Error::InvalidBootSector)
+            Fat32Fs::mount(&dev, &image[..512]),
+            Err(Fat32Error::UnsupportedSectorSize)
+        ));
+
+        // Taille hors des quatre valeurs autorisées
+        let mut image = fat32_image();
+        image[11..13].copy_from_slice(&1000u16.to_le_bytes());
+        let dev = SliceDevice::new(&image);
+        assert!(matches!(
+            Fat32Fs::mount(&dev, &image[..512]),
+            Err(Fat32Error::UnsupportedSectorSize)
         ));
     }
-        #[test]
-    fn test_valid_signature_but_not_fat32() {
-        let dev = DummyDevice;
-        let mut boot = [0u8; 512];
-        boot[510] = 0x55;
-        boot[511] = 0xAA;
-        // Signature valide mais BPB invalide (fat_size_32 = 0)
-        let result = Fat32Fs::mount(&dev, &boot);
-        assert!(matches!(result, Err(Fat32Error::NotFat32)));
+
+    #[test]
+    fn test_mount_rejects_fat16_cluster_count() {
+        // fat_size_16 nul mais trop peu de clusters pour du FAT32
+        let mut image = fat32_image();
+        image[32..36].copy_from_slice(&(2 + 65524u32).to_le_bytes());
+        let dev = SliceDevice::new(&image);
+        assert!(matches!(Fat32Fs::mount(&dev, &image[..512]), Err(Fat32Error::NotFat32)));
     }
 
     #[test]
-    fn test_fat_entry_reading() {
-        // Test avec un device qui retourne des données connues
+    fn test_mount_rejects_invalid_bpb() {
+        // (offset, valeur) : sectors_per_cluster, num_fats, reserved, root_cluster
+        let cases: [(usize, &[u8], &str); 4] = [
+            (13, &[0], "sectors_per_cluster"),
+            (16, &[0], "num_fats"),
+            (14, &[0, 0], "reserved_sector_count"),
+            (44, &[1, 0, 0, 0], "root_cluster"),
+        ];
+        for (offset, value, field) in cases {
+            let mut image = fat32_image();
+            image[offset..offset + value.len()].copy_from_slice(value);
+            let dev = SliceDevice::new(&image);
+            assert!(matches!(
+                Fat32Fs::mount(&dev, &image[..512]),
+                Err(Fat32Error::InvalidBpb(f)) if f == field
+            ));
+        }
+    }
+
+    #[test]
+    fn test_mount_strict() {
+        // Écarts tolérés en lecture : version, octet media et type
+        // (`root_entry_count` réduirait le nombre de clusters de cette image
+        // minimale sous le seuil FAT32)
+        let cases: [(usize, &[u8], &str); 3] = [
+            (42, &[0, 1], "fs_version"),
+            (21, &[0x12], "media"),
+            (82, b"FAT16   ", "fs_type"),
+        ];
+        for (offset, value, field) in cases {
+            let mut image = fat32_image();
+            image[offset..offset + value.len()].copy_from_slice(value);
+            let dev = UnsizedDevice(SliceDevice::new(&image));
+            assert!(Fat32Fs::mount(&dev, &image[..512]).is_ok(), "{field}");
+            assert!(Fat32FsBuilder::new().strict(false).build(&dev, &image[..512]).is_ok());
+            assert!(matches!(
+                Fat32FsBuilder::new().strict(true).build(&dev, &image[..512]),
+                Err(Fat32Error::InvalidBpb(f)) if f == field
+            ));
+        }
+
+        // Un volume conforme est accepté en mode strict
+        let image = fat32_image();
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        assert!(Fat32FsBuilder::new().strict(true).build(&dev, &image[..512]).is_ok());
+        // La géométrie reste fatale en mode tolérant
+        let mut image = fat32_image();
+        image[16] = 0;
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        assert!(matches!(
+            Fat32FsBuilder::new().strict(false).build(&dev, &image[..512]),
+            Err(Fat32Error::InvalidBpb("num_fats"))
+        ));
+    }
+
+    #[test]
+    fn test_mount_partition() {
+        // MBR, trois secteurs libres, puis le volume en partition 1
+        let mut image = vec![0u8; 512 * 4];
+        let entry = &mut image[446 + 16..446 + 32];
+        entry[4] = 0x0C;
+        entry[8..12].copy_from_slice(&4u32.to_le_bytes());
+        entry[12..16].copy_from_slice(&8u32.to_le_bytes());
+        image[510] = 0x55;
+        image[511] = 0xAA;
+        image.extend_from_slice(&fat32_image());
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+
+        let fs = Fat32Fs::mount_partition(&dev, 1).unwrap();
+        assert_eq!(fs.partition_start(), 4);
+        assert_eq!(fs.mode(), Mode::ReadOnly);
+        let fs_rw = Fat32Fs::mount_partition_with_mode(&dev, 1, Mode::ReadWrite).unwrap();
+        assert_eq!(fs_rw.mode(), Mode::ReadWrite);
+        assert_eq!(fs_rw.partition_start(), 4);
+        assert_eq!(fs.read_file_to_vec("/hello.txt").unwrap(), b"Hello, world!");
+        assert_eq!(fs.volume_label(), Ok(None));
+
+        assert!(matches!(Fat32Fs::mount_partition(&dev, 0), Err(Fat32Error::NotFat32)));
+        assert!(matches!(Fat32Fs::mount_partition(&dev, 4), Err(Fat32Error::OutOfBounds)));
+
+        // Options du builder appliquées à la partition
+        let fs = Fat32FsBuilder::new()
+            .read_write()
+            .max_chain(7)
+            .strict(true)
+            .build_partition(&dev, 1)
+            .unwrap();
+        assert_eq!(fs.mode(), Mode::ReadWrite);
+        assert_eq!(fs.max_chain_length(), 7);
+        assert_eq!(fs.partition_start(), 4);
+        let mut quirky = image.clone();
+        quirky[512 * 4 + 21] = 0x12; // media
+        let dev_quirky = UnsizedDevice(SliceDevice::new(&quirky));
+        assert!(Fat32Fs::mount_partition(&dev_quirky, 1).is_ok());
+        assert!(matches!(
+            Fat32FsBuilder::new().strict(true).build_partition(&dev_quirky, 1),
+            Err(Fat32Error::InvalidBpb("media"))
+        ));
+
+        // Sans MBR : le secteur 0 est directement le secteur de boot, dont le
+        // code de démarrage occupe la place de la table
+        let mut image = fat32_image();
+        image[446..510].fill(0xF4);
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        assert!(matches!(Fat32Fs::mount_partition(&dev, 0), Err(Fat32Error::InvalidMbr)));
+    }
+
+    #[test]
+    fn test_read_file_to_vec() {
+        let mut image = fat32_image();
+        // Fichier vide EMPTY.TXT et répertoire SUB à côté de HELLO.TXT
+        image[1056..1088].copy_from_slice(&file_entry(b"EMPTY   TXT", 0x20, 0, 0));
+        image[1088..1120].copy_from_slice(&file_entry(b"SUB        ", 0x10, 4, 0));
+        // Chaînes trop courtes pour la taille annoncée
+        image[1120..1152].copy_from_slice(&file_entry(b"SHORT   TXT", 0x20, 3, 600));
+        image[1152..1184].copy_from_slice(&file_entry(b"NOCLUS  TXT", 0x20, 0, 10));
+        let dev = UnsizedDevice(SliceDevice::new(&image));
+        let fs = Fat32Fs::mount(&dev, &image[..512]).unwrap();
+
+        assert_eq!(fs.read_file_to_vec("/short.txt"), Err(Fat32Error::CorruptedChain));
+        assert_eq!(fs.read_file_to_vec("/noclus.txt"), Err(Fat32Error::CorruptedChain));
+
+        assert_eq!(fs.read_file_to_vec("/hello.txt").unwrap(), b"Hello, world!");
+        assert!(fs.read_file_to_vec("empty.txt").unwrap().is_empty());
+        assert_eq!(fs.read_file_to_vec("/sub"), Err(Fat32Error::IsDirectory));
+        assert_eq!(fs.read_file_to_vec("/missing"), Err(Fat32Error::NotFound));
+    }
+
+    #[test]
+    fn test_next_file_missing_short_entry() {
+        // Fragment sans entrée 8.3 en fin de répertoire, puis fragment
+        // interrompu par une entrée supprimée
+        let dev = device_with_root(&[lfn_entry(0x41, "orphan", 0)]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        assert!(fs.read_root_dir().unwrap().next_file().unwrap().is_none());
+
+        let short = *b"ORPHAN  TXT";
+        let mut deleted = short_entry(b"OLD     TXT", 0x20);
+        deleted[0] = 0xE5;
+        let dev = device_with_root(&[
+            lfn_entry(0x41, "orphan.txt", lfn_checksum(&short)),
+            deleted,
+            short_entry(&short, 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let mut dir = fs.read_root_dir().unwrap();
+        let entry = dir.next_file().unwrap().unwrap();
+        assert_eq!((entry.name(), entry.has_long_name()), ("ORPHAN.TXT", false));
+        assert_eq!(dir.location().slots, 1);
+    }
+
+    #[test]
+    fn test_next_file_bad_sequence() {
+        // Le fragment 0x02 manque : la chaîne saute directement de 3 à 1
+        let dev = device_with_root(&[
+            lfn_entry(0x43, "abc", 0),
+            lfn_entry(0x01, "0123456789012", 0),
+            short_entry(b"ABC~1      ", 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let entry = fs.read_root_dir().unwrap().next_file().unwrap().unwrap();
+        assert_eq!((entry.name(), entry.has_long_name()), ("ABC~1", false));
+
+        // Fragment sans drapeau 0x40 en tête de chaîne
+        let dev = device_with_root(&[
+            lfn_entry(0x01, "abc", 0),
+            short_entry(b"ABC        ", 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let entry = fs.read_root_dir().unwrap().next_file().unwrap().unwrap();
+        assert_eq!((entry.name(), entry.has_long_name()), ("ABC", false));
+
+        // Nouvelle chaîne avant la fin de la précédente : seule la seconde
+        // compte, et le volume label interrompt une chaîne
+        let short = *b"NEW     TXT";
+        let dev = device_with_root(&[
+            lfn_entry(0x42, "stale", 0),
+            lfn_entry(0x41, "new.txt", lfn_checksum(&short)),
+            short_entry(&short, 0x20),
+            lfn_entry(0x41, "label", 0),
+            short_entry(b"VOLUME     ", 0x08),
+            short_entry(b"AFTER   TXT", 0x20),
+        ]);
+        let fs = Fat32Fs::new(&dev, test_geometry());
+        let names: Vec<_> = fs
+            .read_root_dir()
+            .unwrap()
+            .map(|entry| String::from(entry.unwrap().name()))
+            .collect();
+        assert_eq!(names, ["new.txt", "AFTER.TXT"]);
     }
 }
\ No newline at end of file
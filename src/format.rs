@@ -0,0 +1,325 @@
+// Création d'un volume FAT32 vierge (équivalent de `mkfs.fat -F 32`).
+//
+// La disposition suit celle des outils usuels : 32 secteurs réservés (boot
+// sector au secteur 0, FSInfo au secteur 1, copies de secours aux secteurs
+// 6 et 7), deux FAT, puis la zone de données dont le premier cluster (2)
+// est la racine.
+
+use crate::block_device::BlockDevice;
+use crate::boot_sector::{is_valid_sector_size, BACKUP_BOOT_SECTOR, MAX_SECTOR_SIZE};
+use crate::dir_entry::attr;
+use crate::error::{Fat32Error, Result};
+use crate::fat::FatEntry;
+use crate::filesystem::{check_device_size, volume_geometry};
+use crate::fsinfo::{LEAD_SIGNATURE, STRUCT_SIGNATURE, TRAIL_SIGNATURE};
+
+/// Nombre de secteurs réservés avant la première FAT.
+pub const RESERVED_SECTORS: u16 = 32;
+
+/// Nombre de copies de la FAT.
+pub const NUM_FATS: u8 = 2;
+
+/// Label d'un volume sans nom : aucune entrée de label n'est alors créée
+/// dans la racine.
+pub const NO_NAME: [u8; 11] = *b"NO NAME    ";
+
+// Secteur FSInfo et premier cluster de la racine.
+const FS_INFO_SECTOR: u16 = 1;
+const ROOT_CLUSTER: u32 = 2;
+
+// Taille du buffer de zéros : les zones à effacer sont écrites par blocs de
+// plusieurs secteurs plutôt que secteur par secteur.
+const ZERO_CHUNK: usize = 4096;
+
+/// Formate `device` en un volume FAT32 de `total_sectors` secteurs, avec des
+/// clusters de `sectors_per_cluster` secteurs et le label `volume_label`.
+///
+/// Les secteurs font `D::SECTOR_SIZE` octets. Sont écrits : la zone
+/// réservée (mise à zéro), le secteur FSInfo et les copies de secours du
+/// boot sector et du FSInfo, les deux FAT (entrées réservées et fin de
+/// chaîne pour la racine, le reste libre) et le cluster de la racine, vide
+/// sauf l'entrée de label si `volume_label` n'est pas [`NO_NAME`]. Le boot
+/// sector est écrit en dernier, suivi de sa copie de secours : un formatage
+/// interrompu ne laisse pas un volume montable à moitié initialisé, même avec
+/// [`Fat32Fs::mount_from_backup`](crate::Fat32Fs::mount_from_backup).
+///
+/// La taille des FAT est calculée pour couvrir tous les clusters de
+/// données. Le numéro de série du volume est 0, ce qui rend les images
+/// reproductibles. Les données de l'ancien volume au-delà de la racine ne
+/// sont pas effacées.
+///
+/// # Errors
+///
+/// - [`Fat32Error::UnsupportedSectorSize`] si `D::SECTOR_SIZE` n'est pas
+///   une taille de secteur FAT valide
+/// - [`Fat32Error::InvalidBpb`] si `sectors_per_cluster` n'est pas une
+///   puissance de 2 entre 1 et 128
+/// - [`Fat32Error::NotFat32`] si le volume obtenu a trop peu (moins de 65525)
+///   ou trop de clusters pour être un FAT32
+/// - [`Fat32Error::DeviceTooSmall`] si le device annonce moins de
+///   `total_sectors` secteurs
+/// - les erreurs d'écriture du device (qui doit supporter `write_sectors`)
+///
+/// # Exemples
+///
+/// ```no_run
+/// use fat32_parser::format::format;
+/// use fat32_parser::{BlockDevice, Fat32Fs};
+///
+/// fn format_card<D: BlockDevice>(device: &D, sectors: u32) -> fat32_parser::Result<()> {
+///     format(device, sectors, 8, b"SD CARD    ")?;
+///
+///     let mut boot_sector = [0u8; 512];
+///     device.read_sectors(0, 1, &mut boot_sector)?;
+///     let fs = Fat32Fs::mount(device, &boot_sector)?;
+///     assert_eq!(fs.volume_label()?, Some(*b"SD CARD\0\0\0\0"));
+///     Ok(())
+/// }
+/// ```
+pub fn format<D: BlockDevice>(
+    device: &D,
+    total_sectors: u32,
+    sectors_per_cluster: u8,
+    volume_label: &[u8; 11],
+) -> Result<()> {
+    let sector_size = D::SECTOR_SIZE;
+    if !is_valid_sector_size(sector_size as u32) {
+        return Err(Fat32Error::UnsupportedSectorSize);
+    }
+
+    let fat_size = fat_size(total_sectors, sector_size as u32, sectors_per_cluster as u32);
+    let mut boot = [0u8; MAX_SECTOR_SIZE];
+    let boot = &mut boot[..sector_size];
+    write_boot_sector(boot, total_sectors, sectors_per_cluster, fat_size, volume_label);
+
+    // La géométrie est validée comme au montage, avant toute écriture
+    let geom = volume_geometry(boot, sector_size, true)?;
+    if geom.max_valid_cluster() >= FatEntry::bad().value {
+        return Err(Fat32Error::NotFat32);
+    }
+    check_device_size(&geom, device.sector_count(), 0)?;
+
+    // Zone réservée, FSInfo et sa copie de secours
+    zero_sectors(device, 0, RESERVED_SECTORS as u32)?;
+    let mut fs_info = [0u8; MAX_SECTOR_SIZE];
+    let fs_info = &mut fs_info[..sector_size];
+    write_fs_info(fs_info, geom.total_clusters() - 1, ROOT_CLUSTER + 1);
+    device.write_sectors(FS_INFO_SECTOR as u32, 1, fs_info)?;
+    device.write_sectors(BACKUP_BOOT_SECTOR as u32 + FS_INFO_SECTOR as u32, 1, fs_info)?;
+
+    // FAT : entrées 0 et 1 réservées, racine en fin de chaîne
+    let mut first_fat_sector = [0u8; MAX_SECTOR_SIZE];
+    let first_fat_sector = &mut first_fat_sector[..sector_size];
+    let end = FatEntry::end_of_chain().value;
+    for (index, value) in [0x0FFFFF00 | boot[21] as u32, end, end].into_iter().enumerate() {
+        first_fat_sector[index * 4..index * 4 + 4].copy_from_slice(&value.to_le_bytes());
+    }
+    for fat in 0..geom.num_fats {
+        let start = geom.fat_start_lba + fat * geom.fat_size;
+        device.write_sectors(start, 1, first_fat_sector)?;
+        zero_sectors(device, start + 1, geom.fat_size - 1)?;
+    }
+
+    // Racine vide, avec l'entrée de label
+    let root_lba = geom.cluster_to_lba(ROOT_CLUSTER)?;
+    let mut sector = [0u8; MAX_SECTOR_SIZE];
+    let sector = &mut sector[..sector_size];
+    if *volume_label != NO_NAME {
+        sector[..11].copy_from_slice(volume_label);
+        sector[11] = attr::VOLUME_ID;
+    }
+    device.write_sectors(root_lba, 1, sector)?;
+    zero_sectors(device, root_lba + 1, geom.sectors_per_cluster - 1)?;
+
+    // Le boot sector rend le volume montable, sa copie aussi
+    device.write_sectors(0, 1, boot)?;
+    device.write_sectors(BACKUP_BOOT_SECTOR as u32, 1, boot)?;
+    Ok(())
+}
+
+// Remplit de zéros `count` secteurs à partir de `lba`, par blocs de
+// `ZERO_CHUNK` octets.
+fn zero_sectors<D: BlockDevice>(device: &D, lba: u32, count: u32) -> Result<()> {
+    let zero = [0u8; ZERO_CHUNK];
+    let sectors_per_chunk = (ZERO_CHUNK / D::SECTOR_SIZE) as u32;
+    let mut done = 0;
+    while done < count {
+        let chunk = sectors_per_chunk.min(count - done);
+        device.write_sectors(lba + done, chunk, &zero[..chunk as usize * D::SECTOR_SIZE])?;
+        done += chunk;
+    }
+    Ok(())
+}
+
+// Taille d'une FAT en secteurs pour que ses entrées couvrent tous les
+// clusters de données (plus les deux entrées réservées), la zone de données
+// étant ce qui reste après les secteurs réservés et les FAT.
+fn fat_size(total_sectors: u32, bytes_per_sector: u32, sectors_per_cluster: u32) -> u32 {
+    let entries_per_sector = bytes_per_sector as u64 / 4;
+    let available = (total_sectors as u64).saturating_sub(RESERVED_SECTORS as u64);
+    let numerator = available + 2 * sectors_per_cluster as u64;
+    let denominator = entries_per_sector * sectors_per_cluster as u64 + NUM_FATS as u64;
+    numerator.div_ceil(denominator) as u32
+}
+
+// Remplit le boot sector (BPB FAT32, label et signature).
+fn write_boot_sector(
+    sector: &mut [u8],
+    total_sectors: u32,
+    sectors_per_cluster: u8,
+    fat_size: u32,
+    volume_label: &[u8; 11],
+) {
+    sector[0..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+    let bytes_per_sector = sector.len() as u16;
+    sector[3..11].copy_from_slice(b"MSWIN4.1");
+    sector[11..13].copy_from_slice(&bytes_per_sector.to_le_bytes());
+    sector[13] = sectors_per_cluster;
+    sector[14..16].copy_from_slice(&RESERVED_SECTORS.to_le_bytes());
+    sector[16] = NUM_FATS;
+    sector[21] = 0xF8; // disque fixe
+    sector[24..26].copy_from_slice(&63u16.to_le_bytes()); // secteurs par piste
+    sector[26..28].copy_from_slice(&255u16.to_le_bytes()); // têtes
+    sector[32..36].copy_from_slice(&total_sectors.to_le_bytes());
+    sector[36..40].copy_from_slice(&fat_size.to_le_bytes());
+    sector[44..48].copy_from_slice(&ROOT_CLUSTER.to_le_bytes());
+    sector[48..50].copy_from_slice(&FS_INFO_SECTOR.to_le_bytes());
+    sector[50..52].copy_from_slice(&BACKUP_BOOT_SECTOR.to_le_bytes());
+    sector[64] = 0x80; // numéro de lecteur BIOS
+    sector[66] = 0x29; // signature étendue : les 3 champs suivants sont valides
+    sector[71..82].copy_from_slice(volume_label);
+    sector[82..90].copy_from_slice(b"FAT32   ");
+    sector[510] = 0x55;
+    sector[511] = 0xAA;
+}
+
+// Remplit le secteur FSInfo.
+fn write_fs_info(sector: &mut [u8], free_count: u32, next_free: u32) {
+    sector[0..4].copy_from_slice(&LEAD_SIGNATURE.to_le_bytes());
+    sector[484..488].copy_from_slice(&STRUCT_SIGNATURE.to_le_bytes());
+    sector[488..492].copy_from_slice(&free_count.to_le_bytes());
+    sector[492..496].copy_from_slice(&next_free.to_le_bytes());
+    sector[508..512].copy_from_slice(&TRAIL_SIGNATURE.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+    use crate::block_device::BlockDeviceError;
+    use crate::devices::RamDevice;
+    use crate::filesystem::Mode;
+    use crate::testing::FAT32_MIN_CLUSTERS;
+    use crate::Fat32Fs;
+
+    fn boot_sector(device: &RamDevice) -> [u8; 512] {
+        let mut sector = [0u8; 512];
+        device.read_sectors(0, 1, &mut sector).unwrap();
+        sector
+    }
+
+    #[test]
+    fn test_format_and_mount() {
+        let device = RamDevice::new(70_000);
+        // Restes d'un ancien volume
+        device.write_sectors(5, 1, &[0xAA; 512]).unwrap();
+        format(&device, 70_000, 1, b"TEST VOL   ").unwrap();
+
+        let boot = boot_sector(&device);
+        let mut backup = [0u8; 512];
+        device.read_sectors(BACKUP_BOOT_SECTOR as u32, 1, &mut backup).unwrap();
+        assert_eq!(backup, boot);
+        let mut reserved = [0u8; 512];
+        device.read_sectors(5, 1, &mut reserved).unwrap();
+        assert_eq!(reserved, [0u8; 512]);
+
+        let fs = Fat32Fs::mount_with_mode(&device, &boot, Mode::ReadWrite).unwrap();
+        assert_eq!(fs.geom.total_sectors, 70_000);
+        assert_eq!(fs.geom.fat_start_lba, 32);
+        assert!(fs.geom.total_clusters() >= FAT32_MIN_CLUSTERS);
+        // Toutes les entrées de données tiennent dans la FAT
+        assert!(fs.geom.fat_size * 128 >= fs.geom.max_valid_cluster() + 1);
+        assert_eq!(fs.verify_fats().unwrap(), None);
+        assert!(fs.read_fat_entry(2).unwrap().is_end());
+
+        let stats = fs.statfs().unwrap();
+        assert_eq!(stats.free, fs.geom.total_clusters() - 1);
+        let info = fs.read_fsinfo().unwrap();
+        assert_eq!(info.free_clusters(), Some(stats.free));
+        assert_eq!(info.next_free_hint(), Some(3));
+
+        assert_eq!(fs.volume_label().unwrap(), Some(*b"TEST VOL\0\0\0"));
+        assert_eq!(fs.read_root_dir().unwrap().count(), 0);
+
+        // Le volume est utilisable
+        fs.create_file(0, "hello.txt").unwrap();
+        let mut file = fs.open("/hello.txt").unwrap();
+        file.write(&fs, b"Hello, world!").unwrap();
+        assert_eq!(fs.read_file_to_vec("/HELLO.TXT").unwrap(), b"Hello, world!");
+    }
+
+    // Device qui journalise chaque appel à `write_sectors` (LBA, nombre).
+    struct WriteLog {
+        inner: RamDevice,
+        writes: RefCell<Vec<(u32, u32)>>,
+    }
+
+    impl BlockDevice for WriteLog {
+        fn read_sectors(&self, lba: u32, count: u32, buf: &mut [u8]) -> core::result::Result<(), BlockDeviceError> {
+            self.inner.read_sectors(lba, count, buf)
+        }
+
+        fn write_sectors(&self, lba: u32, count: u32, buf: &[u8]) -> core::result::Result<(), BlockDeviceError> {
+            self.writes.borrow_mut().push((lba, count));
+            self.inner.write_sectors(lba, count, buf)
+        }
+
+        fn sector_count(&self) -> core::result::Result<u32, BlockDeviceError> {
+            self.inner.sector_count()
+        }
+    }
+
+    #[test]
+    fn test_format_write_order() {
+        let device = WriteLog {
+            inner: RamDevice::new(70_000),
+            writes: RefCell::new(Vec::new()),
+        };
+        format(&device, 70_000, 1, &NO_NAME).unwrap();
+
+        let writes = device.writes.borrow();
+        // Le boot sector puis sa copie, en tout dernier
+        assert_eq!(writes[writes.len() - 2..], [(0, 1), (BACKUP_BOOT_SECTOR as u32, 1)]);
+        // Avant cela, seule la mise à zéro de la zone réservée les touche
+        let earlier = &writes[..writes.len() - 2];
+        assert!(!earlier.contains(&(0, 1)) && !earlier.contains(&(BACKUP_BOOT_SECTOR as u32, 1)));
+        // Les zones à effacer sont écrites par blocs de 8 secteurs
+        assert_eq!(writes[0], (0, 8));
+        let written: u32 = writes.iter().map(|&(_, count)| count).sum();
+        assert!(writes.len() < written as usize / 7);
+    }
+
+    #[test]
+    fn test_format_without_label() {
+        let device = RamDevice::new(140_000);
+        format(&device, 140_000, 2, &NO_NAME).unwrap();
+
+        let fs = Fat32Fs::mount(&device, &boot_sector(&device)).unwrap();
+        assert_eq!(fs.geom.sectors_per_cluster, 2);
+        assert_eq!(fs.volume_label().unwrap(), None);
+    }
+
+    #[test]
+    fn test_format_errors() {
+        let device = RamDevice::new(70_000);
+        assert_eq!(
+            format(&device, 70_000, 3, &NO_NAME),
+            Err(Fat32Error::InvalidBpb("sectors_per_cluster"))
+        );
+        // Trop peu de clusters : ce serait un FAT16
+        assert_eq!(format(&device, 60_000, 1, &NO_NAME), Err(Fat32Error::NotFat32));
+        assert_eq!(format(&device, 80_000, 1, &NO_NAME), Err(Fat32Error::DeviceTooSmall));
+        // Rien n'a été écrit
+        assert_eq!(boot_sector(&device), [0u8; 512]);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::boot_sector::read_u32;
+use crate::error::{Fat32Error, Result};
+
+/// Signature de tête du secteur FSInfo (offset 0).
+pub const LEAD_SIGNATURE: u32 = 0x41615252;
+
+/// Signature de structure du secteur FSInfo (offset 484).
+pub const STRUCT_SIGNATURE: u32 = 0x61417272;
+
+/// Signature de fin du secteur FSInfo (offset 508).
+pub const TRAIL_SIGNATURE: u32 = 0xAA550000;
+
+/// Valeur indiquant qu'un champ du secteur FSInfo est inconnu.
+pub const UNKNOWN: u32 = 0xFFFFFFFF;
+
+/// Contenu utile du secteur FSInfo d'un volume FAT32.
+///
+/// Ces valeurs sont de simples indications maintenues par le système qui a
+/// écrit le volume : elles peuvent être périmées.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsInfo {
+    /// Dernier nombre connu de clusters libres ([`UNKNOWN`] si inconnu).
+    pub free_count: u32,
+    /// Cluster à partir duquel chercher un cluster libre ([`UNKNOWN`] si inconnu).
+    pub next_free: u32,
+}
+
+impl FsInfo {
+    /// Décode un secteur FSInfo après avoir vérifié ses deux signatures.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`Fat32Error::InvalidFsInfo`] si le secteur est trop court ou
+    /// si une signature est incorrecte.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use fat32_parser::fsinfo::{FsInfo, LEAD_SIGNATURE, STRUCT_SIGNATURE};
+    ///
+    /// let mut sector = [0u8; 512];
+    /// sector[0..4].copy_from_slice(&LEAD_SIGNATURE.to_le_bytes());
+    /// sector[484..488].copy_from_slice(&STRUCT_SIGNATURE.to_le_bytes());
+    /// sector[488..492].copy_from_slice(&100u32.to_le_bytes());
+    /// sector[492..496].copy_from_slice(&0xFFFFFFFFu32.to_le_bytes());
+    ///
+    /// let info = FsInfo::parse(&sector).unwrap();
+    /// assert_eq!(info.free_clusters(), Some(100));
+    /// assert_eq!(info.next_free_hint(), None);
+    /// ```
+    pub fn parse(sector: &[u8]) -> Result<FsInfo> {
+        if sector.len() < 512 {
+            return Err(Fat32Error::InvalidFsInfo);
+        }
+        if read_u32(sector, 0) != LEAD_SIGNATURE || read_u32(sector, 484) != STRUCT_SIGNATURE {
+            return Err(Fat32Error::InvalidFsInfo);
+        }
+
+        Ok(FsInfo {
+            free_count: read_u32(sector, 488),
+            next_free: read_u32(sector, 492),
+        })
+    }
+
+    /// Nombre de clusters libres, ou `None` s'il est inconnu.
+    pub fn free_clusters(&self) -> Option<u32> {
+        match self.free_count {
+            UNKNOWN => None,
+            count => Some(count),
+        }
+    }
+
+    /// Indice de prochain cluster libre, ou `None` s'il est inconnu.
+    pub fn next_free_hint(&self) -> Option<u32> {
+        match self.next_free {
+            UNKNOWN => None,
+            cluster => Some(cluster),
+        }
+    }
+}
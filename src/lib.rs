@@ -1,13 +1,33 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+#[cfg(any(test, feature = "alloc"))]
+extern crate alloc;
 
 pub mod allocator;
+#[cfg(any(test, feature = "async"))]
+pub mod async_fs;
 pub mod block_device;
+pub mod cached_device;
+#[cfg(any(test, feature = "slice-device", feature = "alloc", feature = "std"))]
+pub mod devices;
 pub mod boot_sector;
 pub mod fat;
+pub mod format;
 pub mod dir_entry;
 pub mod filesystem;
+pub mod file;
+pub mod fsinfo;
+pub mod mbr;
+pub mod offset_device;
+pub mod timestamp;
 pub mod error;
 
-pub use filesystem::Fat32Fs;
+#[cfg(test)]
+mod testing;
+
+pub use filesystem::{Fat32Fs, Fat32FsBuilder};
+pub use file::File;
 pub use block_device::{BlockDevice, BlockDeviceError};
+pub use cached_device::CachedDevice;
+pub use offset_device::OffsetDevice;
 pub use error::{Fat32Error, Result};
\ No newline at end of file
@@ -0,0 +1,144 @@
+// Table des partitions MBR (Master Boot Record).
+//
+// Une image de carte SD ou de disque commence en général par un MBR, et non
+// par le secteur de boot FAT : la table de 4 entrées de 16 octets, à l'offset
+// 446 du secteur 0, indique où commence chaque partition.
+
+use crate::boot_sector::read_u32;
+use crate::error::Fat32Error;
+
+/// Offset de la table des partitions dans le secteur 0.
+pub const PARTITION_TABLE_OFFSET: usize = 446;
+
+/// Taille d'une entrée de la table des partitions, en octets.
+pub const PARTITION_ENTRY_SIZE: usize = 16;
+
+/// Type de partition FAT32 avec adressage CHS.
+pub const PARTITION_TYPE_FAT32_CHS: u8 = 0x0B;
+
+/// Type de partition FAT32 avec adressage LBA.
+pub const PARTITION_TYPE_FAT32_LBA: u8 = 0x0C;
+
+/// Entrée de la table des partitions du MBR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PartitionEntry {
+    /// Indicateur de partition active (`0x80`) ou inactive (`0x00`).
+    pub boot_flag: u8,
+    /// Type de système de fichiers déclaré (ex: `0x0C` pour FAT32 LBA).
+    pub partition_type: u8,
+    /// Premier secteur de la partition (LBA absolu sur le disque).
+    pub start_lba: u32,
+    /// Nombre de secteurs de la partition.
+    pub sector_count: u32,
+}
+
+impl PartitionEntry {
+    /// Décode une entrée de 16 octets.
+    pub fn parse(bytes: &[u8; PARTITION_ENTRY_SIZE]) -> Self {
+        Self {
+            boot_flag: bytes[0],
+            partition_type: bytes[4],
+            start_lba: read_u32(bytes, 8),
+            sector_count: read_u32(bytes, 12),
+        }
+    }
+
+    /// Vérifie si l'entrée est inutilisée (type 0 ou taille nulle).
+    pub fn is_empty(&self) -> bool {
+        self.partition_type == 0 || self.sector_count == 0
+    }
+
+    /// Vérifie si la partition est déclarée FAT32 (`0x0B` ou `0x0C`).
+    pub fn is_fat32(&self) -> bool {
+        matches!(
+            self.partition_type,
+            PARTITION_TYPE_FAT32_CHS | PARTITION_TYPE_FAT32_LBA
+        )
+    }
+}
+
+/// Lit les 4 entrées de la table des partitions du secteur 0.
+///
+/// # Errors
+///
+/// Retourne [`Fat32Error::InvalidMbr`] si la signature `0x55AA` est absente
+/// ou si un indicateur de boot n'est ni `0x00` ni `0x80` (ce qui est le cas
+/// d'un secteur de boot FAT sans table de partitions, par exemple).
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::mbr::parse_mbr;
+///
+/// let mut sector0 = [0u8; 512];
+/// sector0[446 + 4] = 0x0C;
+/// sector0[446 + 8..446 + 12].copy_from_slice(&2048u32.to_le_bytes());
+/// sector0[446 + 12..446 + 16].copy_from_slice(&65536u32.to_le_bytes());
+/// sector0[510] = 0x55;
+/// sector0[511] = 0xAA;
+///
+/// let partitions = parse_mbr(&sector0).unwrap();
+/// assert!(partitions[0].is_fat32());
+/// assert_eq!(partitions[0].start_lba, 2048);
+/// assert!(partitions[1].is_empty());
+/// ```
+pub fn parse_mbr(sector0: &[u8; 512]) -> Result<[PartitionEntry; 4], Fat32Error> {
+    if sector0[510] != 0x55 || sector0[511] != 0xAA {
+        return Err(Fat32Error::InvalidMbr);
+    }
+
+    let mut entries = [PartitionEntry::default(); 4];
+    for (i, entry) in entries.iter_mut().enumerate() {
+        let start = PARTITION_TABLE_OFFSET + i * PARTITION_ENTRY_SIZE;
+        let mut bytes = [0u8; PARTITION_ENTRY_SIZE];
+        bytes.copy_from_slice(&sector0[start..start + PARTITION_ENTRY_SIZE]);
+
+        *entry = PartitionEntry::parse(&bytes);
+        if entry.boot_flag != 0x00 && entry.boot_flag != 0x80 {
+            return Err(Fat32Error::InvalidMbr);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mbr() {
+        let mut sector0 = [0u8; 512];
+        let entry = &mut sector0[446 + 16..446 + 32];
+        entry[0] = 0x80;
+        entry[4] = PARTITION_TYPE_FAT32_CHS;
+        entry[8..12].copy_from_slice(&63u32.to_le_bytes());
+        entry[12..16].copy_from_slice(&1000u32.to_le_bytes());
+        sector0[510] = 0x55;
+        sector0[511] = 0xAA;
+
+        let partitions = parse_mbr(&sector0).unwrap();
+        assert!(partitions[0].is_empty());
+        assert_eq!(
+            partitions[1],
+            PartitionEntry {
+                boot_flag: 0x80,
+                partition_type: 0x0B,
+                start_lba: 63,
+                sector_count: 1000,
+            }
+        );
+        assert!(partitions[1].is_fat32());
+    }
+
+    #[test]
+    fn test_parse_mbr_invalid() {
+        let mut sector0 = [0u8; 512];
+        assert_eq!(parse_mbr(&sector0), Err(Fat32Error::InvalidMbr));
+
+        // Secteur de boot FAT : du code à la place de la table
+        sector0[510] = 0x55;
+        sector0[511] = 0xAA;
+        sector0[446] = 0x33;
+        assert_eq!(parse_mbr(&sector0), Err(Fat32Error::InvalidMbr));
+    }
+}
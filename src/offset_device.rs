@@ -0,0 +1,123 @@
+// Device décalé : expose un volume qui commence au secteur `base_lba` d'un
+// device plus grand (partition, image contenant plusieurs volumes...).
+//
+// Le LBA 0 vu par `Fat32Fs` correspond au LBA `base_lba` du device
+// sous-jacent, ce qui évite de propager un décalage dans tout le code FAT32.
+
+use crate::block_device::{BlockDevice, BlockDeviceError};
+
+/// Device qui ajoute `base_lba` à chaque secteur lu ou écrit.
+///
+/// # Exemple
+///
+/// ```no_run
+/// use fat32_parser::{BlockDevice, Fat32Fs, OffsetDevice};
+///
+/// fn mount_at<D: BlockDevice>(device: D, base_lba: u32) -> fat32_parser::Result<u32> {
+///     let volume = OffsetDevice::new(device, base_lba);
+///     let mut boot_sector = [0u8; 512];
+///     volume.read_sectors(0, 1, &mut boot_sector)?;
+///
+///     let fs = Fat32Fs::mount(&volume, &boot_sector)?;
+///     Ok(fs.geom.root_cluster)
+/// }
+/// ```
+pub struct OffsetDevice<D> {
+    inner: D,
+    base_lba: u32,
+}
+
+impl<D: BlockDevice> OffsetDevice<D> {
+    /// Crée un device dont le secteur 0 est le secteur `base_lba` de `inner`.
+    pub fn new(inner: D, base_lba: u32) -> Self {
+        Self { inner, base_lba }
+    }
+
+    /// Premier secteur du volume sur le device sous-jacent.
+    pub fn base_lba(&self) -> u32 {
+        self.base_lba
+    }
+
+    /// Device sous-jacent.
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Récupère le device sous-jacent.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    // Traduit un LBA relatif en LBA du device sous-jacent.
+    fn translate(&self, lba: u32) -> Result<u32, BlockDeviceError> {
+        self.base_lba
+            .checked_add(lba)
+            .ok_or(BlockDeviceError::OutOfBounds)
+    }
+}
+
+impl<D: BlockDevice> BlockDevice for OffsetDevice<D> {
+    const SECTOR_SIZE: usize = D::SECTOR_SIZE;
+
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.inner.read_sectors(self.translate(lba)?, count, buf)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.inner.write_sectors(self.translate(lba)?, count, buf)
+    }
+
+    // Secteurs situés après `base_lba` sur le device sous-jacent.
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        Ok(self.inner.sector_count()?.saturating_sub(self.base_lba))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Fat32Error;
+    use crate::filesystem::Fat32Fs;
+    use crate::testing::*;
+
+    #[test]
+    fn test_offset_read_write() {
+        let mem = MemDevice::new(3);
+        mem.data.borrow_mut()[3 * 512] = 0x42;
+        let dev = OffsetDevice::new(&mem, 3);
+
+        let mut buf = [0u8; 512];
+        dev.read_sectors(0, 1, &mut buf).unwrap();
+        assert_eq!(buf[0], 0x42);
+
+        dev.write_sectors(1, 1, &[0x24; 512]).unwrap();
+        assert_eq!(mem.data.borrow()[4 * 512], 0x24);
+        assert!(matches!(
+            dev.read_sectors(u32::MAX, 1, &mut buf),
+            Err(BlockDeviceError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn test_mount_through_offset() {
+        let mut image = vec![0u8; 512 * 100];
+        image.extend_from_slice(&fat32_image());
+        let dev = OffsetDevice::new(UnsizedDevice(crate::devices::SliceDevice::new(&image)), 100);
+
+        let mut boot_sector = [0u8; 512];
+        dev.read_sectors(0, 1, &mut boot_sector).unwrap();
+        let fs = Fat32Fs::mount(&dev, &boot_sector).unwrap();
+        assert_eq!(fs.read_file_to_vec("/hello.txt").unwrap(), b"Hello, world!");
+        assert_eq!(fs.open("/missing").map(|_| ()), Err(Fat32Error::NotFound));
+    }
+}
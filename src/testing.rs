@@ -0,0 +1,369 @@
+// Outils partagés par les tests unitaires : un device en mémoire et de quoi
+// fabriquer à la main des entrées de répertoire.
+
+use core::cell::{Cell, RefCell};
+
+use crate::block_device::{BlockDevice, BlockDeviceError};
+use crate::boot_sector::Fat32Geometry;
+use crate::dir_entry::LFN_ATTRIBUTE;
+
+// Device en mémoire : secteurs de 512 octets stockés à la suite.
+pub struct MemDevice {
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl MemDevice {
+    // Image compatible avec `test_geometry` : FAT au secteur 1 et
+    // cluster N au secteur N, pour `clusters` clusters de données.
+    pub fn new(clusters: usize) -> Self {
+        Self {
+            data: RefCell::new(vec![0u8; 512 * (2 + clusters)]),
+        }
+    }
+
+    // Écrit la valeur brute de l'entrée FAT de `cluster`.
+    pub fn set_fat(&mut self, cluster: u32, value: u32) {
+        let start = 512 + cluster as usize * 4;
+        self.data.get_mut()[start..start + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    // Écrit des entrées de répertoire au début de `cluster`.
+    pub fn write_entries(&mut self, cluster: u32, entries: &[[u8; 32]]) {
+        for (i, entry) in entries.iter().enumerate() {
+            let start = cluster as usize * 512 + i * 32;
+            self.data.get_mut()[start..start + 32].copy_from_slice(entry);
+        }
+    }
+
+    // Écrit des données brutes au début de `cluster`.
+    pub fn write_data(&mut self, cluster: u32, bytes: &[u8]) {
+        let start = cluster as usize * 512;
+        self.data.get_mut()[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+impl BlockDevice for MemDevice {
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        let data = self.data.borrow();
+        let start = lba as usize * 512;
+        let end = start + count as usize * 512;
+        if end > data.len() {
+            return Err(BlockDeviceError::OutOfBounds);
+        }
+        buf[..end - start].copy_from_slice(&data[start..end]);
+        Ok(())
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        let mut data = self.data.borrow_mut();
+        let start = lba as usize * 512;
+        let end = start + count as usize * 512;
+        if end > data.len() {
+            return Err(BlockDeviceError::OutOfBounds);
+        }
+        data[start..end].copy_from_slice(&buf[..end - start]);
+        Ok(())
+    }
+}
+
+// Enveloppe qui compte les appels à `read_sectors` du device sous-jacent.
+pub struct CountingDevice<D> {
+    inner: D,
+    reads: Cell<usize>,
+}
+
+impl<D: BlockDevice> CountingDevice<D> {
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            reads: Cell::new(0),
+        }
+    }
+
+    pub fn reads(&self) -> usize {
+        self.reads.get()
+    }
+
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: BlockDevice> BlockDevice for CountingDevice<D> {
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.reads.set(self.reads.get() + 1);
+        self.inner.read_sectors(lba, count, buf)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.inner.write_sectors(lba, count, buf)
+    }
+
+    fn sector_count(&self) -> Result<u32, BlockDeviceError> {
+        self.inner.sector_count()
+    }
+}
+
+// Enveloppe qui masque la taille du device sous-jacent : `mount` ne vérifie
+// alors pas que le volume y tient, ce qui permet de monter les images
+// réduites de `fat32_image`.
+pub struct UnsizedDevice<D>(pub D);
+
+impl<D: BlockDevice> BlockDevice for UnsizedDevice<D> {
+    const SECTOR_SIZE: usize = D::SECTOR_SIZE;
+
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.0.read_sectors(lba, count, buf)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.0.write_sectors(lba, count, buf)
+    }
+}
+
+// Enveloppe dont les écritures qui touchent le secteur `fail_lba` échouent
+// avec `IoError`, pour tester les chemins d'erreur.
+pub struct FailingWrites<D> {
+    pub inner: D,
+    pub fail_lba: u32,
+}
+
+impl<D: BlockDevice> BlockDevice for FailingWrites<D> {
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        self.inner.read_sectors(lba, count, buf)
+    }
+
+    fn write_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &[u8],
+    ) -> Result<(), BlockDeviceError> {
+        if (lba..lba.saturating_add(count)).contains(&self.fail_lba) {
+            return Err(BlockDeviceError::IoError);
+        }
+        self.inner.write_sectors(lba, count, buf)
+    }
+}
+
+// Géométrie minimale : FAT au secteur 1, données (cluster 2) au secteur 2,
+// un secteur par cluster.
+pub fn test_geometry() -> Fat32Geometry {
+    Fat32Geometry {
+        first_data_sector: 2,
+        fat_start_lba: 1,
+        root_cluster: 2,
+        sectors_per_cluster: 1,
+        bytes_per_sector: 512,
+        num_fats: 1,
+        fat_size: 1,
+        // 126 clusters (2 à 127) : toutes les entrées tiennent dans le secteur de FAT
+        total_sectors: 128,
+        fs_info_sector: 0,
+    }
+}
+
+// Secteur FSInfo valide annonçant `free_count` clusters libres et
+// `next_free` comme indice de recherche.
+pub fn fsinfo_sector(free_count: u32, next_free: u32) -> [u8; 512] {
+    let mut sector = [0u8; 512];
+    sector[0..4].copy_from_slice(&0x41615252u32.to_le_bytes());
+    sector[484..488].copy_from_slice(&0x61417272u32.to_le_bytes());
+    sector[488..492].copy_from_slice(&free_count.to_le_bytes());
+    sector[492..496].copy_from_slice(&next_free.to_le_bytes());
+    sector[510] = 0x55;
+    sector[511] = 0xAA;
+    sector
+}
+
+// Construit un device dont le répertoire racine (cluster 2) contient `entries`.
+pub fn device_with_root(entries: &[[u8; 32]]) -> MemDevice {
+    let mut dev = MemDevice::new(1);
+    dev.set_fat(2, 0x0FFFFFFF);
+    dev.write_entries(2, entries);
+    dev
+}
+
+pub fn short_entry(name: &[u8; 11], attributes: u8) -> [u8; 32] {
+    file_entry(name, attributes, 0, 0)
+}
+
+// Nom 8.3 numéroté `F00     TXT`, `F01     TXT`, ... pour remplir un
+// répertoire (`i` < 100).
+pub fn numbered_name(i: usize) -> [u8; 11] {
+    let mut name = *b"F00     TXT";
+    name[1] += (i / 10) as u8;
+    name[2] += (i % 10) as u8;
+    name
+}
+
+// Remplit le début du cluster `cluster` avec `count` fichiers numérotés
+// (voir `numbered_name`).
+pub fn fill_dir(dev: &mut MemDevice, cluster: u32, count: usize) {
+    let entries: Vec<[u8; 32]> = (0..count)
+        .map(|i| short_entry(&numbered_name(i), 0x20))
+        .collect();
+    dev.write_entries(cluster, &entries);
+}
+
+pub fn file_entry(name: &[u8; 11], attributes: u8, cluster: u32, size: u32) -> [u8; 32] {
+    let mut entry = [0u8; 32];
+    entry[..11].copy_from_slice(name);
+    entry[11] = attributes;
+    entry[20..22].copy_from_slice(&((cluster >> 16) as u16).to_le_bytes());
+    entry[26..28].copy_from_slice(&(cluster as u16).to_le_bytes());
+    entry[28..32].copy_from_slice(&size.to_le_bytes());
+    entry
+}
+
+pub fn lfn_entry(order: u8, chars: &str, checksum: u8) -> [u8; 32] {
+    const OFFSETS: [usize; 13] = [1, 3, 5, 7, 9, 14, 16, 18, 20, 22, 24, 28, 30];
+    let mut units = [0xFFFFu16; 13];
+    let mut len = 0;
+    for (unit, c) in units.iter_mut().zip(chars.encode_utf16()) {
+        *unit = c;
+        len += 1;
+    }
+    if len < 13 {
+        units[len] = 0x0000;
+    }
+
+    let mut entry = [0u8; 32];
+    entry[0] = order;
+    entry[11] = LFN_ATTRIBUTE;
+    entry[13] = checksum;
+    for (unit, &offset) in units.iter().zip(OFFSETS.iter()) {
+        entry[offset..offset + 2].copy_from_slice(&unit.to_le_bytes());
+    }
+    entry
+}
+
+// Image FAT32 minimale : boot sector (secteur 0), une FAT (secteur 1),
+// répertoire racine au cluster 2 (secteur 2) contenant HELLO.TXT, dont les
+// données occupent le cluster 3.
+//
+// Le boot sector annonce 65525 clusters, le minimum pour être reconnu comme
+// FAT32, mais seuls les 8 premiers secteurs sont présents dans l'image : les
+// tests la montent derrière un `UnsizedDevice`.
+pub fn fat32_image() -> Vec<u8> {
+    fat32_image_with_sector_size(512)
+}
+
+// Plus petit nombre de clusters d'un volume FAT32.
+pub const FAT32_MIN_CLUSTERS: u32 = 65525;
+
+// Même image que `fat32_image`, avec des secteurs de `bps` octets.
+pub fn fat32_image_with_sector_size(bps: usize) -> Vec<u8> {
+    let mut image = vec![0u8; bps * 8];
+
+    let boot = &mut image[..512];
+    boot[0..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+    boot[3..11].copy_from_slice(b"MSWIN4.1");
+    boot[11..13].copy_from_slice(&(bps as u16).to_le_bytes()); // bytes_per_sector
+    boot[13] = 1; // sectors_per_cluster
+    boot[14..16].copy_from_slice(&1u16.to_le_bytes()); // reserved_sector_count
+    boot[16] = 1; // num_fats
+    boot[21] = 0xF8; // media
+    boot[32..36].copy_from_slice(&(2 + FAT32_MIN_CLUSTERS).to_le_bytes()); // total_sectors_32
+    boot[36..40].copy_from_slice(&1u32.to_le_bytes()); // fat_size_32
+    boot[44..48].copy_from_slice(&2u32.to_le_bytes()); // root_cluster
+    boot[66] = 0x29; // signature étendue
+    boot[71..82].copy_from_slice(b"NO NAME    ");
+    boot[82..90].copy_from_slice(b"FAT32   ");
+    boot[510] = 0x55;
+    boot[511] = 0xAA;
+
+    let fat = &mut image[bps..2 * bps];
+    fat[0..4].copy_from_slice(&0x0FFFFFF8u32.to_le_bytes());
+    fat[4..8].copy_from_slice(&0x0FFFFFFFu32.to_le_bytes());
+    fat[8..12].copy_from_slice(&0x0FFFFFFFu32.to_le_bytes()); // racine
+    fat[12..16].copy_from_slice(&0x0FFFFFFFu32.to_le_bytes()); // HELLO.TXT
+
+    image[2 * bps..2 * bps + 32].copy_from_slice(&file_entry(b"HELLO   TXT", 0x20, 3, 13));
+    image[3 * bps..3 * bps + 13].copy_from_slice(b"Hello, world!");
+    image
+}
+
+// Device en lecture seule dont les secteurs font `SECTOR` octets.
+pub struct SizedDevice<const SECTOR: usize> {
+    pub data: Vec<u8>,
+}
+
+impl<const SECTOR: usize> BlockDevice for SizedDevice<SECTOR> {
+    const SECTOR_SIZE: usize = SECTOR;
+
+    fn read_sectors(
+        &self,
+        lba: u32,
+        count: u32,
+        buf: &mut [u8],
+    ) -> Result<(), BlockDeviceError> {
+        let start = lba as usize * SECTOR;
+        let end = start + count as usize * SECTOR;
+        if end > self.data.len() {
+            return Err(BlockDeviceError::OutOfBounds);
+        }
+        buf[..end - start].copy_from_slice(&self.data[start..end]);
+        Ok(())
+    }
+}
+
+// Exécute une future jusqu'au bout en la sondant en boucle : suffisant pour
+// les devices de test, dont les futures sont prêtes immédiatement.
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    // SAFETY: les fonctions de la vtable n'utilisent pas le pointeur de données
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = core::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
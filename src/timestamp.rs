@@ -0,0 +1,224 @@
+/// Date et heure décodées depuis le format DOS utilisé par FAT32.
+///
+/// Les dates DOS commencent en 1980 et ont une résolution de 2 secondes ;
+/// seule la date de création dispose d'un complément en centièmes.
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::timestamp::Timestamp;
+///
+/// let ts = Timestamp::from_dos(0x4A21, 0x0021, 0);
+/// assert_eq!((ts.year, ts.month, ts.day), (2017, 1, 1));
+/// assert_eq!((ts.hour, ts.minute, ts.second), (0, 1, 2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timestamp {
+    /// Année complète (1980 à 2107).
+    pub year: u16,
+    /// Mois (1 à 12).
+    pub month: u8,
+    /// Jour du mois (1 à 31).
+    pub day: u8,
+    /// Heure (0 à 23).
+    pub hour: u8,
+    /// Minute (0 à 59).
+    pub minute: u8,
+    /// Seconde (0 à 59).
+    pub second: u8,
+    /// Centièmes de seconde (0 à 99).
+    pub centiseconds: u8,
+}
+
+impl Timestamp {
+    /// Décode une date et une heure DOS, plus l'octet de dixièmes optionnel
+    /// (unités de 10 ms, 0 à 199) qui complète la résolution de 2 secondes.
+    pub fn from_dos(date: u16, time: u16, tenths: u8) -> Self {
+        let (year, month, day) = decode_date(date);
+        let extra = tenths.min(199);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (time >> 11) as u8,
+            minute: ((time >> 5) & 0x3F) as u8,
+            second: (time & 0x1F) as u8 * 2 + extra / 100,
+            centiseconds: extra % 100,
+        }
+    }
+
+    /// Encode la date et l'heure au format DOS : `(date, heure, dixièmes)`,
+    /// inverse de [`Timestamp::from_dos`].
+    ///
+    /// L'année est ramenée dans la plage représentable (1980 à 2107) ; les
+    /// secondes impaires et les centièmes sont portés par l'octet de
+    /// dixièmes (0 à 199).
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use fat32_parser::timestamp::Timestamp;
+    ///
+    /// let ts = Timestamp::from_dos(0x4A21, 0x0021, 150);
+    /// assert_eq!(ts.to_dos(), (0x4A21, 0x0021, 150));
+    /// ```
+    pub fn to_dos(&self) -> (u16, u16, u8) {
+        let year = self.year.clamp(1980, 2107) - 1980;
+        let date = (year << 9) | ((self.month as u16 & 0x0F) << 5) | (self.day as u16 & 0x1F);
+        let time = ((self.hour as u16 & 0x1F) << 11)
+            | ((self.minute as u16 & 0x3F) << 5)
+            | ((self.second as u16 / 2) & 0x1F);
+        let tenths = (self.second % 2) * 100 + self.centiseconds.min(99);
+        (date, time, tenths)
+    }
+}
+
+/// Début de l'ère DOS (1er janvier 1980, minuit), date la plus ancienne
+/// représentable dans une entrée de répertoire.
+pub const DOS_EPOCH: Timestamp = Timestamp {
+    year: 1980,
+    month: 1,
+    day: 1,
+    hour: 0,
+    minute: 0,
+    second: 0,
+    centiseconds: 0,
+};
+
+/// Source de l'heure courante utilisée pour dater les fichiers créés ou
+/// modifiés.
+///
+/// Un crate `no_std` n'a pas d'horloge : c'est à l'appelant d'en fournir une
+/// (RTC, horloge du système hôte...). Sans horloge, les écritures utilisent
+/// [`DOS_EPOCH`].
+pub trait Clock {
+    /// Date et heure courantes.
+    fn now(&self) -> Timestamp;
+}
+
+/// Horloge arrêtée qui retourne toujours la même date, pratique pour les
+/// tests et les images reproductibles.
+///
+/// # Exemples
+///
+/// ```
+/// use fat32_parser::timestamp::{Clock, FixedClock, DOS_EPOCH};
+///
+/// assert_eq!(FixedClock(DOS_EPOCH).now(), DOS_EPOCH);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixedClock(pub Timestamp);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+/// Horloge du système hôte (`SystemTime`), en UTC (feature `std`).
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemClock;
+
+#[cfg(any(test, feature = "std"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        let elapsed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        from_unix(elapsed.as_secs(), elapsed.subsec_millis())
+    }
+}
+
+// Convertit un instant Unix (secondes, millisecondes) en date UTC.
+#[cfg(any(test, feature = "std"))]
+fn from_unix(secs: u64, millis: u32) -> Timestamp {
+    let days = secs / 86_400;
+    let rest = secs % 86_400;
+
+    // Jours depuis le 1970-01-01 vers (année, mois, jour), algorithme de
+    // H. Hinnant (années commençant en mars)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    Timestamp {
+        year: year.min(u16::MAX as u64) as u16,
+        month,
+        day,
+        hour: (rest / 3600) as u8,
+        minute: (rest / 60 % 60) as u8,
+        second: (rest % 60) as u8,
+        centiseconds: (millis / 10) as u8,
+    }
+}
+
+/// Décode une date DOS en `(année, mois, jour)`.
+///
+/// Bits 15-9 : année depuis 1980, bits 8-5 : mois, bits 4-0 : jour.
+pub fn decode_date(date: u16) -> (u16, u8, u8) {
+    (
+        1980 + (date >> 9),
+        ((date >> 5) & 0x0F) as u8,
+        (date & 0x1F) as u8,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_date() {
+        assert_eq!(decode_date(0x4A21), (2017, 1, 1));
+        assert_eq!(decode_date(0x0021), (1980, 1, 1));
+    }
+
+    #[test]
+    fn test_from_dos_with_tenths() {
+        // 23:59:58 + 150 centièmes = 23:59:59.50
+        let time = (23 << 11) | (59 << 5) | 29;
+        let ts = Timestamp::from_dos(0x4A21, time, 150);
+        assert_eq!((ts.hour, ts.minute, ts.second, ts.centiseconds), (23, 59, 59, 50));
+    }
+
+    #[test]
+    fn test_to_dos() {
+        let ts = Timestamp {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 13,
+            minute: 37,
+            second: 43,
+            centiseconds: 25,
+        };
+        let (date, time, tenths) = ts.to_dos();
+        assert_eq!(Timestamp::from_dos(date, time, tenths), ts);
+        assert_eq!(tenths, 125);
+
+        assert_eq!(DOS_EPOCH.to_dos(), (0x0021, 0, 0));
+        // Années hors plage ramenées aux bornes
+        let early = Timestamp { year: 1970, ..DOS_EPOCH };
+        assert_eq!(early.to_dos().0 >> 9, 0);
+        let late = Timestamp { year: 2200, ..DOS_EPOCH };
+        assert_eq!(late.to_dos().0 >> 9, 127);
+    }
+
+    #[test]
+    fn test_from_unix() {
+        assert_eq!(from_unix(0, 0), Timestamp { year: 1970, ..DOS_EPOCH });
+        // 2024-02-29 13:37:43.250 UTC
+        let ts = from_unix(1_709_213_863, 250);
+        assert_eq!((ts.year, ts.month, ts.day), (2024, 2, 29));
+        assert_eq!((ts.hour, ts.minute, ts.second, ts.centiseconds), (13, 37, 43, 25));
+        assert!(SystemClock.now().year >= 2024);
+    }
+}